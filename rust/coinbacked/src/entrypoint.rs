@@ -5,6 +5,7 @@
 use solana_program::
 {
     account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, pubkey::Pubkey,
+    program_error::PrintProgramError,
 };
 
 use solana_security_txt::security_txt;
@@ -25,7 +26,13 @@ fn process_instruction(
     instruction_data: &[u8],
 ) -> ProgramResult 
 {
-    crate::processor::process_instruction(program_id, accounts, instruction_data)
+    if let Err(error) = crate::processor::process_instruction(program_id, accounts, instruction_data)
+    {
+        // catch the error so we can print it
+        error.print::<crate::error::CoinbackedError>();
+        return Err(error);
+    }
+    Ok(())
 }
 
 // https://medium.com/coinmonks/understanding-arithmetic-overflow-underflows-in-rust-and-solana-smart-contracts-9f3c9802dc45
@@ -1,17 +1,307 @@
 //! Error types
 
 use num_derive::FromPrimitive;
-use solana_program::{decode_error::DecodeError, program_error::ProgramError};
+use num_traits::FromPrimitive;
+use solana_program::{decode_error::DecodeError, msg, program_error::{PrintProgramError, ProgramError}};
 use thiserror::Error;
 
-/// Errors that may be returned by the TokenLending program.
+/// Errors that may be returned by the Coinbacked program.
+/// Numeric codes are stable, new variants are only ever appended.
 #[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
 pub enum CoinbackedError 
 {
 
     /// Math operation error
     #[error("Math operation erorr")]
-    MathError,
+    MathError = 0,
+
+    // instruction data
+
+    /// Unknown operation
+    #[error("Unknown instruction")]
+    InvalidInstruction = 1,
+
+    /// Instruction data has wrong size for operation
+    #[error("Instruction data has wrong size")]
+    InvalidInstructionLength = 2,
+
+    /// Terms of service signature is not valid UTF-8 or too long
+    #[error("Invalid terms of service signature")]
+    InvalidSignedTos = 3,
+
+    // accounts in general
+
+    /// Required signer did not sign
+    #[error("Account is not signer")]
+    AccountNotSigner = 4,
+
+    /// Required account is not writable
+    #[error("Account is not writable")]
+    AccountNotWritable = 5,
+
+    /// System program or rent sysvar passed is not the expected one
+    #[error("Invalid system program or rent sysvar")]
+    InvalidSystemProgram = 6,
+
+    /// Token program passed is not the expected one
+    #[error("Invalid token program")]
+    InvalidTokenProgram = 7,
+
+    // token accounts
+
+    /// Token account does not belong to mint
+    #[error("Token account does not belong to mint")]
+    TokenAccountMintMismatch = 8,
+
+    /// Token account does not belong to signer
+    #[error("Token account does not belong to signer")]
+    TokenAccountOwnerMismatch = 9,
+
+    /// Token account holds no tokens
+    #[error("Token account is empty")]
+    TokenAccountEmpty = 10,
+
+    /// Mint has no supply
+    #[error("Mint supply is zero")]
+    MintSupplyZero = 11,
+
+    // backing account
+
+    /// Backing account is not the PDA of the mint
+    #[error("Backing account is not valid PDA for mint")]
+    InvalidBackingAccountAddress = 12,
+
+    /// Backing account exists although it should not
+    #[error("Backing account exists already")]
+    BackingAccountAlreadyExists = 13,
+
+    /// Backing account data is missing or malformed
+    #[error("Invalid backing account data")]
+    InvalidBackingAccountData = 14,
+
+    /// Backing account points to a different mint
+    #[error("Backing account not pointing to mint")]
+    BackingAccountMintMismatch = 15,
+
+    /// Stored backing account bump is not the canonical one
+    #[error("Backing account bump is incorrect")]
+    InvalidBackingAccountBump = 16,
+
+    /// Backing account cannot cover payout
+    #[error("Backing account has insufficient funds")]
+    InsufficientBackingFunds = 17,
+
+    // treasury account
+
+    /// Treasury account is not the PDA of the program
+    #[error("Treasury account is not valid PDA")]
+    InvalidTreasuryAccountAddress = 18,
+
+    /// Treasury account data is missing or malformed
+    #[error("Invalid treasury account data")]
+    InvalidTreasuryAccountData = 19,
+
+    /// Stored treasury account bump is not the canonical one
+    #[error("Treasury account bump is incorrect")]
+    InvalidTreasuryAccountBump = 20,
+
+    /// Treasury account has not been created yet
+    #[error("Treasury account does not exist")]
+    TreasuryAccountMissing = 21,
+
+    /// Transfer would cut into rent excemption of treasury
+    #[error("Treasury account has insufficient funds")]
+    InsufficientTreasuryFunds = 22,
+
+    /// Payer cannot cover protocol fee
+    #[error("Insufficient funds for protocol fee")]
+    InsufficientFundsForProtocolFee = 23,
+
+    // admin
+
+    /// Program account is not this program
+    #[error("Program account has incorrect ID")]
+    InvalidProgramAccount = 24,
+
+    /// Program executable data account does not belong to program account
+    #[error("Program executable data account does not fit program account")]
+    InvalidProgramExecutableDataAccount = 25,
+
+    /// Signer is not the program update authority
+    #[error("Signer is not update authority")]
+    NotUpdateAuthority = 26,
+
+    // events
+
+    /// Event data is malformed or of unknown version
+    #[error("Invalid event data")]
+    InvalidEventData = 27,
+
+    /// Token amount is larger than mint supply
+    #[error("Token amount exceeds supply")]
+    AmountExceedsSupply = 28,
+
+    /// Return data is malformed
+    #[error("Invalid return data")]
+    InvalidReturnData = 29,
+
+    /// Strict validation of backing account failed
+    #[error("Backing account validation failed")]
+    ValidationFailed = 30,
+
+    /// Payout is lower than the minimum requested
+    #[error("Payout below minimum")]
+    PayoutBelowMinimum = 31,
+
+    /// Recipient account is not a writable system account
+    #[error("Invalid recipient account")]
+    InvalidRecipientAccount = 32,
+
+    /// Delegate is not approved for the token amount
+    #[error("Insufficient delegated amount")]
+    InsufficientDelegatedAmount = 33,
+
+    /// Not enough multisig signers
+    #[error("Multisig threshold not met")]
+    MultisigThresholdNotMet = 34,
+
+    /// Token-2022 mint carries an extension that breaks the payout guarantee
+    #[error("Unsupported mint extension")]
+    UnsupportedMintExtension = 35,
+
+    // collateral
+
+    /// Collateral vault is not the associated token account of the backing account
+    #[error("Invalid collateral vault account")]
+    InvalidCollateralVaultAccount = 36,
+
+    /// Backing account is not backed by the given collateral, or not by collateral at all
+    #[error("Backing account collateral mismatch")]
+    BackingCollateralMismatch = 37,
+
+    /// Basket accounts or amounts do not match the collateral of the backing account
+    #[error("Invalid basket accounts")]
+    InvalidBasketAccounts = 38,
+
+    // backer record
+
+    /// Backer record is not the PDA of mint and contributor
+    #[error("Backer record is not valid PDA for mint and contributor")]
+    InvalidBackerRecordAddress = 39,
+
+    /// Backer record data is malformed or belongs to another mint or contributor
+    #[error("Invalid backer record data")]
+    InvalidBackerRecordData = 40,
+
+    /// Account holding program state is not owned by the program
+    #[error("Account not owned by program")]
+    InvalidAccountOwner = 41,
+
+    // config account
+
+    /// Config account is not the PDA of the program
+    #[error("Config account is not valid PDA")]
+    InvalidConfigAccountAddress = 42,
+
+    /// Config account data is missing or malformed
+    #[error("Invalid config account data")]
+    InvalidConfigAccountData = 43,
+
+    /// Config account has not been initialised yet
+    #[error("Config account does not exist")]
+    ConfigAccountMissing = 44,
+
+    /// Config account has been initialised already
+    #[error("Config account exists already")]
+    ConfigAccountAlreadyExists = 45,
+
+    /// Signer is not the admin stored in the config account
+    #[error("Signer is not admin")]
+    NotAdmin = 46,
+
+    /// Backing amount is lower than the configured minimum
+    #[error("Backing amount below minimum")]
+    BackingBelowMinimum = 47,
+
+    /// Fee rate exceeds 10000 basis points
+    #[error("Invalid fee rate")]
+    InvalidFeeRate = 48,
+
+    /// Exit fee exceeds the maximum
+    #[error("Invalid exit fee")]
+    InvalidExitFee = 49,
+
+    /// Signer is not the admin proposed in the config account
+    #[error("Signer is not pending admin")]
+    NotPendingAdmin = 50,
+
+    // treasury multisig
+
+    /// Treasury signers are duplicated, too many or threshold does not fit
+    #[error("Invalid treasury signers")]
+    InvalidTreasurySigners = 51,
+
+    /// Signer is not one of the treasury signers
+    #[error("Signer is not treasury signer")]
+    NotTreasurySigner = 52,
+
+    /// Withdrawal proposal is not the PDA of its id
+    #[error("Withdrawal proposal is not valid PDA")]
+    InvalidWithdrawalProposalAddress = 53,
+
+    /// Withdrawal proposal data is missing or malformed
+    #[error("Invalid withdrawal proposal data")]
+    InvalidWithdrawalProposalData = 54,
+
+    /// Withdrawal proposal has expired
+    #[error("Withdrawal proposal expired")]
+    WithdrawalProposalExpired = 55,
+
+    /// Treasury signers are configured, withdrawals and signer changes require approval
+    #[error("Treasury withdrawal requires multisig approval")]
+    TreasuryMultisigRequired = 56,
+
+    /// No admin transfer matching amount and receiver is queued
+    #[error("Treasury transfer not queued")]
+    TreasuryTransferNotQueued = 57,
+
+    /// Delay of treasury withdrawal has not passed yet
+    #[error("Treasury withdrawal delay not passed")]
+    TreasuryWithdrawalDelayed = 58,
+
+    /// Withdrawal would exceed the treasury epoch cap
+    #[error("Treasury epoch cap exceeded")]
+    TreasuryEpochCapExceeded = 59,
+
+    // pause
+
+    /// Operation is paused by admin
+    #[error("Operation paused")]
+    OperationPaused = 60,
+
+    /// Pause flags or burn pause window are not valid
+    #[error("Invalid pause")]
+    InvalidPause = 61,
+
+    /// Creator of backing account is not passed or not writable
+    #[error("Invalid creator account")]
+    InvalidCreatorAccount = 62,
+
+    /// Referrer is not a writable, funded system account
+    #[error("Invalid referrer account")]
+    InvalidReferrerAccount = 63,
+
+    /// Burn fee rate or flat burn fee exceeds hard cap
+    #[error("Burn fee above cap")]
+    BurnFeeAboveCap = 64,
+
+    /// Loader program is not the upgradeable BPF loader
+    #[error("Invalid loader program")]
+    InvalidLoaderProgram = 65,
+
+    /// Withdrawal proposal can still be approved and executed
+    #[error("Withdrawal proposal not expired")]
+    WithdrawalProposalNotExpired = 66,
 
 }
 
@@ -27,4 +317,14 @@ impl<T> DecodeError<T> for CoinbackedError
     fn type_of() -> &'static str {
         "Coindbacked Error"
     }
-}
\ No newline at end of file
+}
+
+impl PrintProgramError for CoinbackedError
+{
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!("Error: {}", self);
+    }
+}
@@ -0,0 +1,557 @@
+//! Structured program events, emitted via `sol_log_data`
+
+use solana_program::
+{
+    msg, pubkey::Pubkey, program_error::ProgramError, log::sol_log_data,
+};
+
+use arrayref::
+{
+    array_ref, array_refs, mut_array_refs, array_mut_ref
+};
+
+use crate::
+{
+    utils::*,
+    error::CoinbackedError,
+};
+
+/// Event layout version 1: first layout
+pub const CO_EVENT_VERSION_INITIAL: u8 = 1;
+/// Event layout version 2: treasury events carry epoch withdrawn, epoch cap and delay, see `TreasuryEvent`
+pub const CO_EVENT_VERSION_TREASURY_LIMITS: u8 = 2;
+/// Current event layout version, first byte of event data; data of earlier versions still unpacks
+pub const CO_EVENT_VERSION: u8 = CO_EVENT_VERSION_TREASURY_LIMITS;
+/// Length of layout version in event data
+pub const CO_EVENT_VERSION_BYTE_LEN: usize = 1;
+/// Length of event kind in event data, follows the version
+pub const CO_EVENT_KIND_BYTE_LEN: usize = 1;
+/// Length of event data
+pub const CO_EVENT_BYTE_LEN: usize = CO_EVENT_VERSION_BYTE_LEN + CO_EVENT_KIND_BYTE_LEN + 3 * CO_PUBKEY_BYTE_LEN + 2 * CO_LAMPORTS_BYTE_LEN + 3 * CO_TOKEN_AMOUNT_BYTE_LEN;
+
+/// Event kind code of `EventKind::CreateBackingAccount`
+pub const CO_EVENT_CREATE_BACKING_ACCOUNT: u8 = 0;
+/// Event kind code of `EventKind::AddToBalanceOfBackingAccount`
+pub const CO_EVENT_ADD_TO_BALANCE_OF_BACKING_ACCOUNT: u8 = 1;
+/// Event kind code of `EventKind::BurnTokenAndFreeBalance`
+pub const CO_EVENT_BURN_AND_FREE_BALANCE: u8 = 2;
+/// Event kind code of `EventKind::CleanAccountsAfterBurning`
+pub const CO_EVENT_CLEAN_ACCOUNTS_AFTER_BURNING: u8 = 3;
+/// Event kind code of `EventKind::AdminTransferFromTreasuryAccount`
+pub const CO_EVENT_ADMIN_TRANSFER_FROM_TREASURY_ACCOUNT: u8 = 4;
+/// Event kind code of `EventKind::CreateCollateralBackingAccount`
+pub const CO_EVENT_CREATE_COLLATERAL_BACKING_ACCOUNT: u8 = 5;
+/// Event kind code of `EventKind::AddCollateralToBalanceOfBackingAccount`
+pub const CO_EVENT_ADD_COLLATERAL_TO_BALANCE_OF_BACKING_ACCOUNT: u8 = 6;
+/// Event kind code of `EventKind::BurnTokenAndFreeCollateral`
+pub const CO_EVENT_BURN_AND_FREE_COLLATERAL: u8 = 7;
+/// Event kind code of `EventKind::CreateBasketBackingAccount`
+pub const CO_EVENT_CREATE_BASKET_BACKING_ACCOUNT: u8 = 8;
+/// Event kind code of `EventKind::BurnTokenAndFreeBasket`
+pub const CO_EVENT_BURN_AND_FREE_BASKET: u8 = 9;
+/// Event kind code of `EventKind::FreeBasketCollateral`
+pub const CO_EVENT_FREE_BASKET_COLLATERAL: u8 = 10;
+/// Event kind code of `EventKind::MigrateBackingAccount`
+pub const CO_EVENT_MIGRATE_BACKING_ACCOUNT: u8 = 11;
+/// Event kind code of `EventKind::ExecuteTreasuryWithdrawal`
+pub const CO_EVENT_EXECUTE_TREASURY_WITHDRAWAL: u8 = 12;
+/// Event kind code of `EventKind::QueueTreasuryTransfer`
+pub const CO_EVENT_QUEUE_TREASURY_TRANSFER: u8 = 13;
+/// Event kind code of `EventKind::ProtocolFeeShare`
+pub const CO_EVENT_PROTOCOL_FEE_SHARE: u8 = 14;
+
+/// Kind of event, determines how the actor and counterparty fields are to be read
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventKind
+{
+    /// backing account created, actor is the payer, counterparty the backing account
+    CreateBackingAccount,
+
+    /// balance of backing account increased, actor is the payer, counterparty the backing account
+    AddToBalanceOfBackingAccount,
+
+    /// token burned and balance freed, actor is the token owner, counterparty the receiver of the payout
+    BurnTokenAndFreeBalance,
+
+    /// accounts cleaned after burning, actor is the token owner, counterparty the treasury receiving the remaining backing balance
+    CleanAccountsAfterBurning,
+
+    /// admin transfer from treasury, carried by a `TreasuryEvent`; actor is the admin, counterparty the receiver
+    AdminTransferFromTreasuryAccount,
+
+    /// collateral backed account created, actor is the payer, counterparty the collateral vault; lamports field holds collateral amount
+    CreateCollateralBackingAccount,
+
+    /// collateral of backing account increased, actor is the payer, counterparty the collateral vault; lamports field holds collateral amount
+    AddCollateralToBalanceOfBackingAccount,
+
+    /// token burned and collateral freed, actor is the token owner, counterparty the receiving token account; lamports field holds collateral amount
+    BurnTokenAndFreeCollateral,
+
+    /// basket backed account created, actor is the payer, counterparty the backing account; followed by one `AddCollateralToBalanceOfBackingAccount` per asset
+    CreateBasketBackingAccount,
+
+    /// token burned and lamport share of basket freed, actor is the token owner, counterparty the receiver of the lamports; followed by one `FreeBasketCollateral` per asset
+    BurnTokenAndFreeBasket,
+
+    /// collateral share of basket freed, actor is the token owner, counterparty the receiving token account; lamports field holds collateral amount
+    FreeBasketCollateral,
+
+    /// backing account migrated to current layout, actor is the payer, counterparty the backing account; lamports field holds additional rent
+    MigrateBackingAccount,
+
+    /// treasury withdrawal executed after reaching approval threshold, carried by a `TreasuryEvent`; actor is the proposer, counterparty the recipient
+    ExecuteTreasuryWithdrawal,
+
+    /// admin transfer from treasury queued, carried by a `TreasuryEvent`; actor is the admin, counterparty the receiver
+    QueueTreasuryTransfer,
+
+    /// share of protocol fee routed away from treasury, actor is the fee payer, counterparty the receiver of the share; lamports field holds the share, token_amount the whole protocol fee
+    ProtocolFeeShare,
+}
+
+impl EventKind
+{
+    /// Reads the kind of event data, to pick `Event` or `TreasuryEvent` for unpacking.
+    pub fn unpack(source: &[u8]) -> Result<EventKind, ProgramError>
+    {
+        if source.len() != CO_EVENT_BYTE_LEN
+        {
+            msg!("Invalid event data length. Aborting.");
+            return Err(CoinbackedError::InvalidEventData.into());
+        }
+
+        // events keep their layout across versions, later versions only fill more fields
+        let version = source[0];
+        if !(CO_EVENT_VERSION_INITIAL..=CO_EVENT_VERSION).contains(&version)
+        {
+            msg!("Unsupported event version {}. Aborting.", version);
+            return Err(CoinbackedError::InvalidEventData.into());
+        }
+
+        EventKind::from_u8(source[CO_EVENT_VERSION_BYTE_LEN])
+    }
+
+    /// Whether events of this kind are carried by a `TreasuryEvent`.
+    pub fn is_treasury(self) -> bool
+    {
+        matches!(
+            self,
+            EventKind::AdminTransferFromTreasuryAccount
+                | EventKind::ExecuteTreasuryWithdrawal
+                | EventKind::QueueTreasuryTransfer
+        )
+    }
+
+    fn to_u8(self) -> u8
+    {
+        match self
+        {
+            EventKind::CreateBackingAccount => CO_EVENT_CREATE_BACKING_ACCOUNT,
+            EventKind::AddToBalanceOfBackingAccount => CO_EVENT_ADD_TO_BALANCE_OF_BACKING_ACCOUNT,
+            EventKind::BurnTokenAndFreeBalance => CO_EVENT_BURN_AND_FREE_BALANCE,
+            EventKind::CleanAccountsAfterBurning => CO_EVENT_CLEAN_ACCOUNTS_AFTER_BURNING,
+            EventKind::AdminTransferFromTreasuryAccount => CO_EVENT_ADMIN_TRANSFER_FROM_TREASURY_ACCOUNT,
+            EventKind::CreateCollateralBackingAccount => CO_EVENT_CREATE_COLLATERAL_BACKING_ACCOUNT,
+            EventKind::AddCollateralToBalanceOfBackingAccount => CO_EVENT_ADD_COLLATERAL_TO_BALANCE_OF_BACKING_ACCOUNT,
+            EventKind::BurnTokenAndFreeCollateral => CO_EVENT_BURN_AND_FREE_COLLATERAL,
+            EventKind::CreateBasketBackingAccount => CO_EVENT_CREATE_BASKET_BACKING_ACCOUNT,
+            EventKind::BurnTokenAndFreeBasket => CO_EVENT_BURN_AND_FREE_BASKET,
+            EventKind::FreeBasketCollateral => CO_EVENT_FREE_BASKET_COLLATERAL,
+            EventKind::MigrateBackingAccount => CO_EVENT_MIGRATE_BACKING_ACCOUNT,
+            EventKind::ExecuteTreasuryWithdrawal => CO_EVENT_EXECUTE_TREASURY_WITHDRAWAL,
+            EventKind::QueueTreasuryTransfer => CO_EVENT_QUEUE_TREASURY_TRANSFER,
+            EventKind::ProtocolFeeShare => CO_EVENT_PROTOCOL_FEE_SHARE,
+        }
+    }
+
+    fn from_u8(kind: u8) -> Result<EventKind, ProgramError>
+    {
+        match kind
+        {
+            CO_EVENT_CREATE_BACKING_ACCOUNT => Ok(EventKind::CreateBackingAccount),
+            CO_EVENT_ADD_TO_BALANCE_OF_BACKING_ACCOUNT => Ok(EventKind::AddToBalanceOfBackingAccount),
+            CO_EVENT_BURN_AND_FREE_BALANCE => Ok(EventKind::BurnTokenAndFreeBalance),
+            CO_EVENT_CLEAN_ACCOUNTS_AFTER_BURNING => Ok(EventKind::CleanAccountsAfterBurning),
+            CO_EVENT_ADMIN_TRANSFER_FROM_TREASURY_ACCOUNT => Ok(EventKind::AdminTransferFromTreasuryAccount),
+            CO_EVENT_CREATE_COLLATERAL_BACKING_ACCOUNT => Ok(EventKind::CreateCollateralBackingAccount),
+            CO_EVENT_ADD_COLLATERAL_TO_BALANCE_OF_BACKING_ACCOUNT => Ok(EventKind::AddCollateralToBalanceOfBackingAccount),
+            CO_EVENT_BURN_AND_FREE_COLLATERAL => Ok(EventKind::BurnTokenAndFreeCollateral),
+            CO_EVENT_CREATE_BASKET_BACKING_ACCOUNT => Ok(EventKind::CreateBasketBackingAccount),
+            CO_EVENT_BURN_AND_FREE_BASKET => Ok(EventKind::BurnTokenAndFreeBasket),
+            CO_EVENT_FREE_BASKET_COLLATERAL => Ok(EventKind::FreeBasketCollateral),
+            CO_EVENT_MIGRATE_BACKING_ACCOUNT => Ok(EventKind::MigrateBackingAccount),
+            CO_EVENT_EXECUTE_TREASURY_WITHDRAWAL => Ok(EventKind::ExecuteTreasuryWithdrawal),
+            CO_EVENT_QUEUE_TREASURY_TRANSFER => Ok(EventKind::QueueTreasuryTransfer),
+            CO_EVENT_PROTOCOL_FEE_SHARE => Ok(EventKind::ProtocolFeeShare),
+            _ =>
+            {
+                msg!("Unknown event kind {}.", kind);
+                Err(CoinbackedError::InvalidEventData.into())
+            }
+        }
+    }
+}
+
+/// Event data of backing accounts, one layout shared by all event kinds except treasury ones
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Event
+{
+    /// kind of event
+    pub kind: EventKind,
+
+    /// mint the event relates to
+    pub mint: Pubkey,
+
+    /// account initiating the operation
+    pub actor: Pubkey,
+
+    /// account on the other side of the lamport movement
+    pub counterparty: Pubkey,
+
+    /// lamports moved between actor/backing account and counterparty
+    pub lamports: u64,
+
+    /// token amount involved, e.g. burned
+    pub token_amount: u64,
+
+    /// mint supply before the operation, zero if mint is not part of the operation
+    pub supply_before: u64,
+
+    /// mint supply after the operation, zero if mint is not part of the operation
+    pub supply_after: u64,
+
+    /// per token unit payout after the operation in lamports, or collateral base units for collateral events
+    pub per_unit_payout: u64,
+}
+
+impl Event
+{
+    /// Packs the event into a byte buffer, prefixed by layout version and kind.
+    pub fn pack(&self, dst: &mut [u8])
+    {
+        let dst = array_mut_ref![dst, 0, CO_EVENT_BYTE_LEN];
+        let (
+            version_dst,
+            kind_dst,
+            mint_dst,
+            actor_dst,
+            counterparty_dst,
+            lamports_dst,
+            token_amount_dst,
+            supply_before_dst,
+            supply_after_dst,
+            per_unit_payout_dst,
+        ) = mut_array_refs![
+            dst,
+            CO_EVENT_VERSION_BYTE_LEN,
+            CO_EVENT_KIND_BYTE_LEN,
+            CO_PUBKEY_BYTE_LEN,
+            CO_PUBKEY_BYTE_LEN,
+            CO_PUBKEY_BYTE_LEN,
+            CO_LAMPORTS_BYTE_LEN,
+            CO_TOKEN_AMOUNT_BYTE_LEN,
+            CO_TOKEN_AMOUNT_BYTE_LEN,
+            CO_TOKEN_AMOUNT_BYTE_LEN,
+            CO_LAMPORTS_BYTE_LEN
+        ];
+
+        *version_dst = CO_EVENT_VERSION.to_le_bytes();
+        *kind_dst = self.kind.to_u8().to_le_bytes();
+        mint_dst.copy_from_slice(self.mint.as_ref());
+        actor_dst.copy_from_slice(self.actor.as_ref());
+        counterparty_dst.copy_from_slice(self.counterparty.as_ref());
+        *lamports_dst = self.lamports.to_le_bytes();
+        *token_amount_dst = self.token_amount.to_le_bytes();
+        *supply_before_dst = self.supply_before.to_le_bytes();
+        *supply_after_dst = self.supply_after.to_le_bytes();
+        *per_unit_payout_dst = self.per_unit_payout.to_le_bytes();
+    }
+
+    /// Unpacks an event from the data of a `Program data:` log line, treasury events unpack as `TreasuryEvent`.
+    pub fn unpack(source: &[u8]) -> Result<Event, ProgramError>
+    {
+        let event = Event::unpack_any_kind(source)?;
+        if event.kind.is_treasury()
+        {
+            msg!("Treasury event, unpack as treasury event. Aborting.");
+            return Err(CoinbackedError::InvalidEventData.into());
+        }
+
+        Ok(event)
+    }
+
+    fn unpack_any_kind(source: &[u8]) -> Result<Event, ProgramError>
+    {
+        let kind = EventKind::unpack(source)?;
+
+        let (
+            _,
+            _,
+            mint_data,
+            actor_data,
+            counterparty_data,
+            lamports_data,
+            token_amount_data,
+            supply_before_data,
+            supply_after_data,
+            per_unit_payout_data,
+        ) = array_refs![
+            array_ref![source, 0, CO_EVENT_BYTE_LEN],
+            CO_EVENT_VERSION_BYTE_LEN,
+            CO_EVENT_KIND_BYTE_LEN,
+            CO_PUBKEY_BYTE_LEN,
+            CO_PUBKEY_BYTE_LEN,
+            CO_PUBKEY_BYTE_LEN,
+            CO_LAMPORTS_BYTE_LEN,
+            CO_TOKEN_AMOUNT_BYTE_LEN,
+            CO_TOKEN_AMOUNT_BYTE_LEN,
+            CO_TOKEN_AMOUNT_BYTE_LEN,
+            CO_LAMPORTS_BYTE_LEN
+        ];
+
+        Ok(
+            Event
+            {
+                kind,
+                mint: Pubkey::new_from_array(*mint_data),
+                actor: Pubkey::new_from_array(*actor_data),
+                counterparty: Pubkey::new_from_array(*counterparty_data),
+                lamports: u64::from_le_bytes(*lamports_data),
+                token_amount: u64::from_le_bytes(*token_amount_data),
+                supply_before: u64::from_le_bytes(*supply_before_data),
+                supply_after: u64::from_le_bytes(*supply_after_data),
+                per_unit_payout: u64::from_le_bytes(*per_unit_payout_data),
+            }
+        )
+    }
+
+    /// Emits the event as program data log.
+    pub fn emit(&self)
+    {
+        let mut data = [0u8; CO_EVENT_BYTE_LEN];
+        self.pack(&mut data);
+        sol_log_data(&[&data]);
+    }
+}
+
+/// Event data of treasury transfers; shares the event layout, mint and payout are unset, limits take the token and supply fields
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryEvent
+{
+    /// kind of event, one of the treasury kinds
+    pub kind: EventKind,
+
+    /// account initiating the transfer
+    pub actor: Pubkey,
+
+    /// receiver of the transfer
+    pub counterparty: Pubkey,
+
+    /// lamports transferred or queued
+    pub lamports: u64,
+
+    /// lamports withdrawn from treasury in the current epoch; zero before version 2
+    pub epoch_withdrawn: u64,
+
+    /// cap on lamports withdrawn per epoch, zero if uncapped or before version 2
+    pub epoch_cap: u64,
+
+    /// delay of transfers in slots; zero before version 2
+    pub delay_slots: u64,
+}
+
+impl TreasuryEvent
+{
+    /// Packs the event into a byte buffer, prefixed by layout version and kind.
+    pub fn pack(&self, dst: &mut [u8])
+    {
+        Event
+        {
+            kind: self.kind,
+            mint: Pubkey::default(),
+            actor: self.actor,
+            counterparty: self.counterparty,
+            lamports: self.lamports,
+            token_amount: self.epoch_withdrawn,
+            supply_before: self.epoch_cap,
+            supply_after: self.delay_slots,
+            per_unit_payout: 0,
+        }.pack(dst);
+    }
+
+    /// Unpacks a treasury event from the data of a `Program data:` log line.
+    pub fn unpack(source: &[u8]) -> Result<TreasuryEvent, ProgramError>
+    {
+        let event = Event::unpack_any_kind(source)?;
+        if !event.kind.is_treasury()
+        {
+            msg!("Not a treasury event. Aborting.");
+            return Err(CoinbackedError::InvalidEventData.into());
+        }
+
+        Ok(
+            TreasuryEvent
+            {
+                kind: event.kind,
+                actor: event.actor,
+                counterparty: event.counterparty,
+                lamports: event.lamports,
+                epoch_withdrawn: event.token_amount,
+                epoch_cap: event.supply_before,
+                delay_slots: event.supply_after,
+            }
+        )
+    }
+
+    /// Emits the event as program data log.
+    pub fn emit(&self)
+    {
+        let mut data = [0u8; CO_EVENT_BYTE_LEN];
+        self.pack(&mut data);
+        sol_log_data(&[&data]);
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn sample_event(kind: EventKind) -> Event
+    {
+        Event
+        {
+            kind,
+            mint: Pubkey::new_unique(),
+            actor: Pubkey::new_unique(),
+            counterparty: Pubkey::new_unique(),
+            lamports: 1_000_000,
+            token_amount: 42,
+            supply_before: 1_000,
+            supply_after: 958,
+            per_unit_payout: 1_044,
+        }
+    }
+
+    const ALL_KINDS: [EventKind; 15] = [
+        EventKind::CreateBackingAccount,
+        EventKind::AddToBalanceOfBackingAccount,
+        EventKind::BurnTokenAndFreeBalance,
+        EventKind::CleanAccountsAfterBurning,
+        EventKind::AdminTransferFromTreasuryAccount,
+        EventKind::CreateCollateralBackingAccount,
+        EventKind::AddCollateralToBalanceOfBackingAccount,
+        EventKind::BurnTokenAndFreeCollateral,
+        EventKind::CreateBasketBackingAccount,
+        EventKind::BurnTokenAndFreeBasket,
+        EventKind::FreeBasketCollateral,
+        EventKind::MigrateBackingAccount,
+        EventKind::ExecuteTreasuryWithdrawal,
+        EventKind::QueueTreasuryTransfer,
+        EventKind::ProtocolFeeShare,
+    ];
+
+    fn sample_treasury_event(kind: EventKind) -> TreasuryEvent
+    {
+        TreasuryEvent
+        {
+            kind,
+            actor: Pubkey::new_unique(),
+            counterparty: Pubkey::new_unique(),
+            lamports: 1_000_000,
+            epoch_withdrawn: 3_000_000,
+            epoch_cap: 10_000_000,
+            delay_slots: 150,
+        }
+    }
+
+    #[test]
+    fn test_event_round_trip()
+    {
+        for kind in ALL_KINDS
+        {
+            let mut data = [0u8; CO_EVENT_BYTE_LEN];
+            if kind.is_treasury()
+            {
+                let event = sample_treasury_event(kind);
+                event.pack(&mut data);
+                assert_eq!(TreasuryEvent::unpack(&data).unwrap(), event);
+                assert_eq!(Event::unpack(&data).unwrap_err(), CoinbackedError::InvalidEventData.into());
+            }
+            else
+            {
+                let event = sample_event(kind);
+                event.pack(&mut data);
+                assert_eq!(Event::unpack(&data).unwrap(), event);
+                assert_eq!(TreasuryEvent::unpack(&data).unwrap_err(), CoinbackedError::InvalidEventData.into());
+            }
+
+            assert_eq!(data[0], CO_EVENT_VERSION);
+            assert_eq!(data[CO_EVENT_VERSION_BYTE_LEN], kind.to_u8());
+            assert_eq!(EventKind::unpack(&data).unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn test_event_unpack_earlier_versions()
+    {
+        // events logged before treasury limits keep decoding, limits read as zero
+        let mut data = [0u8; CO_EVENT_BYTE_LEN];
+        let event = sample_event(EventKind::BurnTokenAndFreeBalance);
+        event.pack(&mut data);
+        data[0] = CO_EVENT_VERSION_INITIAL;
+        assert_eq!(Event::unpack(&data).unwrap(), event);
+
+        let event = TreasuryEvent { epoch_withdrawn: 0, epoch_cap: 0, delay_slots: 0, ..sample_treasury_event(EventKind::AdminTransferFromTreasuryAccount) };
+        event.pack(&mut data);
+        data[0] = CO_EVENT_VERSION_INITIAL;
+        assert_eq!(TreasuryEvent::unpack(&data).unwrap(), event);
+    }
+
+    #[test]
+    fn test_event_kind_codes()
+    {
+        // every code in use decodes to exactly one kind, all others are rejected
+        for code in 0..=u8::MAX
+        {
+            match EventKind::from_u8(code)
+            {
+                Ok(kind) =>
+                {
+                    assert!(ALL_KINDS.contains(&kind));
+                    assert_eq!(kind.to_u8(), code);
+                },
+                Err(error) => assert_eq!(error, CoinbackedError::InvalidEventData.into()),
+            }
+        }
+
+        assert_eq!((0..=u8::MAX).filter(|code| EventKind::from_u8(*code).is_ok()).count(), ALL_KINDS.len());
+    }
+
+    #[test]
+    fn test_event_unpack_rejects_invalid_data()
+    {
+        let mut data = [0u8; CO_EVENT_BYTE_LEN];
+        sample_event(EventKind::BurnTokenAndFreeBalance).pack(&mut data);
+
+        // wrong length
+        assert!(Event::unpack(&data[..CO_EVENT_BYTE_LEN - 1]).is_err());
+
+        // unsupported version, before first and after current
+        for version in [0, CO_EVENT_VERSION + 1]
+        {
+            let mut wrong_version = data;
+            wrong_version[0] = version;
+            assert_eq!(Event::unpack(&wrong_version).unwrap_err(), CoinbackedError::InvalidEventData.into());
+        }
+
+        // unknown kind, first unused code and highest code
+        for kind in [CO_EVENT_PROTOCOL_FEE_SHARE + 1, u8::MAX]
+        {
+            let mut wrong_kind = data;
+            wrong_kind[CO_EVENT_VERSION_BYTE_LEN] = kind;
+            assert_eq!(Event::unpack(&wrong_kind).unwrap_err(), CoinbackedError::InvalidEventData.into());
+        }
+    }
+}
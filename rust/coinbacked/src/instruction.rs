@@ -1,6 +1,18 @@
+//! Instruction types
+
 use solana_program::
 {
-    msg, program_error::ProgramError,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::rent,
+    bpf_loader_upgradeable,
+    instruction::
+    {
+        AccountMeta,
+        Instruction as SolanaInstruction
+    },
 };
 
 use arrayref::
@@ -8,13 +20,27 @@ use arrayref::
     array_ref, array_refs
 };
 
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
 use std::str::from_utf8;
 
 use crate::
 {
     utils::*,
+    error::CoinbackedError,
 };
 
+/// Pause flag of `AdminSetPause` for creating backing accounts
+pub const CO_PAUSE_CREATE: u8 = 1;
+/// Pause flag of `AdminSetPause` for adding to backing accounts
+pub const CO_PAUSE_ADD: u8 = 2;
+/// Pause flag of `AdminSetPause` for validating backing accounts
+pub const CO_PAUSE_VALIDATE: u8 = 4;
+/// Pause flag of `AdminSetPause` for burning, lapses after the burn pause window
+pub const CO_PAUSE_BURN: u8 = 8;
+/// All pause flags of `AdminSetPause`
+pub const CO_PAUSE_ALL: u8 = CO_PAUSE_CREATE | CO_PAUSE_ADD | CO_PAUSE_VALIDATE | CO_PAUSE_BURN;
+
 /// Program instructions
 pub enum Instruction 
 {
@@ -30,6 +56,9 @@ pub enum Instruction
     /// validate existing account
     ValidateBackingAccount,
 
+    /// validate existing account, fails if any check fails
+    ValidateBackingAccountStrict,
+
     /// increase balance of backing account
     AddToBalanceOfBackingAccount 
     {  
@@ -48,28 +77,262 @@ pub enum Instruction
         signed_tos: String
     },
 
+    /// burn a token and receive (portion) of balance, fails if payout is below minimum
+    BurnTokenAndFreeBalanceWithMinPayout
+    {
+        /// amount of token to burn
+        amount: u64,
+        /// minimum payout in lamports after protocol fee
+        min_lamports_out: u64,
+        /// tos
+        signed_tos: String
+    },
+
+    /// burn a token and send (portion) of balance to recipient, protocol fee is taken from payout
+    BurnTokenAndFreeBalanceToRecipient
+    {
+        /// amount of token to burn
+        amount: u64,
+        /// minimum payout in lamports after protocol fee
+        min_lamports_out: u64,
+        /// tos
+        signed_tos: String
+    },
+
     /// clean up after burning and freeing
     CleanAccountsAfterBurning,
 
-    /// admin transaction to create treasury account
+    /// quote payout for burning, read-only and free of protocol fee, result via return data
+    QuotePayout
+    {
+        /// amount of token to quote
+        amount: u64
+    },
+
+    /// creation of backing account for token, backed by SPL collateral held in a vault
+    CreateCollateralBackingAccount
+    {
+        /// initial collateral amount to back token
+        amount: u64,
+        /// tos
+        signed_tos: String
+    },
+
+    /// increase collateral of backing account
+    AddCollateralToBalanceOfBackingAccount
+    {
+        /// collateral amount to add to vault of backing account
+        amount: u64,
+        /// tos
+        signed_tos: String
+    },
+
+    /// burn a token and receive (portion) of collateral of a single asset account, fails if payout is below minimum
+    BurnTokenAndFreeCollateral
+    {
+        /// amount of token to burn
+        amount: u64,
+        /// minimum payout in collateral base units
+        min_amount_out: u64,
+        /// tos
+        signed_tos: String
+    },
+
+    /// creation of backing account for token, backed by lamports and a basket of SPL collateral held in vaults
+    CreateBasketBackingAccount
+    {
+        /// initial coin amount to back token
+        lamports: u64,
+        /// initial collateral amounts in basket order, unused entries are zero
+        amounts: [u64; CO_BASKET_MAX_COLLATERAL],
+        /// tos
+        signed_tos: String
+    },
+
+    /// burn a token and receive (portion) of every basket asset, fails if any payout is below minimum
+    BurnTokenAndFreeBasket
+    {
+        /// amount of token to burn
+        amount: u64,
+        /// minimum payout in lamports
+        min_lamports_out: u64,
+        /// minimum payouts in collateral base units in basket order, unused entries are zero
+        min_amounts_out: [u64; CO_BASKET_MAX_COLLATERAL],
+        /// tos
+        signed_tos: String
+    },
+
+    /// migrate backing account to current layout version, permissionless, payer funds additional rent
+    MigrateBackingAccount,
+
+    /// admin transaction to create treasury account, migrates an existing one to current layout version; signer has to be config admin
     AdminCreateTreasuryAccount,
 
-    /// admin transaction to transfer from treasury account
+    /// admin transaction to transfer from treasury account; signer has to be config admin, transfer has to be queued if a delay is set
     AdminTransferFromTreasuryAccount
     {
         /// amount to transfer
         lamports: u64
     },
+
+    /// admin transaction to initialise config account, signer becomes config admin
+    AdminInitConfigAccount
+    {
+        /// protocol fee for creating a backing account
+        create_fee: u64,
+        /// protocol fee for validating a backing account
+        validate_fee: u64,
+        /// protocol fee for adding to the balance of a backing account
+        add_fee: u64,
+        /// protocol fee for burning, capped at CO_BURN_FEE_MAX_LAMPORTS
+        burn_fee: u64,
+        /// minimum lamports to create a lamport backed account with
+        min_backing_lamports: u64
+    },
+
+    /// admin transaction to update fees and minimum backing in config account
+    AdminUpdateConfigAccount
+    {
+        /// protocol fee for creating a backing account
+        create_fee: u64,
+        /// protocol fee for validating a backing account
+        validate_fee: u64,
+        /// protocol fee for adding to the balance of a backing account
+        add_fee: u64,
+        /// protocol fee for burning, capped at CO_BURN_FEE_MAX_LAMPORTS
+        burn_fee: u64,
+        /// minimum lamports to create a lamport backed account with
+        min_backing_lamports: u64
+    },
+
+    /// admin transaction to update the fee rates in config account, migrates it to current layout version
+    AdminUpdateConfigFeeRates
+    {
+        /// fee rate in basis points of the lamport payout of burns, capped at CO_BURN_FEE_MAX_BPS
+        burn_fee_bps: u16,
+        /// maximum fee for burns, zero for no maximum, capped at CO_BURN_FEE_MAX_LAMPORTS
+        burn_fee_max: u64,
+        /// fee rate in basis points of the lamports added to backing
+        add_fee_bps: u16,
+        /// maximum fee for adding, zero for no maximum
+        add_fee_max: u64
+    },
+
+    /// creation of backing account for token with an exit fee retained in backing on burns
+    CreateBackingAccountWithExitFee
+    {
+        /// initial coin amount to back token
+        lamports: u64,
+        /// exit fee in basis points of payouts, at most `CO_EXIT_FEE_MAX_BPS`
+        exit_fee_bps: u16,
+        /// tos
+        signed_tos: String
+    },
+
+    /// admin transaction to propose a new config admin, migrates config to current layout version; proposing the current admin cancels a handover
+    AdminProposeAdmin
+    {
+        /// admin taking over once accepted
+        new_admin: Pubkey
+    },
+
+    /// transaction of the proposed admin accepting the handover
+    AdminAcceptAdmin,
+
+    /// update authority transaction to revoke the upgrade authority, treasury and config stay administered by config admin
+    AdminMakeProgramImmutable,
+
+    /// admin transaction to set the treasury signers, migrates config to current layout version; once set, withdrawals and changes of signers require approval by threshold signers
+    AdminSetTreasurySigners
+    {
+        /// approvals required for withdrawals, zero to let admin withdraw alone again
+        threshold: u8,
+        /// signers allowed to propose and approve, at most `CO_TREASURY_MAX_SIGNERS`
+        signers: Vec<Pubkey>
+    },
+
+    /// treasury signer transaction to propose a withdrawal, counts as first approval
+    ProposeTreasuryWithdrawal
+    {
+        /// id of proposal, part of the proposal account seeds
+        proposal_id: u64,
+        /// amount to withdraw
+        lamports: u64,
+        /// last slot the withdrawal can be approved and executed in
+        expiry_slot: u64
+    },
+
+    /// treasury signer transaction to approve a withdrawal proposal
+    ApproveTreasuryWithdrawal,
+
+    /// permissionless transaction to execute a withdrawal proposal that reached threshold and passed the delay, closes the proposal
+    ExecuteTreasuryWithdrawal,
+
+    /// admin transaction to update delay and epoch cap of treasury withdrawals, migrates config to current layout version; tightening applies immediately, loosening is queued and applies when repeated after the current delay
+    AdminUpdateTreasuryLimits
+    {
+        /// slots between queueing or proposing a withdrawal and its execution, zero for none
+        delay_slots: u64,
+        /// maximum lamports withdrawn per epoch, zero for no maximum
+        epoch_cap: u64
+    },
+
+    /// admin transaction to queue a transfer from treasury account, replaces a queued one; migrates treasury to current layout version
+    AdminQueueTreasuryTransfer
+    {
+        /// amount to transfer once the delay passed
+        lamports: u64
+    },
+
+    /// admin transaction to pause or resume operations, migrates config to current layout version; burn pauses lapse after their window
+    AdminSetPause
+    {
+        /// paused operations, combination of `CO_PAUSE_*` flags
+        pause_flags: u8,
+        /// slots a burn pause lasts at most, up to `CO_PAUSE_BURN_MAX_SLOTS`
+        burn_pause_max_slots: u64
+    },
+
+    /// creation of backing account for token with an exit fee and a creator receiving a share of protocol fees of burns and top-ups
+    CreateBackingAccountWithCreator
+    {
+        /// initial coin amount to back token
+        lamports: u64,
+        /// exit fee in basis points of payouts, at most `CO_EXIT_FEE_MAX_BPS`
+        exit_fee_bps: u16,
+        /// creator of backing, none if packed as zeroes
+        creator: Option<Pubkey>,
+        /// tos
+        signed_tos: String
+    },
+
+    /// admin transaction to update the share of burn and top-up protocol fees routed to creators, migrates config to current layout version
+    AdminUpdateCreatorFeeShare
+    {
+        /// share of protocol fee in basis points
+        creator_fee_share_bps: u16
+    },
+
+    /// admin transaction to update the share of create, top-up and burn protocol fees routed to referrers, migrates config to current layout version
+    AdminUpdateReferrerFeeShare
+    {
+        /// share of protocol fee in basis points
+        referrer_fee_share_bps: u16
+    },
+
+    /// permissionless transaction to close an expired withdrawal proposal, rent goes back to proposer and proposal id can be reused
+    CloseExpiredTreasuryWithdrawal,
 }
 
 impl Instruction
 {
+    /// Unpacks a byte buffer into an [Instruction](enum.Instruction.html).
     pub fn unpack(instruction_data: &[u8]) -> Result<Instruction, ProgramError>
     {
-        if instruction_data.len() < 1
+        if instruction_data.is_empty()
         {
             msg!("Incorrect data format, too short. Aborting.");
-            return Err(ProgramError::InvalidInstructionData);
+            return Err(CoinbackedError::InvalidInstructionLength.into());
         }
 
         match instruction_data[0]
@@ -79,7 +342,7 @@ impl Instruction
                 if instruction_data.len() != CO_OP_CREATE_BACKING_ACCOUNT_BYTE_LEN 
                 {
                     msg!("Incorrect data format, wrong size for operation CREATE BACKING ACCOUNT. Aborting.");
-                    return Err(ProgramError::InvalidInstructionData);
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
                 }
                 
                 // slice into segments of data
@@ -89,10 +352,10 @@ impl Instruction
                 let lamports = u64::from_le_bytes(*lamports_data);
                 let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
                     msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
-                    ProgramError::InvalidInstructionData
+                    CoinbackedError::InvalidSignedTos
                 })?;
 
-                Ok(Instruction::CreateBackingAccount {lamports: lamports, signed_tos: signed_tos.to_string()})
+                Ok(Instruction::CreateBackingAccount {lamports, signed_tos: signed_tos.to_string()})
             },
 
             CO_OP_VALIDATE_BACKING_ACCOUNT =>
@@ -100,18 +363,29 @@ impl Instruction
                 if instruction_data.len() != CO_OP_VALIDATE_BACKING_ACCOUNT_BYTE_LEN
                 {
                     msg!("Incorrect data format, wrong size for operation VALIDATE BACKING ACCOUNT. Aborting");
-                    return Err(ProgramError::InvalidInstructionData);
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
                 }
 
                 Ok(Instruction::ValidateBackingAccount)
             },
 
+            CO_OP_VALIDATE_BACKING_ACCOUNT_STRICT =>
+            {
+                if instruction_data.len() != CO_OP_VALIDATE_BACKING_ACCOUNT_STRICT_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation VALIDATE BACKING ACCOUNT STRICT. Aborting");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                Ok(Instruction::ValidateBackingAccountStrict)
+            },
+
             CO_OP_ADD_TO_BALANCE_OF_BACKING_ACCOUNT =>
             {
                 if instruction_data.len() != CO_OP_ADD_TO_BALANCE_OF_BACKING_ACCOUNT_BYTE_LEN
                 {
                     msg!("Incorrect data format, wrong size for operation ADD TO BALANCE OF BACKING ACCOUNT. Aborting.");
-                    return Err(ProgramError::InvalidInstructionData);
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
                 }
                 
                 // slice into segments of data
@@ -121,10 +395,10 @@ impl Instruction
                 let lamports = u64::from_le_bytes(*lamports_data);
                 let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
                     msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
-                    ProgramError::InvalidInstructionData
+                    CoinbackedError::InvalidSignedTos
                 })?;
 
-                Ok(Instruction::AddToBalanceOfBackingAccount {lamports: lamports, signed_tos: signed_tos.to_string()})
+                Ok(Instruction::AddToBalanceOfBackingAccount {lamports, signed_tos: signed_tos.to_string()})
             },
 
             CO_OP_BURN_AND_FREE_BALANCE =>
@@ -132,7 +406,7 @@ impl Instruction
                 if instruction_data.len() != CO_OP_BURN_AND_FREE_BALANCE_BYTE_LEN
                 {
                     msg!("Incorrect data format, wrong size for operation BURN AND FREE BALANCE. Aborting.");
-                    return Err(ProgramError::InvalidInstructionData);
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
                 }
                 
                 // slice into segments of data
@@ -142,10 +416,54 @@ impl Instruction
                 let amount = u64::from_le_bytes(*amount_data);
                 let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
                     msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
-                    ProgramError::InvalidInstructionData
+                    CoinbackedError::InvalidSignedTos
+                })?;
+
+                Ok(Instruction::BurnTokenAndFreeBalanace {amount, signed_tos: signed_tos.to_string()})
+            },
+
+            CO_OP_BURN_AND_FREE_BALANCE_WITH_MIN_PAYOUT =>
+            {
+                if instruction_data.len() != CO_OP_BURN_AND_FREE_BALANCE_WITH_MIN_PAYOUT_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation BURN AND FREE BALANCE WITH MIN PAYOUT. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                // slice into segments of data
+                let (amount_data, min_lamports_out_data, signed_tos_data) = array_refs![array_ref![instruction_data, 1, CO_OP_BURN_AND_FREE_BALANCE_WITH_MIN_PAYOUT_BYTE_LEN-1], CO_TOKEN_AMOUNT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_SIGNATURE_BASE58_BYTE_LEN];
+
+                // create parameters
+                let amount = u64::from_le_bytes(*amount_data);
+                let min_lamports_out = u64::from_le_bytes(*min_lamports_out_data);
+                let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
+                    msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
+                    CoinbackedError::InvalidSignedTos
+                })?;
+
+                Ok(Instruction::BurnTokenAndFreeBalanceWithMinPayout {amount, min_lamports_out, signed_tos: signed_tos.to_string()})
+            },
+
+            CO_OP_BURN_AND_FREE_BALANCE_TO_RECIPIENT =>
+            {
+                if instruction_data.len() != CO_OP_BURN_AND_FREE_BALANCE_TO_RECIPIENT_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation BURN AND FREE BALANCE TO RECIPIENT. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                // slice into segments of data
+                let (amount_data, min_lamports_out_data, signed_tos_data) = array_refs![array_ref![instruction_data, 1, CO_OP_BURN_AND_FREE_BALANCE_TO_RECIPIENT_BYTE_LEN-1], CO_TOKEN_AMOUNT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_SIGNATURE_BASE58_BYTE_LEN];
+
+                // create parameters
+                let amount = u64::from_le_bytes(*amount_data);
+                let min_lamports_out = u64::from_le_bytes(*min_lamports_out_data);
+                let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
+                    msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
+                    CoinbackedError::InvalidSignedTos
                 })?;
 
-                Ok(Instruction::BurnTokenAndFreeBalanace {amount: amount, signed_tos: signed_tos.to_string()})
+                Ok(Instruction::BurnTokenAndFreeBalanceToRecipient {amount, min_lamports_out, signed_tos: signed_tos.to_string()})
             },
 
             CO_OP_CLEAN_ACCOUNTS_AFTER_BURNING =>
@@ -153,18 +471,151 @@ impl Instruction
                 if instruction_data.len() != CO_OP_CLEAN_ACCOUNTS_AFTER_BURNING_BYTE_LEN
                 {
                     msg!("Incorrect data format, wrong size for operation CLEAN ACCOUNTS AFTER BURNING. Aborting");
-                    return Err(ProgramError::InvalidInstructionData);
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
                 }
 
                 Ok(Instruction::CleanAccountsAfterBurning)
             },
 
+            CO_OP_QUOTE_PAYOUT =>
+            {
+                if instruction_data.len() != CO_OP_QUOTE_PAYOUT_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation QUOTE PAYOUT. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+                let amount_data = array_ref![instruction_data, 1, CO_OP_QUOTE_PAYOUT_BYTE_LEN-1];
+                let amount = u64::from_le_bytes(*amount_data);
+
+                Ok(Instruction::QuotePayout { amount })
+            },
+
+            CO_OP_CREATE_COLLATERAL_BACKING_ACCOUNT =>
+            {
+                if instruction_data.len() != CO_OP_CREATE_COLLATERAL_BACKING_ACCOUNT_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation CREATE COLLATERAL BACKING ACCOUNT. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                // slice into segments of data
+                let (amount_data, signed_tos_data) = array_refs![array_ref![instruction_data, 1, CO_OP_CREATE_COLLATERAL_BACKING_ACCOUNT_BYTE_LEN-1], CO_TOKEN_AMOUNT_BYTE_LEN, CO_SIGNATURE_BASE58_BYTE_LEN];
+
+                // create parameters
+                let amount = u64::from_le_bytes(*amount_data);
+                let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
+                    msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
+                    CoinbackedError::InvalidSignedTos
+                })?;
+
+                Ok(Instruction::CreateCollateralBackingAccount {amount, signed_tos: signed_tos.to_string()})
+            },
+
+            CO_OP_ADD_COLLATERAL_TO_BALANCE_OF_BACKING_ACCOUNT =>
+            {
+                if instruction_data.len() != CO_OP_ADD_COLLATERAL_TO_BALANCE_OF_BACKING_ACCOUNT_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADD COLLATERAL TO BALANCE OF BACKING ACCOUNT. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                // slice into segments of data
+                let (amount_data, signed_tos_data) = array_refs![array_ref![instruction_data, 1, CO_OP_ADD_COLLATERAL_TO_BALANCE_OF_BACKING_ACCOUNT_BYTE_LEN-1], CO_TOKEN_AMOUNT_BYTE_LEN, CO_SIGNATURE_BASE58_BYTE_LEN];
+
+                // create parameters
+                let amount = u64::from_le_bytes(*amount_data);
+                let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
+                    msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
+                    CoinbackedError::InvalidSignedTos
+                })?;
+
+                Ok(Instruction::AddCollateralToBalanceOfBackingAccount {amount, signed_tos: signed_tos.to_string()})
+            },
+
+            CO_OP_BURN_AND_FREE_COLLATERAL =>
+            {
+                if instruction_data.len() != CO_OP_BURN_AND_FREE_COLLATERAL_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation BURN AND FREE COLLATERAL. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                // slice into segments of data
+                let (amount_data, min_amount_out_data, signed_tos_data) = array_refs![array_ref![instruction_data, 1, CO_OP_BURN_AND_FREE_COLLATERAL_BYTE_LEN-1], CO_TOKEN_AMOUNT_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN, CO_SIGNATURE_BASE58_BYTE_LEN];
+
+                // create parameters
+                let amount = u64::from_le_bytes(*amount_data);
+                let min_amount_out = u64::from_le_bytes(*min_amount_out_data);
+                let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
+                    msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
+                    CoinbackedError::InvalidSignedTos
+                })?;
+
+                Ok(Instruction::BurnTokenAndFreeCollateral {amount, min_amount_out, signed_tos: signed_tos.to_string()})
+            },
+
+            CO_OP_CREATE_BASKET_BACKING_ACCOUNT =>
+            {
+                if instruction_data.len() != CO_OP_CREATE_BASKET_BACKING_ACCOUNT_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation CREATE BASKET BACKING ACCOUNT. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                // slice into segments of data
+                let (lamports_data, amounts_data, signed_tos_data) = array_refs![array_ref![instruction_data, 1, CO_OP_CREATE_BASKET_BACKING_ACCOUNT_BYTE_LEN-1], CO_LAMPORTS_BYTE_LEN, CO_BASKET_MAX_COLLATERAL * CO_TOKEN_AMOUNT_BYTE_LEN, CO_SIGNATURE_BASE58_BYTE_LEN];
+
+                // create parameters
+                let lamports = u64::from_le_bytes(*lamports_data);
+                let amounts = unpack_basket_amounts(amounts_data);
+                let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
+                    msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
+                    CoinbackedError::InvalidSignedTos
+                })?;
+
+                Ok(Instruction::CreateBasketBackingAccount {lamports, amounts, signed_tos: signed_tos.to_string()})
+            },
+
+            CO_OP_BURN_AND_FREE_BASKET =>
+            {
+                if instruction_data.len() != CO_OP_BURN_AND_FREE_BASKET_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation BURN AND FREE BASKET. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                // slice into segments of data
+                let (amount_data, min_lamports_out_data, min_amounts_out_data, signed_tos_data) = array_refs![array_ref![instruction_data, 1, CO_OP_BURN_AND_FREE_BASKET_BYTE_LEN-1], CO_TOKEN_AMOUNT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_BASKET_MAX_COLLATERAL * CO_TOKEN_AMOUNT_BYTE_LEN, CO_SIGNATURE_BASE58_BYTE_LEN];
+
+                // create parameters
+                let amount = u64::from_le_bytes(*amount_data);
+                let min_lamports_out = u64::from_le_bytes(*min_lamports_out_data);
+                let min_amounts_out = unpack_basket_amounts(min_amounts_out_data);
+                let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
+                    msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
+                    CoinbackedError::InvalidSignedTos
+                })?;
+
+                Ok(Instruction::BurnTokenAndFreeBasket {amount, min_lamports_out, min_amounts_out, signed_tos: signed_tos.to_string()})
+            },
+
+            CO_OP_MIGRATE_BACKING_ACCOUNT =>
+            {
+                if instruction_data.len() != CO_OP_MIGRATE_BACKING_ACCOUNT_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation MIGRATE BACKING ACCOUNT. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                Ok(Instruction::MigrateBackingAccount)
+            },
+
             CO_OP_ADMIN_CREATE_TREASURY_ACCOUNT =>
             {
                 if instruction_data.len() != CO_OP_ADMIN_CREATE_TREASURY_ACCOUNT_BYTE_LEN
                 {
                     msg!("Incorrect data format, wrong size for operation ADMIN CREATE TREASURY ACCOUNT. Aborting.");
-                    return Err(ProgramError::InvalidInstructionData);
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
                 }
 
                 Ok(Instruction::AdminCreateTreasuryAccount)
@@ -175,16 +626,1959 @@ impl Instruction
                 if instruction_data.len() != CO_OP_ADMIN_TRANSFER_FROM_TREASURY_ACCOUNT_BYTE_LEN
                 {
                     msg!("Incorrect data format, wrong size for operation ADMIN TRANSFER FROM TREASURY ACCOUNT. Aborting.");
-                    return Err(ProgramError::InvalidInstructionData);
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
                 }
                 let lamports_data = array_ref![instruction_data, 1, CO_OP_ADMIN_TRANSFER_FROM_TREASURY_ACCOUNT_BYTE_LEN-1];
                 let lamports = u64::from_le_bytes(*lamports_data);
 
-                Ok(Instruction::AdminTransferFromTreasuryAccount { lamports: lamports})
-            }
+                Ok(Instruction::AdminTransferFromTreasuryAccount { lamports })
+            },
 
-            _ => Err(ProgramError::InvalidInstructionData)
-        }
+            CO_OP_ADMIN_INIT_CONFIG_ACCOUNT =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_INIT_CONFIG_ACCOUNT_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN INIT CONFIG ACCOUNT. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+                let (create_fee_data, validate_fee_data, add_fee_data, burn_fee_data, min_backing_lamports_data) = array_refs![array_ref![instruction_data, 1, CO_OP_ADMIN_INIT_CONFIG_ACCOUNT_BYTE_LEN-1], CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
 
-    }
-}
\ No newline at end of file
+                Ok(Instruction::AdminInitConfigAccount
+                {
+                    create_fee: u64::from_le_bytes(*create_fee_data),
+                    validate_fee: u64::from_le_bytes(*validate_fee_data),
+                    add_fee: u64::from_le_bytes(*add_fee_data),
+                    burn_fee: u64::from_le_bytes(*burn_fee_data),
+                    min_backing_lamports: u64::from_le_bytes(*min_backing_lamports_data),
+                })
+            },
+
+            CO_OP_ADMIN_UPDATE_CONFIG_ACCOUNT =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_UPDATE_CONFIG_ACCOUNT_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN UPDATE CONFIG ACCOUNT. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+                let (create_fee_data, validate_fee_data, add_fee_data, burn_fee_data, min_backing_lamports_data) = array_refs![array_ref![instruction_data, 1, CO_OP_ADMIN_UPDATE_CONFIG_ACCOUNT_BYTE_LEN-1], CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+
+                Ok(Instruction::AdminUpdateConfigAccount
+                {
+                    create_fee: u64::from_le_bytes(*create_fee_data),
+                    validate_fee: u64::from_le_bytes(*validate_fee_data),
+                    add_fee: u64::from_le_bytes(*add_fee_data),
+                    burn_fee: u64::from_le_bytes(*burn_fee_data),
+                    min_backing_lamports: u64::from_le_bytes(*min_backing_lamports_data),
+                })
+            },
+
+            CO_OP_ADMIN_UPDATE_CONFIG_FEE_RATES =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_UPDATE_CONFIG_FEE_RATES_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN UPDATE CONFIG FEE RATES. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+                let (burn_fee_bps_data, burn_fee_max_data, add_fee_bps_data, add_fee_max_data) = array_refs![array_ref![instruction_data, 1, CO_OP_ADMIN_UPDATE_CONFIG_FEE_RATES_BYTE_LEN-1], CO_BPS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_BPS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+
+                Ok(Instruction::AdminUpdateConfigFeeRates
+                {
+                    burn_fee_bps: u16::from_le_bytes(*burn_fee_bps_data),
+                    burn_fee_max: u64::from_le_bytes(*burn_fee_max_data),
+                    add_fee_bps: u16::from_le_bytes(*add_fee_bps_data),
+                    add_fee_max: u64::from_le_bytes(*add_fee_max_data),
+                })
+            },
+
+            CO_OP_CREATE_BACKING_ACCOUNT_WITH_EXIT_FEE =>
+            {
+                if instruction_data.len() != CO_OP_CREATE_BACKING_ACCOUNT_WITH_EXIT_FEE_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation CREATE BACKING ACCOUNT WITH EXIT FEE. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                let (lamports_data, exit_fee_bps_data, signed_tos_data) = array_refs![array_ref![instruction_data, 1, CO_OP_CREATE_BACKING_ACCOUNT_WITH_EXIT_FEE_BYTE_LEN-1], CO_LAMPORTS_BYTE_LEN, CO_BPS_BYTE_LEN, CO_SIGNATURE_BASE58_BYTE_LEN];
+
+                let lamports = u64::from_le_bytes(*lamports_data);
+                let exit_fee_bps = u16::from_le_bytes(*exit_fee_bps_data);
+                let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
+                    msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
+                    CoinbackedError::InvalidSignedTos
+                })?;
+
+                Ok(Instruction::CreateBackingAccountWithExitFee {lamports, exit_fee_bps, signed_tos: signed_tos.to_string()})
+            },
+
+            CO_OP_ADMIN_PROPOSE_ADMIN =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_PROPOSE_ADMIN_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN PROPOSE ADMIN. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+                let new_admin_data = array_ref![instruction_data, 1, CO_PUBKEY_BYTE_LEN];
+
+                Ok(Instruction::AdminProposeAdmin { new_admin: Pubkey::new_from_array(*new_admin_data) })
+            },
+
+            CO_OP_ADMIN_ACCEPT_ADMIN =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_ACCEPT_ADMIN_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN ACCEPT ADMIN. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                Ok(Instruction::AdminAcceptAdmin)
+            },
+
+            CO_OP_ADMIN_MAKE_PROGRAM_IMMUTABLE =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_MAKE_PROGRAM_IMMUTABLE_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN MAKE PROGRAM IMMUTABLE. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                Ok(Instruction::AdminMakeProgramImmutable)
+            },
+
+            CO_OP_ADMIN_SET_TREASURY_SIGNERS =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_SET_TREASURY_SIGNERS_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN SET TREASURY SIGNERS. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                // slice into segments of data
+                let (threshold_data, count_data, signers_data) = array_refs![array_ref![instruction_data, 1, CO_OP_ADMIN_SET_TREASURY_SIGNERS_BYTE_LEN-1], 1, 1, CO_TREASURY_MAX_SIGNERS * CO_PUBKEY_BYTE_LEN];
+
+                let count = count_data[0] as usize;
+                if count > CO_TREASURY_MAX_SIGNERS
+                {
+                    msg!("Treasury signers exceed {}. Aborting.", CO_TREASURY_MAX_SIGNERS);
+                    return Err(CoinbackedError::InvalidTreasurySigners.into());
+                }
+
+                let signers = signers_data
+                    .chunks_exact(CO_PUBKEY_BYTE_LEN)
+                    .take(count)
+                    .map(|signer_data| Pubkey::new_from_array(*array_ref![signer_data, 0, CO_PUBKEY_BYTE_LEN]))
+                    .collect();
+
+                Ok(Instruction::AdminSetTreasurySigners { threshold: threshold_data[0], signers })
+            },
+
+            CO_OP_PROPOSE_TREASURY_WITHDRAWAL =>
+            {
+                if instruction_data.len() != CO_OP_PROPOSE_TREASURY_WITHDRAWAL_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation PROPOSE TREASURY WITHDRAWAL. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                let (proposal_id_data, lamports_data, expiry_slot_data) = array_refs![array_ref![instruction_data, 1, CO_OP_PROPOSE_TREASURY_WITHDRAWAL_BYTE_LEN-1], CO_COUNT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_SLOT_BYTE_LEN];
+
+                Ok(Instruction::ProposeTreasuryWithdrawal
+                {
+                    proposal_id: u64::from_le_bytes(*proposal_id_data),
+                    lamports: u64::from_le_bytes(*lamports_data),
+                    expiry_slot: u64::from_le_bytes(*expiry_slot_data),
+                })
+            },
+
+            CO_OP_APPROVE_TREASURY_WITHDRAWAL =>
+            {
+                if instruction_data.len() != CO_OP_APPROVE_TREASURY_WITHDRAWAL_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation APPROVE TREASURY WITHDRAWAL. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                Ok(Instruction::ApproveTreasuryWithdrawal)
+            },
+
+            CO_OP_EXECUTE_TREASURY_WITHDRAWAL =>
+            {
+                if instruction_data.len() != CO_OP_EXECUTE_TREASURY_WITHDRAWAL_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation EXECUTE TREASURY WITHDRAWAL. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                Ok(Instruction::ExecuteTreasuryWithdrawal)
+            },
+
+            CO_OP_ADMIN_UPDATE_TREASURY_LIMITS =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_UPDATE_TREASURY_LIMITS_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN UPDATE TREASURY LIMITS. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                let (delay_slots_data, epoch_cap_data) = array_refs![array_ref![instruction_data, 1, CO_OP_ADMIN_UPDATE_TREASURY_LIMITS_BYTE_LEN-1], CO_SLOT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+
+                Ok(Instruction::AdminUpdateTreasuryLimits
+                {
+                    delay_slots: u64::from_le_bytes(*delay_slots_data),
+                    epoch_cap: u64::from_le_bytes(*epoch_cap_data),
+                })
+            },
+
+            CO_OP_ADMIN_QUEUE_TREASURY_TRANSFER =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_QUEUE_TREASURY_TRANSFER_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN QUEUE TREASURY TRANSFER. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+                let lamports_data = array_ref![instruction_data, 1, CO_OP_ADMIN_QUEUE_TREASURY_TRANSFER_BYTE_LEN-1];
+                let lamports = u64::from_le_bytes(*lamports_data);
+
+                Ok(Instruction::AdminQueueTreasuryTransfer { lamports })
+            },
+
+            CO_OP_ADMIN_SET_PAUSE =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_SET_PAUSE_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN SET PAUSE. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                let (pause_flags_data, burn_pause_max_slots_data) = array_refs![array_ref![instruction_data, 1, CO_OP_ADMIN_SET_PAUSE_BYTE_LEN-1], 1, CO_SLOT_BYTE_LEN];
+
+                Ok(Instruction::AdminSetPause
+                {
+                    pause_flags: pause_flags_data[0],
+                    burn_pause_max_slots: u64::from_le_bytes(*burn_pause_max_slots_data),
+                })
+            },
+
+            CO_OP_CREATE_BACKING_ACCOUNT_WITH_CREATOR =>
+            {
+                if instruction_data.len() != CO_OP_CREATE_BACKING_ACCOUNT_WITH_CREATOR_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation CREATE BACKING ACCOUNT WITH CREATOR. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                let (lamports_data, exit_fee_bps_data, creator_data, signed_tos_data) = array_refs![array_ref![instruction_data, 1, CO_OP_CREATE_BACKING_ACCOUNT_WITH_CREATOR_BYTE_LEN-1], CO_LAMPORTS_BYTE_LEN, CO_BPS_BYTE_LEN, CO_PUBKEY_BYTE_LEN, CO_SIGNATURE_BASE58_BYTE_LEN];
+
+                let lamports = u64::from_le_bytes(*lamports_data);
+                let exit_fee_bps = u16::from_le_bytes(*exit_fee_bps_data);
+                let creator = Some(Pubkey::new_from_array(*creator_data)).filter(|creator| creator.ne(&Pubkey::default()));
+                let signed_tos = from_utf8(signed_tos_data).map_err(|err| {
+                    msg!("Invalid UTF-8, from byte {}. Aborting.", err.valid_up_to());
+                    CoinbackedError::InvalidSignedTos
+                })?;
+
+                Ok(Instruction::CreateBackingAccountWithCreator {lamports, exit_fee_bps, creator, signed_tos: signed_tos.to_string()})
+            },
+
+            CO_OP_ADMIN_UPDATE_CREATOR_FEE_SHARE =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_UPDATE_CREATOR_FEE_SHARE_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN UPDATE CREATOR FEE SHARE. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+                let creator_fee_share_bps_data = array_ref![instruction_data, 1, CO_OP_ADMIN_UPDATE_CREATOR_FEE_SHARE_BYTE_LEN-1];
+
+                Ok(Instruction::AdminUpdateCreatorFeeShare { creator_fee_share_bps: u16::from_le_bytes(*creator_fee_share_bps_data) })
+            },
+
+            CO_OP_ADMIN_UPDATE_REFERRER_FEE_SHARE =>
+            {
+                if instruction_data.len() != CO_OP_ADMIN_UPDATE_REFERRER_FEE_SHARE_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation ADMIN UPDATE REFERRER FEE SHARE. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+                let referrer_fee_share_bps_data = array_ref![instruction_data, 1, CO_OP_ADMIN_UPDATE_REFERRER_FEE_SHARE_BYTE_LEN-1];
+
+                Ok(Instruction::AdminUpdateReferrerFeeShare { referrer_fee_share_bps: u16::from_le_bytes(*referrer_fee_share_bps_data) })
+            },
+
+            CO_OP_CLOSE_EXPIRED_TREASURY_WITHDRAWAL =>
+            {
+                if instruction_data.len() != CO_OP_CLOSE_EXPIRED_TREASURY_WITHDRAWAL_BYTE_LEN
+                {
+                    msg!("Incorrect data format, wrong size for operation CLOSE EXPIRED TREASURY WITHDRAWAL. Aborting.");
+                    return Err(CoinbackedError::InvalidInstructionLength.into());
+                }
+
+                Ok(Instruction::CloseExpiredTreasuryWithdrawal)
+            },
+
+            _ => Err(CoinbackedError::InvalidInstruction.into())
+        }
+
+    }
+
+    /// Packs an [Instruction](enum.Instruction.html) into a byte buffer.
+    pub fn pack(&self) -> Result<Vec<u8>, ProgramError>
+    {
+        let mut buf = Vec::with_capacity(CO_OPERATION_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_SIGNATURE_BASE58_BYTE_LEN);
+
+        match self
+        {
+            Instruction::CreateBackingAccount { lamports, signed_tos } =>
+            {
+                buf.push(CO_OP_CREATE_BACKING_ACCOUNT);
+                buf.extend_from_slice(&lamports.to_le_bytes());
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::ValidateBackingAccount =>
+            {
+                buf.push(CO_OP_VALIDATE_BACKING_ACCOUNT);
+            },
+
+            Instruction::ValidateBackingAccountStrict =>
+            {
+                buf.push(CO_OP_VALIDATE_BACKING_ACCOUNT_STRICT);
+            },
+
+            Instruction::AddToBalanceOfBackingAccount { lamports, signed_tos } =>
+            {
+                buf.push(CO_OP_ADD_TO_BALANCE_OF_BACKING_ACCOUNT);
+                buf.extend_from_slice(&lamports.to_le_bytes());
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::BurnTokenAndFreeBalanace { amount, signed_tos } =>
+            {
+                buf.push(CO_OP_BURN_AND_FREE_BALANCE);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::BurnTokenAndFreeBalanceWithMinPayout { amount, min_lamports_out, signed_tos } =>
+            {
+                buf.push(CO_OP_BURN_AND_FREE_BALANCE_WITH_MIN_PAYOUT);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&min_lamports_out.to_le_bytes());
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::BurnTokenAndFreeBalanceToRecipient { amount, min_lamports_out, signed_tos } =>
+            {
+                buf.push(CO_OP_BURN_AND_FREE_BALANCE_TO_RECIPIENT);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&min_lamports_out.to_le_bytes());
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::CleanAccountsAfterBurning =>
+            {
+                buf.push(CO_OP_CLEAN_ACCOUNTS_AFTER_BURNING);
+            },
+
+            Instruction::QuotePayout { amount } =>
+            {
+                buf.push(CO_OP_QUOTE_PAYOUT);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            },
+
+            Instruction::CreateCollateralBackingAccount { amount, signed_tos } =>
+            {
+                buf.push(CO_OP_CREATE_COLLATERAL_BACKING_ACCOUNT);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::AddCollateralToBalanceOfBackingAccount { amount, signed_tos } =>
+            {
+                buf.push(CO_OP_ADD_COLLATERAL_TO_BALANCE_OF_BACKING_ACCOUNT);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::BurnTokenAndFreeCollateral { amount, min_amount_out, signed_tos } =>
+            {
+                buf.push(CO_OP_BURN_AND_FREE_COLLATERAL);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&min_amount_out.to_le_bytes());
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::CreateBasketBackingAccount { lamports, amounts, signed_tos } =>
+            {
+                buf.push(CO_OP_CREATE_BASKET_BACKING_ACCOUNT);
+                buf.extend_from_slice(&lamports.to_le_bytes());
+                for amount in amounts.iter()
+                {
+                    buf.extend_from_slice(&amount.to_le_bytes());
+                }
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::BurnTokenAndFreeBasket { amount, min_lamports_out, min_amounts_out, signed_tos } =>
+            {
+                buf.push(CO_OP_BURN_AND_FREE_BASKET);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&min_lamports_out.to_le_bytes());
+                for min_amount_out in min_amounts_out.iter()
+                {
+                    buf.extend_from_slice(&min_amount_out.to_le_bytes());
+                }
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::MigrateBackingAccount =>
+            {
+                buf.push(CO_OP_MIGRATE_BACKING_ACCOUNT);
+            },
+
+            Instruction::AdminCreateTreasuryAccount =>
+            {
+                buf.push(CO_OP_ADMIN_CREATE_TREASURY_ACCOUNT);
+            },
+
+            Instruction::AdminTransferFromTreasuryAccount { lamports } =>
+            {
+                buf.push(CO_OP_ADMIN_TRANSFER_FROM_TREASURY_ACCOUNT);
+                buf.extend_from_slice(&lamports.to_le_bytes());
+            },
+
+            Instruction::AdminInitConfigAccount { create_fee, validate_fee, add_fee, burn_fee, min_backing_lamports } =>
+            {
+                buf.push(CO_OP_ADMIN_INIT_CONFIG_ACCOUNT);
+                buf.extend_from_slice(&create_fee.to_le_bytes());
+                buf.extend_from_slice(&validate_fee.to_le_bytes());
+                buf.extend_from_slice(&add_fee.to_le_bytes());
+                buf.extend_from_slice(&burn_fee.to_le_bytes());
+                buf.extend_from_slice(&min_backing_lamports.to_le_bytes());
+            },
+
+            Instruction::AdminUpdateConfigAccount { create_fee, validate_fee, add_fee, burn_fee, min_backing_lamports } =>
+            {
+                buf.push(CO_OP_ADMIN_UPDATE_CONFIG_ACCOUNT);
+                buf.extend_from_slice(&create_fee.to_le_bytes());
+                buf.extend_from_slice(&validate_fee.to_le_bytes());
+                buf.extend_from_slice(&add_fee.to_le_bytes());
+                buf.extend_from_slice(&burn_fee.to_le_bytes());
+                buf.extend_from_slice(&min_backing_lamports.to_le_bytes());
+            },
+
+            Instruction::AdminUpdateConfigFeeRates { burn_fee_bps, burn_fee_max, add_fee_bps, add_fee_max } =>
+            {
+                buf.push(CO_OP_ADMIN_UPDATE_CONFIG_FEE_RATES);
+                buf.extend_from_slice(&burn_fee_bps.to_le_bytes());
+                buf.extend_from_slice(&burn_fee_max.to_le_bytes());
+                buf.extend_from_slice(&add_fee_bps.to_le_bytes());
+                buf.extend_from_slice(&add_fee_max.to_le_bytes());
+            },
+
+            Instruction::CreateBackingAccountWithExitFee { lamports, exit_fee_bps, signed_tos } =>
+            {
+                buf.push(CO_OP_CREATE_BACKING_ACCOUNT_WITH_EXIT_FEE);
+                buf.extend_from_slice(&lamports.to_le_bytes());
+                buf.extend_from_slice(&exit_fee_bps.to_le_bytes());
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::AdminProposeAdmin { new_admin } =>
+            {
+                buf.push(CO_OP_ADMIN_PROPOSE_ADMIN);
+                buf.extend_from_slice(new_admin.as_ref());
+            },
+
+            Instruction::AdminAcceptAdmin =>
+            {
+                buf.push(CO_OP_ADMIN_ACCEPT_ADMIN);
+            },
+
+            Instruction::AdminMakeProgramImmutable =>
+            {
+                buf.push(CO_OP_ADMIN_MAKE_PROGRAM_IMMUTABLE);
+            },
+
+            Instruction::AdminSetTreasurySigners { threshold, signers } =>
+            {
+                if signers.len() > CO_TREASURY_MAX_SIGNERS
+                {
+                    msg!("Treasury signers exceed {}. Aborting.", CO_TREASURY_MAX_SIGNERS);
+                    return Err(CoinbackedError::InvalidTreasurySigners.into());
+                }
+
+                // zero padded to the fixed length expected by unpack
+                let mut signers_data = [0u8; CO_TREASURY_MAX_SIGNERS * CO_PUBKEY_BYTE_LEN];
+                for (signer, signer_dst) in signers.iter().zip(signers_data.chunks_exact_mut(CO_PUBKEY_BYTE_LEN))
+                {
+                    signer_dst.copy_from_slice(signer.as_ref());
+                }
+
+                buf.push(CO_OP_ADMIN_SET_TREASURY_SIGNERS);
+                buf.push(*threshold);
+                buf.push(signers.len() as u8);
+                buf.extend_from_slice(&signers_data);
+            },
+
+            Instruction::ProposeTreasuryWithdrawal { proposal_id, lamports, expiry_slot } =>
+            {
+                buf.push(CO_OP_PROPOSE_TREASURY_WITHDRAWAL);
+                buf.extend_from_slice(&proposal_id.to_le_bytes());
+                buf.extend_from_slice(&lamports.to_le_bytes());
+                buf.extend_from_slice(&expiry_slot.to_le_bytes());
+            },
+
+            Instruction::ApproveTreasuryWithdrawal =>
+            {
+                buf.push(CO_OP_APPROVE_TREASURY_WITHDRAWAL);
+            },
+
+            Instruction::ExecuteTreasuryWithdrawal =>
+            {
+                buf.push(CO_OP_EXECUTE_TREASURY_WITHDRAWAL);
+            },
+
+            Instruction::AdminUpdateTreasuryLimits { delay_slots, epoch_cap } =>
+            {
+                buf.push(CO_OP_ADMIN_UPDATE_TREASURY_LIMITS);
+                buf.extend_from_slice(&delay_slots.to_le_bytes());
+                buf.extend_from_slice(&epoch_cap.to_le_bytes());
+            },
+
+            Instruction::AdminQueueTreasuryTransfer { lamports } =>
+            {
+                buf.push(CO_OP_ADMIN_QUEUE_TREASURY_TRANSFER);
+                buf.extend_from_slice(&lamports.to_le_bytes());
+            },
+
+            Instruction::AdminSetPause { pause_flags, burn_pause_max_slots } =>
+            {
+                buf.push(CO_OP_ADMIN_SET_PAUSE);
+                buf.push(*pause_flags);
+                buf.extend_from_slice(&burn_pause_max_slots.to_le_bytes());
+            },
+
+            Instruction::CreateBackingAccountWithCreator { lamports, exit_fee_bps, creator, signed_tos } =>
+            {
+                buf.push(CO_OP_CREATE_BACKING_ACCOUNT_WITH_CREATOR);
+                buf.extend_from_slice(&lamports.to_le_bytes());
+                buf.extend_from_slice(&exit_fee_bps.to_le_bytes());
+                buf.extend_from_slice(creator.unwrap_or_default().as_ref());
+                buf.extend_from_slice(&pack_signed_tos(signed_tos)?);
+            },
+
+            Instruction::AdminUpdateCreatorFeeShare { creator_fee_share_bps } =>
+            {
+                buf.push(CO_OP_ADMIN_UPDATE_CREATOR_FEE_SHARE);
+                buf.extend_from_slice(&creator_fee_share_bps.to_le_bytes());
+            },
+
+            Instruction::AdminUpdateReferrerFeeShare { referrer_fee_share_bps } =>
+            {
+                buf.push(CO_OP_ADMIN_UPDATE_REFERRER_FEE_SHARE);
+                buf.extend_from_slice(&referrer_fee_share_bps.to_le_bytes());
+            },
+
+            Instruction::CloseExpiredTreasuryWithdrawal =>
+            {
+                buf.push(CO_OP_CLOSE_EXPIRED_TREASURY_WITHDRAWAL);
+            },
+        }
+
+        Ok(buf)
+    }
+}
+
+/// Creates a `CreateBackingAccount` instruction.
+pub fn create_backing_account(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    lamports: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::CreateBackingAccount { lamports, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(rent::ID, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `CreateBackingAccount` instruction, recording the contribution in the backer record of payer.
+pub fn create_backing_account_with_backer_record(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    lamports: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let mut instruction = create_backing_account(program_id, payer, mint, token_account, lamports, signed_tos)?;
+    // config stays last
+    instruction.accounts.insert(instruction.accounts.len() - 1, AccountMeta::new(find_backer_record_address(mint, payer, program_id).0, false));
+
+    Ok(instruction)
+}
+
+/// Creates a `CreateBackingAccountWithExitFee` instruction.
+pub fn create_backing_account_with_exit_fee(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    lamports: u64,
+    exit_fee_bps: u16,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let mut instruction = create_backing_account(program_id, payer, mint, token_account, lamports, signed_tos)?;
+    instruction.data = Instruction::CreateBackingAccountWithExitFee { lamports, exit_fee_bps, signed_tos: signed_tos.to_string() }.pack()?;
+
+    Ok(instruction)
+}
+
+/// Creates a `CreateBackingAccountWithCreator` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn create_backing_account_with_creator(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    creator: &Pubkey,
+    lamports: u64,
+    exit_fee_bps: u16,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let mut instruction = create_backing_account(program_id, payer, mint, token_account, lamports, signed_tos)?;
+    instruction.data = Instruction::CreateBackingAccountWithCreator { lamports, exit_fee_bps, creator: Some(*creator), signed_tos: signed_tos.to_string() }.pack()?;
+
+    Ok(instruction)
+}
+
+/// Creates a `CreateBackingAccount` instruction, paying the referrer share of the protocol fee to referrer.
+pub fn create_backing_account_with_referrer(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    referrer: &Pubkey,
+    lamports: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let mut instruction = create_backing_account(program_id, payer, mint, token_account, lamports, signed_tos)?;
+    // config stays last
+    instruction.accounts.insert(instruction.accounts.len() - 1, AccountMeta::new(*referrer, false));
+
+    Ok(instruction)
+}
+
+/// Creates a `ValidateBackingAccount` instruction.
+pub fn validate_backing_account(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::ValidateBackingAccount.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `ValidateBackingAccountStrict` instruction, usable as on-chain guard.
+pub fn validate_backing_account_strict(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::ValidateBackingAccountStrict.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AddToBalanceOfBackingAccount` instruction.
+pub fn add_to_balance_of_backing_account(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    lamports: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AddToBalanceOfBackingAccount { lamports, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AddToBalanceOfBackingAccount` instruction, recording the contribution in the backer record of payer.
+pub fn add_to_balance_of_backing_account_with_backer_record(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    lamports: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let mut instruction = add_to_balance_of_backing_account(program_id, payer, mint, lamports, signed_tos)?;
+    // config stays last
+    instruction.accounts.insert(instruction.accounts.len() - 1, AccountMeta::new(find_backer_record_address(mint, payer, program_id).0, false));
+
+    Ok(instruction)
+}
+
+/// Creates an `AddToBalanceOfBackingAccount` instruction, paying the creator share of the protocol fee to creator of backing; required once a creator share is configured.
+pub fn add_to_balance_of_backing_account_with_creator(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    creator: &Pubkey,
+    lamports: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let mut instruction = add_to_balance_of_backing_account(program_id, payer, mint, lamports, signed_tos)?;
+    // creator takes the slot right after config
+    instruction.accounts.push(AccountMeta::new(*creator, false));
+
+    Ok(instruction)
+}
+
+/// Creates an `AddToBalanceOfBackingAccount` instruction, paying the referrer share of the protocol fee to referrer.
+pub fn add_to_balance_of_backing_account_with_referrer(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    referrer: &Pubkey,
+    lamports: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let mut instruction = add_to_balance_of_backing_account(program_id, payer, mint, lamports, signed_tos)?;
+    // config stays last
+    instruction.accounts.insert(instruction.accounts.len() - 1, AccountMeta::new(*referrer, false));
+
+    Ok(instruction)
+}
+
+/// Creates a `BurnTokenAndFreeBalanace` instruction.
+pub fn burn_token_and_free_balance(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::BurnTokenAndFreeBalanace { amount, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `BurnTokenAndFreeBalanceWithMinPayout` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_token_and_free_balance_with_min_payout(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+    min_lamports_out: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::BurnTokenAndFreeBalanceWithMinPayout { amount, min_lamports_out, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `BurnTokenAndFreeBalanceWithMinPayout` instruction, paying the creator share of the protocol fee to creator of backing; required once a creator share is configured.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_token_and_free_balance_with_creator(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    creator: &Pubkey,
+    amount: u64,
+    min_lamports_out: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let mut instruction = burn_token_and_free_balance_with_min_payout(program_id, token_program_id, owner, mint, token_account, amount, min_lamports_out, signed_tos)?;
+    // creator takes the slot right after config
+    instruction.accounts.push(AccountMeta::new(*creator, false));
+
+    Ok(instruction)
+}
+
+/// Creates a `BurnTokenAndFreeBalanceWithMinPayout` instruction, paying the referrer share of the protocol fee to referrer.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_token_and_free_balance_with_referrer(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    referrer: &Pubkey,
+    amount: u64,
+    min_lamports_out: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let mut instruction = burn_token_and_free_balance_with_min_payout(program_id, token_program_id, owner, mint, token_account, amount, min_lamports_out, signed_tos)?;
+    // config stays last
+    instruction.accounts.insert(instruction.accounts.len() - 1, AccountMeta::new(*referrer, false));
+
+    Ok(instruction)
+}
+
+/// Creates a `BurnTokenAndFreeBalanceToRecipient` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_token_and_free_balance_to_recipient(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+    min_lamports_out: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::BurnTokenAndFreeBalanceToRecipient { amount, min_lamports_out, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*recipient, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `BurnTokenAndFreeBalanceWithMinPayout` instruction signed by an approved delegate, payout goes to the token owner.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_token_and_free_balance_as_delegate(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    delegate: &Pubkey,
+    token_owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+    min_lamports_out: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::BurnTokenAndFreeBalanceWithMinPayout { amount, min_lamports_out, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*delegate, true),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*token_owner, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `BurnTokenAndFreeBalanceToRecipient` instruction for a token account owned by an SPL multisig.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_token_and_free_balance_with_multisig(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    multisig: &Pubkey,
+    signer_pubkeys: &[&Pubkey],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+    min_lamports_out: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::BurnTokenAndFreeBalanceToRecipient { amount, min_lamports_out, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*multisig, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*recipient, false),
+    ];
+    for signer_pubkey in signer_pubkeys.iter()
+    {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    // config trails all other accounts
+    accounts.push(AccountMeta::new_readonly(find_config_account_address(program_id).0, false));
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `CleanAccountsAfterBurning` instruction.
+pub fn clean_accounts_after_burning(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::CleanAccountsAfterBurning.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `CleanAccountsAfterBurning` instruction for a token account owned by an SPL multisig.
+pub fn clean_accounts_after_burning_with_multisig(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    multisig: &Pubkey,
+    signer_pubkeys: &[&Pubkey],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    destination: &Pubkey,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::CleanAccountsAfterBurning.pack()?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*multisig, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(*destination, false),
+    ];
+    for signer_pubkey in signer_pubkeys.iter()
+    {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `QuotePayout` instruction, to be used with transaction simulation.
+pub fn quote_payout(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::QuotePayout { amount }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(find_backing_account_address(mint, program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `QuotePayout` instruction for a collateral backed account, to be used with transaction simulation.
+pub fn quote_collateral_payout(
+    program_id: &Pubkey,
+    collateral_token_program_id: &Pubkey,
+    mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    amount: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::QuotePayout { amount }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new_readonly(find_collateral_vault_address(mint, collateral_mint, collateral_token_program_id, program_id), false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `CreateCollateralBackingAccount` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn create_collateral_backing_account(
+    program_id: &Pubkey,
+    collateral_token_program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    collateral_mint: &Pubkey,
+    collateral_source_account: &Pubkey,
+    amount: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::CreateCollateralBackingAccount { amount, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*collateral_mint, false),
+        AccountMeta::new(*collateral_source_account, false),
+        AccountMeta::new(find_collateral_vault_address(mint, collateral_mint, collateral_token_program_id, program_id), false),
+        AccountMeta::new_readonly(*collateral_token_program_id, false),
+        AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(rent::ID, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AddCollateralToBalanceOfBackingAccount` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn add_collateral_to_balance_of_backing_account(
+    program_id: &Pubkey,
+    collateral_token_program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    collateral_source_account: &Pubkey,
+    amount: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AddCollateralToBalanceOfBackingAccount { amount, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*collateral_mint, false),
+        AccountMeta::new(*collateral_source_account, false),
+        AccountMeta::new(find_collateral_vault_address(mint, collateral_mint, collateral_token_program_id, program_id), false),
+        AccountMeta::new_readonly(*collateral_token_program_id, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `BurnTokenAndFreeCollateral` instruction, collateral is sent to the destination token account.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_token_and_free_collateral(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    collateral_token_program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    collateral_mint: &Pubkey,
+    collateral_destination_account: &Pubkey,
+    amount: u64,
+    min_amount_out: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::BurnTokenAndFreeCollateral { amount, min_amount_out, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*collateral_mint, false),
+        AccountMeta::new(find_collateral_vault_address(mint, collateral_mint, collateral_token_program_id, program_id), false),
+        AccountMeta::new(*collateral_destination_account, false),
+        AccountMeta::new_readonly(*collateral_token_program_id, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `BurnTokenAndFreeCollateral` instruction for a token account owned by an SPL multisig, first signer pays the protocol fee.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_token_and_free_collateral_with_multisig(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    collateral_token_program_id: &Pubkey,
+    multisig: &Pubkey,
+    signer_pubkeys: &[&Pubkey],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    collateral_mint: &Pubkey,
+    collateral_destination_account: &Pubkey,
+    amount: u64,
+    min_amount_out: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::BurnTokenAndFreeCollateral { amount, min_amount_out, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*multisig, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*collateral_mint, false),
+        AccountMeta::new(find_collateral_vault_address(mint, collateral_mint, collateral_token_program_id, program_id), false),
+        AccountMeta::new(*collateral_destination_account, false),
+        AccountMeta::new_readonly(*collateral_token_program_id, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    push_multisig_signers(&mut accounts, signer_pubkeys);
+    // config trails all other accounts
+    accounts.push(AccountMeta::new_readonly(find_config_account_address(program_id).0, false));
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Collateral asset of a basket, as passed to the basket instruction builders
+pub struct BasketCollateral
+{
+    /// mint of the collateral
+    pub mint: Pubkey,
+
+    /// token program owning the collateral mint
+    pub token_program_id: Pubkey,
+
+    /// token account of the user, source of deposits or destination of payouts
+    pub token_account: Pubkey,
+
+    /// amount to deposit, or minimum payout when burning
+    pub amount: u64,
+}
+
+/// Creates a `ValidateBackingAccount` or `ValidateBackingAccountStrict` instruction for a collateral backed account, reporting the whole basket.
+pub fn validate_basket_backing_account(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    collateral: &[BasketCollateral],
+    strict: bool,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = if strict
+    {
+        Instruction::ValidateBackingAccountStrict.pack()?
+    }
+    else
+    {
+        Instruction::ValidateBackingAccount.pack()?
+    };
+
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for asset in collateral.iter()
+    {
+        accounts.push(AccountMeta::new_readonly(find_collateral_vault_address(mint, &asset.mint, &asset.token_program_id, program_id), false));
+    }
+    // config trails all other accounts
+    accounts.push(AccountMeta::new_readonly(find_config_account_address(program_id).0, false));
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `QuotePayout` instruction for a collateral backed account, quoting the whole basket.
+pub fn quote_basket_payout(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    collateral: &[BasketCollateral],
+    amount: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::QuotePayout { amount }.pack()?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(find_backing_account_address(mint, program_id).0, false),
+    ];
+    for asset in collateral.iter()
+    {
+        accounts.push(AccountMeta::new_readonly(find_collateral_vault_address(mint, &asset.mint, &asset.token_program_id, program_id), false));
+    }
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `CreateBasketBackingAccount` instruction, collateral amounts are taken from the basket entries.
+pub fn create_basket_backing_account(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    lamports: u64,
+    collateral: &[BasketCollateral],
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::CreateBasketBackingAccount { lamports, amounts: pack_basket_amounts(collateral)?, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(rent::ID, false),
+    ];
+    for asset in collateral.iter()
+    {
+        accounts.push(AccountMeta::new_readonly(asset.mint, false));
+        accounts.push(AccountMeta::new(asset.token_account, false));
+        accounts.push(AccountMeta::new(find_collateral_vault_address(mint, &asset.mint, &asset.token_program_id, program_id), false));
+        accounts.push(AccountMeta::new_readonly(asset.token_program_id, false));
+    }
+    // config trails all other accounts
+    accounts.push(AccountMeta::new_readonly(find_config_account_address(program_id).0, false));
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `BurnTokenAndFreeBasket` instruction, minimum payouts are taken from the basket entries.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_token_and_free_basket(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    recipient: &Pubkey,
+    collateral: &[BasketCollateral],
+    amount: u64,
+    min_lamports_out: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::BurnTokenAndFreeBasket { amount, min_lamports_out, min_amounts_out: pack_basket_amounts(collateral)?, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*recipient, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for asset in collateral.iter()
+    {
+        accounts.push(AccountMeta::new_readonly(asset.mint, false));
+        accounts.push(AccountMeta::new(find_collateral_vault_address(mint, &asset.mint, &asset.token_program_id, program_id), false));
+        accounts.push(AccountMeta::new(asset.token_account, false));
+        accounts.push(AccountMeta::new_readonly(asset.token_program_id, false));
+    }
+    // config trails all other accounts
+    accounts.push(AccountMeta::new_readonly(find_config_account_address(program_id).0, false));
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `BurnTokenAndFreeBasket` instruction for a token account owned by an SPL multisig, first signer pays the protocol fee and receives the lamports.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_token_and_free_basket_with_multisig(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    multisig: &Pubkey,
+    signer_pubkeys: &[&Pubkey],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    collateral: &[BasketCollateral],
+    amount: u64,
+    min_lamports_out: u64,
+    signed_tos: &str,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::BurnTokenAndFreeBasket { amount, min_lamports_out, min_amounts_out: pack_basket_amounts(collateral)?, signed_tos: signed_tos.to_string() }.pack()?;
+
+    let recipient = signer_pubkeys.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*multisig, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(**recipient, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for asset in collateral.iter()
+    {
+        accounts.push(AccountMeta::new_readonly(asset.mint, false));
+        accounts.push(AccountMeta::new(find_collateral_vault_address(mint, &asset.mint, &asset.token_program_id, program_id), false));
+        accounts.push(AccountMeta::new(asset.token_account, false));
+        accounts.push(AccountMeta::new_readonly(asset.token_program_id, false));
+    }
+    push_multisig_signers(&mut accounts, signer_pubkeys);
+    // config trails all other accounts
+    accounts.push(AccountMeta::new_readonly(find_config_account_address(program_id).0, false));
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `MigrateBackingAccount` instruction.
+pub fn migrate_backing_account(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::MigrateBackingAccount.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(find_backing_account_address(mint, program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminCreateTreasuryAccount` instruction.
+pub fn admin_create_treasury_account(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminCreateTreasuryAccount.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(rent::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminTransferFromTreasuryAccount` instruction.
+pub fn admin_transfer_from_treasury_account(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    receiver: &Pubkey,
+    lamports: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminTransferFromTreasuryAccount { lamports }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(*receiver, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminInitConfigAccount` instruction.
+pub fn admin_init_config_account(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    create_fee: u64,
+    validate_fee: u64,
+    add_fee: u64,
+    burn_fee: u64,
+    min_backing_lamports: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminInitConfigAccount { create_fee, validate_fee, add_fee, burn_fee, min_backing_lamports }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*program_id, false),
+        AccountMeta::new_readonly(find_program_executable_data_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(rent::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminUpdateConfigAccount` instruction.
+pub fn admin_update_config_account(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    create_fee: u64,
+    validate_fee: u64,
+    add_fee: u64,
+    burn_fee: u64,
+    min_backing_lamports: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminUpdateConfigAccount { create_fee, validate_fee, add_fee, burn_fee, min_backing_lamports }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminUpdateConfigFeeRates` instruction.
+pub fn admin_update_config_fee_rates(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    burn_fee_bps: u16,
+    burn_fee_max: u64,
+    add_fee_bps: u16,
+    add_fee_max: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminUpdateConfigFeeRates { burn_fee_bps, burn_fee_max, add_fee_bps, add_fee_max }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminProposeAdmin` instruction.
+pub fn admin_propose_admin(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    new_admin: &Pubkey,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminProposeAdmin { new_admin: *new_admin }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminAcceptAdmin` instruction.
+pub fn admin_accept_admin(
+    program_id: &Pubkey,
+    new_admin: &Pubkey,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminAcceptAdmin.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*new_admin, true),
+        AccountMeta::new(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminMakeProgramImmutable` instruction.
+pub fn admin_make_program_immutable(
+    program_id: &Pubkey,
+    update_authority: &Pubkey,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminMakeProgramImmutable.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*update_authority, true),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(*program_id, false),
+        AccountMeta::new(find_program_executable_data_account_address(program_id).0, false),
+        AccountMeta::new_readonly(bpf_loader_upgradeable::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminSetTreasurySigners` instruction.
+pub fn admin_set_treasury_signers(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    threshold: u8,
+    signers: &[Pubkey],
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminSetTreasurySigners { threshold, signers: signers.to_vec() }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminSetTreasurySigners` instruction, co-signed by current treasury signers as required once signers are set.
+pub fn admin_set_treasury_signers_with_approvals(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    threshold: u8,
+    signers: &[Pubkey],
+    approver_pubkeys: &[&Pubkey],
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let mut instruction = admin_set_treasury_signers(program_id, admin, threshold, signers)?;
+    for approver_pubkey in approver_pubkeys.iter()
+    {
+        instruction.accounts.push(AccountMeta::new_readonly(**approver_pubkey, true));
+    }
+
+    Ok(instruction)
+}
+
+/// Creates a `ProposeTreasuryWithdrawal` instruction.
+pub fn propose_treasury_withdrawal(
+    program_id: &Pubkey,
+    proposer: &Pubkey,
+    recipient: &Pubkey,
+    proposal_id: u64,
+    lamports: u64,
+    expiry_slot: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::ProposeTreasuryWithdrawal { proposal_id, lamports, expiry_slot }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*proposer, true),
+        AccountMeta::new(find_withdrawal_proposal_address(proposal_id, program_id).0, false),
+        AccountMeta::new_readonly(*recipient, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(rent::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `ApproveTreasuryWithdrawal` instruction.
+pub fn approve_treasury_withdrawal(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    proposal_id: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::ApproveTreasuryWithdrawal.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*signer, true),
+        AccountMeta::new(find_withdrawal_proposal_address(proposal_id, program_id).0, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `ExecuteTreasuryWithdrawal` instruction.
+pub fn execute_treasury_withdrawal(
+    program_id: &Pubkey,
+    proposer: &Pubkey,
+    recipient: &Pubkey,
+    proposal_id: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::ExecuteTreasuryWithdrawal.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(find_withdrawal_proposal_address(proposal_id, program_id).0, false),
+        AccountMeta::new(*proposer, false),
+        AccountMeta::new(*recipient, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates a `CloseExpiredTreasuryWithdrawal` instruction.
+pub fn close_expired_treasury_withdrawal(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    proposer: &Pubkey,
+    proposal_id: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::CloseExpiredTreasuryWithdrawal.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*signer, true),
+        AccountMeta::new(find_withdrawal_proposal_address(proposal_id, program_id).0, false),
+        AccountMeta::new(*proposer, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminUpdateTreasuryLimits` instruction.
+pub fn admin_update_treasury_limits(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    delay_slots: u64,
+    epoch_cap: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminUpdateTreasuryLimits { delay_slots, epoch_cap }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminQueueTreasuryTransfer` instruction.
+pub fn admin_queue_treasury_transfer(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    receiver: &Pubkey,
+    lamports: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminQueueTreasuryTransfer { lamports }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new_readonly(*receiver, false),
+        AccountMeta::new(find_protocol_treasury_account_address(program_id).0, false),
+        AccountMeta::new_readonly(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminSetPause` instruction.
+pub fn admin_set_pause(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pause_flags: u8,
+    burn_pause_max_slots: u64,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminSetPause { pause_flags, burn_pause_max_slots }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminUpdateCreatorFeeShare` instruction.
+pub fn admin_update_creator_fee_share(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    creator_fee_share_bps: u16,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminUpdateCreatorFeeShare { creator_fee_share_bps }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Creates an `AdminUpdateReferrerFeeShare` instruction.
+pub fn admin_update_referrer_fee_share(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    referrer_fee_share_bps: u16,
+) -> Result<SolanaInstruction, ProgramError>
+{
+    let data = Instruction::AdminUpdateReferrerFeeShare { referrer_fee_share_bps }.pack()?;
+
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(find_config_account_address(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    Ok(SolanaInstruction { program_id: *program_id, accounts, data })
+}
+
+/// Derives the backing account PDA and bump for a mint.
+pub fn find_backing_account_address(
+    mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8)
+{
+    Pubkey::find_program_address(
+        &[
+            mint.as_ref(),
+            program_id.as_ref(),
+            CO_SEED_COINBACKED
+        ],
+        program_id
+    )
+}
+
+/// Derives the backer record PDA and bump for a mint and contributor.
+pub fn find_backer_record_address(
+    mint: &Pubkey,
+    contributor: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8)
+{
+    Pubkey::find_program_address(
+        &[
+            mint.as_ref(),
+            contributor.as_ref(),
+            program_id.as_ref(),
+            CO_SEED_BACKER_RECORD
+        ],
+        program_id
+    )
+}
+
+/// Derives the protocol treasury account PDA and bump.
+pub fn find_protocol_treasury_account_address(
+    program_id: &Pubkey,
+) -> (Pubkey, u8)
+{
+    Pubkey::find_program_address(
+        &[
+            program_id.as_ref(),
+            CO_SEED_PROTOCOL_TREASURY
+        ],
+        program_id
+    )
+}
+
+/// Derives the protocol config account PDA and bump.
+pub fn find_config_account_address(
+    program_id: &Pubkey,
+) -> (Pubkey, u8)
+{
+    Pubkey::find_program_address(
+        &[
+            program_id.as_ref(),
+            CO_SEED_CONFIG
+        ],
+        program_id
+    )
+}
+
+/// Derives the treasury withdrawal proposal PDA and bump for a proposal id.
+pub fn find_withdrawal_proposal_address(
+    proposal_id: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8)
+{
+    Pubkey::find_program_address(
+        &[
+            program_id.as_ref(),
+            CO_SEED_WITHDRAWAL_PROPOSAL,
+            &proposal_id.to_le_bytes()
+        ],
+        program_id
+    )
+}
+
+/// Derives the collateral vault of a backing account, the associated token account of the backing account PDA.
+pub fn find_collateral_vault_address(
+    mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    collateral_token_program_id: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey
+{
+    get_associated_token_address_with_program_id(
+        &find_backing_account_address(mint, program_id).0,
+        collateral_mint,
+        collateral_token_program_id
+    )
+}
+
+/// Derives the program executable data account of an upgradeable program.
+pub fn find_program_executable_data_account_address(
+    program_id: &Pubkey,
+) -> (Pubkey, u8)
+{
+    Pubkey::find_program_address(
+        &[
+            program_id.as_ref()
+        ],
+        &bpf_loader_upgradeable::ID
+    )
+}
+
+// helper functions
+
+fn pack_signed_tos(
+    signed_tos: &str
+) -> Result<[u8; CO_SIGNATURE_BASE58_BYTE_LEN], ProgramError>
+{
+    if signed_tos.len() > CO_SIGNATURE_BASE58_BYTE_LEN
+    {
+        msg!("Terms of service signature exceeds {} bytes. Aborting.", CO_SIGNATURE_BASE58_BYTE_LEN);
+        return Err(CoinbackedError::InvalidSignedTos.into());
+    }
+
+    // zero padded to the fixed length expected by unpack
+    let mut dst = [0u8; CO_SIGNATURE_BASE58_BYTE_LEN];
+    dst[..signed_tos.len()].copy_from_slice(signed_tos.as_bytes());
+
+    Ok(dst)
+}
+
+fn pack_basket_amounts(
+    collateral: &[BasketCollateral]
+) -> Result<[u64; CO_BASKET_MAX_COLLATERAL], ProgramError>
+{
+    if collateral.len() > CO_BASKET_MAX_COLLATERAL
+    {
+        msg!("Basket exceeds {} collateral assets. Aborting.", CO_BASKET_MAX_COLLATERAL);
+        return Err(CoinbackedError::InvalidBasketAccounts.into());
+    }
+
+    let mut amounts = [0u64; CO_BASKET_MAX_COLLATERAL];
+    for (amount, asset) in amounts.iter_mut().zip(collateral.iter())
+    {
+        *amount = asset.amount;
+    }
+
+    Ok(amounts)
+}
+
+fn unpack_basket_amounts(
+    amounts_data: &[u8; CO_BASKET_MAX_COLLATERAL * CO_TOKEN_AMOUNT_BYTE_LEN]
+) -> [u64; CO_BASKET_MAX_COLLATERAL]
+{
+    let mut amounts = [0u64; CO_BASKET_MAX_COLLATERAL];
+    for (amount, amount_data) in amounts.iter_mut().zip(amounts_data.chunks_exact(CO_TOKEN_AMOUNT_BYTE_LEN))
+    {
+        *amount = u64::from_le_bytes(*array_ref![amount_data, 0, CO_TOKEN_AMOUNT_BYTE_LEN]);
+    }
+
+    amounts
+}
+
+fn push_multisig_signers(
+    accounts: &mut Vec<AccountMeta>,
+    signer_pubkeys: &[&Pubkey]
+)
+{
+    // first signer pays the protocol fee
+    for (position, signer_pubkey) in signer_pubkeys.iter().enumerate()
+    {
+        if position == 0
+        {
+            accounts.push(AccountMeta::new(**signer_pubkey, true));
+        }
+        else
+        {
+            accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+        }
+    }
+}
@@ -8,8 +8,10 @@ pub mod processor;
 mod utils;
 mod entrypoint;
 mod state;
-mod instruction;
-mod error;
+pub mod instruction;
+pub mod error;
+pub mod event;
+pub mod return_data;
 mod math;
 
 // for development
@@ -15,6 +15,7 @@ pub trait TrySub: Sized
 }
 
 /// Try to subtract, return an error on overflow
+#[allow(dead_code)]
 pub trait TryAdd: Sized 
 {
     /// Add
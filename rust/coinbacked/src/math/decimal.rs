@@ -4,6 +4,7 @@
 #![allow(clippy::assign_op_pattern)]
 #![allow(clippy::ptr_offset_with_cast)]
 #![allow(clippy::manual_range_contains)]
+#![allow(clippy::manual_div_ceil)]
 
 use crate::
 {
@@ -41,6 +42,19 @@ impl Decimal
             .ok_or(CoinbackedError::MathError)?;
         Ok(u64::try_from(ceil_val).map_err(|_| CoinbackedError::MathError)?)
     }
+
+    /// Ceiling scaled decimal to u64
+    pub fn try_ceil_u64(&self) -> Result<u64, ProgramError>
+    {
+        let ceil_val = Self::wad()
+            .checked_sub(U192::from(1u64))
+            .ok_or(CoinbackedError::MathError)?
+            .checked_add(self.0)
+            .ok_or(CoinbackedError::MathError)?
+            .checked_div(Self::wad())
+            .ok_or(CoinbackedError::MathError)?;
+        Ok(u64::try_from(ceil_val).map_err(|_| CoinbackedError::MathError)?)
+    }
 }
 
 impl fmt::Display for Decimal 
@@ -8,6 +8,7 @@ use solana_program::
     entrypoint::ProgramResult,
     msg,
     system_program,
+    bpf_loader_upgradeable,
     rent::Rent,
     pubkey::Pubkey,
     program_error::ProgramError,
@@ -18,38 +19,60 @@ use solana_program::
     system_instruction::
     {
         create_account, 
-        transfer
+        transfer,
+        allocate,
+        assign
     },
     program::
     {
         invoke, 
-        invoke_signed
+        invoke_signed,
+        set_return_data
     },
-    sysvar::{Sysvar, rent},
+    sysvar::{self, Sysvar, rent},
+    clock::Clock,
 };
 
 use arrayref::array_ref;
 
-use spl_token::
+use spl_token_2022::
 {
     state::
     {
         Account, 
-        Mint
+        Mint,
+        Multisig
     },
     instruction::
     {
         burn, 
-        close_account
+        close_account,
+        transfer_checked,
+        MAX_SIGNERS
     },
+    extension::
+    {
+        StateWithExtensions,
+        BaseStateWithExtensions,
+        ExtensionType
+    },
+};
+
+use spl_associated_token_account::
+{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
 };
 
 use crate::
 {
     utils::*,
-    state::{BackingAccount, TreasuryAccount},
-    instruction::Instruction,
+    state::{BackingAccount, BackingStats, TreasuryAccount, BackerRecord, ConfigAccount, WithdrawalProposal},
+    instruction::{Instruction, CO_PAUSE_CREATE, CO_PAUSE_ADD, CO_PAUSE_VALIDATE, CO_PAUSE_BURN, CO_PAUSE_ALL},
     math::{Decimal, TryMul, TrySub, TryDiv},
+    error::CoinbackedError,
+    event::{Event, EventKind, TreasuryEvent},
+    return_data::*,
 };
 
 /// Instruction processor
@@ -68,6 +91,8 @@ pub fn process_instruction(
                 program_id, 
                 accounts, 
                 lamports, 
+                0,
+                None,
                 signed_tos)?;         
         },
 
@@ -76,7 +101,17 @@ pub fn process_instruction(
             msg!("Instruction: Validate Backing Account");  
             process_validate_backing_account(
                 program_id, 
-                accounts)?;
+                accounts,
+                false)?;
+        },
+
+        Instruction::ValidateBackingAccountStrict =>
+        {
+            msg!("Instruction: Validate Backing Account Strict");
+            process_validate_backing_account(
+                program_id,
+                accounts,
+                true)?;
         },
 
         Instruction::AddToBalanceOfBackingAccount {lamports, signed_tos} =>
@@ -96,6 +131,32 @@ pub fn process_instruction(
                 program_id, 
                 accounts, 
                 amount, 
+                None,
+                false,
+                signed_tos)?;
+        },
+
+        Instruction::BurnTokenAndFreeBalanceWithMinPayout {amount, min_lamports_out, signed_tos} =>
+        {
+            msg!("Instruction: Burn Token and Free Balance With Min Payout");
+            process_burn_token_and_free_balanace(
+                program_id,
+                accounts,
+                amount,
+                Some(min_lamports_out),
+                false,
+                signed_tos)?;
+        },
+
+        Instruction::BurnTokenAndFreeBalanceToRecipient {amount, min_lamports_out, signed_tos} =>
+        {
+            msg!("Instruction: Burn Token and Free Balance To Recipient");
+            process_burn_token_and_free_balanace(
+                program_id,
+                accounts,
+                amount,
+                Some(min_lamports_out),
+                true,
                 signed_tos)?;
         },
 
@@ -103,6 +164,78 @@ pub fn process_instruction(
         {
             msg!("Instruction: Clean Accounts After Burning");
             process_clean_accounts_after_burning(
+                program_id,
+                accounts)?;
+        },
+
+        Instruction::QuotePayout {amount} =>
+        {
+            msg!("Instruction: Quote Payout");
+            process_quote_payout(
+                program_id,
+                accounts,
+                amount)?;
+        },
+
+        Instruction::CreateCollateralBackingAccount {amount, signed_tos} =>
+        {
+            msg!("Instruction: Create Collateral Backing Account");
+            process_create_collateral_backing_account(
+                program_id,
+                accounts,
+                amount,
+                signed_tos)?;
+        },
+
+        Instruction::AddCollateralToBalanceOfBackingAccount {amount, signed_tos} =>
+        {
+            msg!("Instruction: Add Collateral to Balance of Backing Account");
+            process_add_collateral_to_balance_of_backing_account(
+                program_id,
+                accounts,
+                amount,
+                signed_tos)?;
+        },
+
+        Instruction::BurnTokenAndFreeCollateral {amount, min_amount_out, signed_tos} =>
+        {
+            msg!("Instruction: Burn Token and Free Collateral");
+            process_burn_token_and_free_collateral(
+                program_id,
+                accounts,
+                amount,
+                min_amount_out,
+                signed_tos)?;
+        },
+
+        Instruction::CreateBasketBackingAccount {lamports, amounts, signed_tos} =>
+        {
+            msg!("Instruction: Create Basket Backing Account");
+            process_create_basket_backing_account(
+                program_id,
+                accounts,
+                lamports,
+                amounts,
+                signed_tos)?;
+        },
+
+        Instruction::BurnTokenAndFreeBasket {amount, min_lamports_out, min_amounts_out, signed_tos} =>
+        {
+            msg!("Instruction: Burn Token and Free Basket");
+            process_burn_token_and_free_basket(
+                program_id,
+                accounts,
+                amount,
+                min_lamports_out,
+                min_amounts_out,
+                signed_tos)?;
+        },
+
+        Instruction::MigrateBackingAccount =>
+        {
+            msg!("Instruction: Migrate Backing Account");
+            process_migrate_backing_account(
+                program_id,
                 accounts)?;
         },
 
@@ -122,6 +255,185 @@ pub fn process_instruction(
                 accounts, 
                 lamports)?;               
         },
+
+        Instruction::AdminInitConfigAccount {create_fee, validate_fee, add_fee, burn_fee, min_backing_lamports} =>
+        {
+            msg!("Instruction: Admin Init Config Account");
+            process_admin_init_config_account(
+                program_id,
+                accounts,
+                create_fee,
+                validate_fee,
+                add_fee,
+                burn_fee,
+                min_backing_lamports)?;
+        },
+
+        Instruction::AdminUpdateConfigAccount {create_fee, validate_fee, add_fee, burn_fee, min_backing_lamports} =>
+        {
+            msg!("Instruction: Admin Update Config Account");
+            process_admin_update_config_account(
+                program_id,
+                accounts,
+                create_fee,
+                validate_fee,
+                add_fee,
+                burn_fee,
+                min_backing_lamports)?;
+        },
+
+        Instruction::AdminUpdateConfigFeeRates {burn_fee_bps, burn_fee_max, add_fee_bps, add_fee_max} =>
+        {
+            msg!("Instruction: Admin Update Config Fee Rates");
+            process_admin_update_config_fee_rates(
+                program_id,
+                accounts,
+                burn_fee_bps,
+                burn_fee_max,
+                add_fee_bps,
+                add_fee_max)?;
+        },
+
+        Instruction::AdminProposeAdmin {new_admin} =>
+        {
+            msg!("Instruction: Admin Propose Admin");
+            process_admin_propose_admin(
+                program_id,
+                accounts,
+                new_admin)?;
+        },
+
+        Instruction::AdminAcceptAdmin =>
+        {
+            msg!("Instruction: Admin Accept Admin");
+            process_admin_accept_admin(
+                program_id,
+                accounts)?;
+        },
+
+        Instruction::AdminMakeProgramImmutable =>
+        {
+            msg!("Instruction: Admin Make Program Immutable");
+            process_admin_make_program_immutable(
+                program_id,
+                accounts)?;
+        },
+
+        Instruction::AdminSetTreasurySigners {threshold, signers} =>
+        {
+            msg!("Instruction: Admin Set Treasury Signers");
+            process_admin_set_treasury_signers(
+                program_id,
+                accounts,
+                threshold,
+                signers)?;
+        },
+
+        Instruction::ProposeTreasuryWithdrawal {proposal_id, lamports, expiry_slot} =>
+        {
+            msg!("Instruction: Propose Treasury Withdrawal");
+            process_propose_treasury_withdrawal(
+                program_id,
+                accounts,
+                proposal_id,
+                lamports,
+                expiry_slot)?;
+        },
+
+        Instruction::ApproveTreasuryWithdrawal =>
+        {
+            msg!("Instruction: Approve Treasury Withdrawal");
+            process_approve_treasury_withdrawal(
+                program_id,
+                accounts)?;
+        },
+
+        Instruction::ExecuteTreasuryWithdrawal =>
+        {
+            msg!("Instruction: Execute Treasury Withdrawal");
+            process_execute_treasury_withdrawal(
+                program_id,
+                accounts)?;
+        },
+
+        Instruction::CloseExpiredTreasuryWithdrawal =>
+        {
+            msg!("Instruction: Close Expired Treasury Withdrawal");
+            process_close_expired_treasury_withdrawal(
+                program_id,
+                accounts)?;
+        },
+
+        Instruction::AdminUpdateTreasuryLimits {delay_slots, epoch_cap} =>
+        {
+            msg!("Instruction: Admin Update Treasury Limits");
+            process_admin_update_treasury_limits(
+                program_id,
+                accounts,
+                delay_slots,
+                epoch_cap)?;
+        },
+
+        Instruction::AdminQueueTreasuryTransfer {lamports} =>
+        {
+            msg!("Instruction: Admin Queue Treasury Transfer");
+            process_admin_queue_treasury_transfer(
+                program_id,
+                accounts,
+                lamports)?;
+        },
+
+        Instruction::AdminSetPause {pause_flags, burn_pause_max_slots} =>
+        {
+            msg!("Instruction: Admin Set Pause");
+            process_admin_set_pause(
+                program_id,
+                accounts,
+                pause_flags,
+                burn_pause_max_slots)?;
+        },
+
+        Instruction::CreateBackingAccountWithCreator{lamports, exit_fee_bps, creator, signed_tos} =>
+        {
+            msg!("Instruction: Create Backing Account With Creator");
+            process_create_backing_account(
+                program_id,
+                accounts,
+                lamports,
+                exit_fee_bps,
+                creator,
+                signed_tos)?;
+        },
+
+        Instruction::AdminUpdateCreatorFeeShare{creator_fee_share_bps} =>
+        {
+            msg!("Instruction: Admin Update Creator Fee Share");
+            process_admin_update_creator_fee_share(
+                program_id,
+                accounts,
+                creator_fee_share_bps)?;
+        },
+
+        Instruction::AdminUpdateReferrerFeeShare{referrer_fee_share_bps} =>
+        {
+            msg!("Instruction: Admin Update Referrer Fee Share");
+            process_admin_update_referrer_fee_share(
+                program_id,
+                accounts,
+                referrer_fee_share_bps)?;
+        },
+
+        Instruction::CreateBackingAccountWithExitFee{lamports, exit_fee_bps, signed_tos} =>
+        {
+            msg!("Instruction: Create Backing Account With Exit Fee");
+            process_create_backing_account(
+                program_id,
+                accounts,
+                lamports,
+                exit_fee_bps,
+                None,
+                signed_tos)?;
+        },
     }
 
     Ok(())
@@ -131,6 +443,8 @@ fn process_create_backing_account(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     lamports: u64,
+    exit_fee_bps: u16,
+    creator: Option<Pubkey>,
     signed_tos: String,
 ) -> ProgramResult
 {
@@ -145,68 +459,118 @@ fn process_create_backing_account(
     let system_program = next_account_info(account_info_iter)?;
     let rent_sysvar = next_account_info(account_info_iter)?;
 
+    // optional record of contributor and referrer, config follows them
+    let (trailing_accounts, config_account, _) = split_config_account(program_id, account_info_iter.as_slice());
+    let (backer_record_account, referrer_account) = get_backer_record_and_referrer(program_id, source_account, mint_account, trailing_accounts, &[])?;
+
     // checking if payer account is the signer
     if !source_account.is_signer 
     {
         msg!("Account is not signer! Aborting.");
-        return Err(ProgramError::MissingRequiredSignature);
+        return Err(CoinbackedError::AccountNotSigner.into());
     }
 
     // checking if accounts are writable
     if !backing_pda.is_writable || !protocol_treasury_account.is_writable
     {
         msg!("Required accounts not writable! Aborting.");
-        return Err(ProgramError::InvalidAccountData);
+        return Err(CoinbackedError::AccountNotWritable.into());
     }
 
     // system & rent correct?
     if system_program.key.ne(&system_program::ID) || rent_sysvar.key.ne(&rent::ID)
     {
         msg!("Invalid system programs! Aborting.");
-        return Err(ProgramError::IncorrectProgramId);
+        return Err(CoinbackedError::InvalidSystemProgram.into());
     }
 
     // unpack SPL-related accounts
-    let token_account_spl = Account::unpack(&token_account.try_borrow_data()?)?;
+    let token_account_spl = unpack_token_account(token_account)?;
+    let mint_account_spl = unpack_mint(mint_account)?;
+
+    // refuse mints whose extensions break the payout guarantee
+    if has_unsupported_mint_extension(mint_account)?
+    {
+        msg!("Mint has extension incompatible with backing! Aborting.");
+        return Err(CoinbackedError::UnsupportedMintExtension.into());
+    }
 
     // make sure that token account belongs to mint
     if token_account_spl.mint.ne(mint_account.key)
     {
         msg!("Token account does not belong to mint! Aborting.");
-        return Err(ProgramError::InvalidInstructionData); 
+        return Err(CoinbackedError::TokenAccountMintMismatch.into());
     }
 
     // make sure that source account is owner of token account
     if token_account_spl.owner.ne(source_account.key)
     {
         msg!("Token account does not belong to signer! Aborting.");
-        return Err(ProgramError::InvalidInstructionData); 
+        return Err(CoinbackedError::TokenAccountOwnerMismatch.into());
     }
 
     // make sure that token account has token amount > 0, only holders can back tokens
     if token_account_spl.amount == 0
     {
         msg!("Token account does not store any token amount, but required for backing! Aborting.");
-        return Err(ProgramError::InvalidInstructionData); 
+        return Err(CoinbackedError::TokenAccountEmpty.into());
     }
 
     // check backing pda
     let bump = check_backing_account(backing_pda, mint_account, program_id, true)?;
 
+    // protocol config, lamport backing has to reach configured minimum
+    let config = unpack_config_account_or_default(config_account, program_id)?;
+    check_not_paused(&config, CO_PAUSE_CREATE)?;
+    if lamports < config.min_backing_lamports
+    {
+        msg!("Backing of {} lamports is below minimum {}! Aborting.", lamports, config.min_backing_lamports);
+        return Err(CoinbackedError::BackingBelowMinimum.into());
+    }
+
+    // exit fee is capped to keep burning worthwhile
+    if exit_fee_bps > CO_EXIT_FEE_MAX_BPS
+    {
+        msg!("Exit fee of {} bps exceeds maximum of {} bps! Aborting.", exit_fee_bps, CO_EXIT_FEE_MAX_BPS);
+        return Err(CoinbackedError::InvalidExitFee.into());
+    }
+
     // log ToS signature
     msg!("All pre-checks passed. User signed terms of service: {}", signed_tos);
 
     // create backing account and back with sol
+    let slot = Clock::get()?.slot;
+    let mut actual_account_data = BackingAccount
+    {
+            version: CO_ACCOUNT_BACKING_VERSION,
+            token_key: *mint_account.key,
+            rent_excemption: 0,
+            bump,
+            stats: Some(
+                BackingStats
+                {
+                    total_deposited: lamports,
+                    creation_slot: slot,
+                    last_activity_slot: slot,
+                    ..BackingStats::default()
+                }
+            ),
+            exit_fee_bps,
+            creator,
+            collateral_mints: Vec::new()
+    };
+
     let rent = Rent::get()?;
-    let min_excemption_balance = rent.minimum_balance(CO_ACCOUNT_BACKING_BYTE_LEN).max(1);
+    let min_excemption_balance = rent.minimum_balance(actual_account_data.byte_len()).max(1);
+    actual_account_data.rent_excemption = min_excemption_balance;
 
     invoke_signed(
         &create_account(
-            &source_account.key,
-            &backing_pda.key,
+            source_account.key,
+            backing_pda.key,
             min_excemption_balance + lamports,
-            CO_ACCOUNT_BACKING_BYTE_LEN as u64,
-            &program_id
+            actual_account_data.byte_len() as u64,
+            program_id
         ), 
         &[
             source_account.clone(), 
@@ -222,25 +586,44 @@ fn process_create_backing_account(
         ]]
     )?;
 
-    let actual_account_data = BackingAccount
-    {
-            token_key: *mint_account.key,
-            rent_excemption: min_excemption_balance, 
-            bump: bump
-    };
-
     let data = &mut backing_pda.try_borrow_mut_data()?[..];
     actual_account_data.pack(data);
 
-    // pay protocol
-    pay_protocol(source_account, protocol_treasury_account, program_id, accounts, true)?;
+    // pay protocol, referrer takes configured share
+    let fee_shares: Vec<_> = get_referrer_fee_share(referrer_account, &config).into_iter().collect();
+    pay_protocol_with_shares(source_account, protocol_treasury_account, mint_account, program_id, accounts, config.create_fee, &fee_shares, true)?;
+
+    // track contribution
+    if let Some(backer_record_account) = backer_record_account
+    {
+        record_backer_contribution(program_id, source_account, mint_account, backer_record_account, lamports, accounts)?;
+    }
+
+    Event
+    {
+        kind: EventKind::CreateBackingAccount,
+        mint: *mint_account.key,
+        actor: *source_account.key,
+        counterparty: *backing_pda.key,
+        lamports,
+        token_amount: 0,
+        supply_before: mint_account_spl.supply,
+        supply_after: mint_account_spl.supply,
+        per_unit_payout: get_per_unit_payout(
+            mint_account_spl.decimals,
+            mint_account_spl.supply,
+            backing_pda.lamports(),
+            min_excemption_balance
+        )?,
+    }.emit();
 
     Ok(())
 }
 
 fn process_validate_backing_account(
     program_id: &Pubkey,
-    accounts: &[AccountInfo]
+    accounts: &[AccountInfo],
+    strict: bool
 ) -> ProgramResult
 {
     let account_info_iter = &mut accounts.iter();
@@ -250,7 +633,12 @@ fn process_validate_backing_account(
     let backing_pda = next_account_info(account_info_iter)?;
     let protocol_treasury_account = next_account_info(account_info_iter)?;
 
-    let mut validation_failure = false;
+    // system program and collateral vaults follow, config trails them
+    let (trailing_accounts, config_account, _) = split_config_account(program_id, account_info_iter.as_slice());
+    let account_info_iter = &mut trailing_accounts.iter();
+
+    // bitmask of passed checks
+    let mut checks = 0;
     
     // pda check
     let seeds = &[
@@ -259,64 +647,105 @@ fn process_validate_backing_account(
         CO_SEED_COINBACKED
     ];
 
-    let (backing_pda_key, bump) = Pubkey::find_program_address(seeds, &program_id);
+    let (backing_pda_key, bump) = Pubkey::find_program_address(seeds, program_id);
 
     if backing_pda_key.ne(backing_pda.key)
     {
         msg!("Validation failure: Backing account address is not valid PDA for mint.");
-        validation_failure = true;
     }
     else
     {
         msg!("Validation success: Backing account address is valid PDA for mint.");
+        checks |= CO_VALIDATION_CHECK_PDA;
     }
 
-    let backing_account = BackingAccount::unpack(&backing_pda.data.borrow()[..])?;
-    let mint_account_spl = Mint::unpack(&mut mint_account.data.borrow_mut())?;
+    let backing_account = unpack_backing_account(backing_pda, program_id)?;
+    let mint_account_spl = unpack_mint(mint_account)?;
 
     // backing account belongs to mint
     if backing_account.token_key.ne(mint_account.key)
     {
         msg!("Validation failure: Backing account not pointing to mint account.");
-        validation_failure = true;
     }
     else
     {
         msg!("Validation success: Backing account pointing to mint account.");
+        checks |= CO_VALIDATION_CHECK_MINT;
     }
 
     // backing account holds at least rent excemption
     if backing_account.rent_excemption > **backing_pda.lamports.borrow()
     {
         msg!("Validation failure: Backing account does not hold rent excemption.");
-        validation_failure = true;
     }
     else
     {
         msg!("Validation success: Backing account holds at least rent excempt.");
+        checks |= CO_VALIDATION_CHECK_RENT_EXCEMPTION;
     }
 
     // seed bump check
     if backing_account.bump != bump
     {
         msg!("Validation failure: Backing account bump is incorrect.");
-        validation_failure = true;
     }
     else
     {
         msg!("Validation success: Backing account bump is correct.");
+        checks |= CO_VALIDATION_CHECK_BUMP;
     }
 
-    // additional information: current payout per unit
-    let per_unit_payout = get_payout_in_lamport(
-        token_amount_one_unit(mint_account_spl.decimals), 
-        mint_account_spl.supply, 
-        backing_pda.lamports(), 
-        backing_account.rent_excemption
-    )?;
+    // mint extensions check
+    if has_unsupported_mint_extension(mint_account)?
+    {
+        msg!("Validation failure: Mint has extension incompatible with backing.");
+    }
+    else
+    {
+        msg!("Validation success: Mint has no extension incompatible with backing.");
+        checks |= CO_VALIDATION_CHECK_MINT_EXTENSIONS;
+    }
+
+    // additional information: current payout per unit, nothing to pay out if rent excemption is not covered
+    let per_unit_payout = if checks & CO_VALIDATION_CHECK_RENT_EXCEMPTION != 0
+    {
+        get_per_unit_payout(
+            mint_account_spl.decimals, 
+            mint_account_spl.supply, 
+            backing_pda.lamports(), 
+            backing_account.rent_excemption
+        )?
+    }
+    else
+    {
+        0
+    };
     
     msg!("Information only: Current per token unit payout is: {} lamport / {}", per_unit_payout, Sol(per_unit_payout));
 
+    // collateral backed accounts report each asset, vaults follow the system program in basket order
+    let collateral = if backing_account.collateral_mints.is_empty()
+    {
+        Vec::new()
+    }
+    else
+    {
+        next_account_info(account_info_iter)?;
+        get_collateral_quotes(
+            backing_pda,
+            &backing_account,
+            account_info_iter,
+            token_amount_one_unit(mint_account_spl.decimals),
+            mint_account_spl.decimals,
+            mint_account_spl.supply
+        )?
+    };
+
+    for collateral_quote in collateral.iter()
+    {
+        msg!("Information only: Current per token unit collateral payout is: {} of {}", collateral_quote.per_unit_payout, collateral_quote.mint);
+    }
+
     // check if mint is of fixed supply, if not warn that payout floor is not fixed...
     if mint_account_spl.mint_authority.is_none()
     {
@@ -327,18 +756,46 @@ fn process_validate_backing_account(
         msg!("Information only: (WARNING) supply of token is NOT fixed, payout floor might decrease.");
     }
 
+    // exit fee stays in backing, raising the floor for remaining holders
+    if backing_account.exit_fee_bps > 0
+    {
+        msg!("Information only: exit fee of {} bps is retained in backing on burns.", backing_account.exit_fee_bps);
+    }
+
+    let report = ValidationReport
+    {
+        checks,
+        per_unit_payout,
+        mint_authority_revoked: mint_account_spl.mint_authority.is_none(),
+        collateral,
+        exit_fee_bps: backing_account.exit_fee_bps,
+    };
+
     // overall result
-    if validation_failure
+    if !report.is_valid()
     {
         msg!("Overall result: validation failed.");
+
+        if strict
+        {
+            return Err(CoinbackedError::ValidationFailed.into());
+        }
     }
     else
     {
         msg!("Overall result: validation sucessfull.");
     }
 
+    // protocol config
+    let config = unpack_config_account_or_default(config_account, program_id)?;
+    check_not_paused(&config, CO_PAUSE_VALIDATE)?;
+
     // pay protocol
-    pay_protocol(payer_account, protocol_treasury_account, program_id, accounts, true)?;
+    pay_protocol(payer_account, protocol_treasury_account, program_id, accounts, config.validate_fee, true)?;
+
+    let mut data = [0u8; CO_RETURN_VALIDATION_REPORT_BYTE_LEN + CO_RETURN_BASKET_BYTE_LEN + CO_RETURN_EXIT_FEE_BYTE_LEN];
+    report.pack(&mut data);
+    set_return_data(&data);
 
     Ok(())
 }
@@ -357,42 +814,89 @@ fn process_add_to_balance_of_backing_account(
     let backing_pda = next_account_info(account_info_iter)?;
     let protocol_treasury_account = next_account_info(account_info_iter)?;
 
+    // optional record of contributor and referrer follow the system program, config trails them
+    let (trailing_accounts, config_account, after_config_accounts) = split_config_account(program_id, account_info_iter.as_slice().get(1..).unwrap_or_default());
+
      // checking if payer account is the signer
      if !source_account.is_signer 
      {
          msg!("Account is not signer! Aborting.");
-         return Err(ProgramError::MissingRequiredSignature);
+         return Err(CoinbackedError::AccountNotSigner.into());
      }
 
     // checking if accounts are writable
     if !backing_pda.is_writable || !protocol_treasury_account.is_writable
     {
         msg!("Required accounts not writable! Aborting.");
-        return Err(ProgramError::InvalidAccountData);
+        return Err(CoinbackedError::AccountNotWritable.into());
     }
 
-    // check backing pda
+    // check backing pda, lamports are only added to lamport backed accounts
     check_backing_account(backing_pda, mint_account, program_id, false)?;
+    check_backing_collateral(backing_pda, program_id, None)?;
 
     // check mint, only increase balance if there is still tokens to guarantee payout
-    let mint_account_spl = Mint::unpack(&mut mint_account.data.borrow_mut())?;
+    let mint_account_spl = unpack_mint(mint_account)?;
     if mint_account_spl.supply == 0
     {
         msg!("Mint supply is 0, cannot add balance! Aborting.");
-        return Err(ProgramError::InvalidAccountData);
+        return Err(CoinbackedError::MintSupplyZero.into());
     }
 
+    // do not add to backing of mints whose extensions break the payout guarantee
+    if has_unsupported_mint_extension(mint_account)?
+    {
+        msg!("Mint has extension incompatible with backing! Aborting.");
+        return Err(CoinbackedError::UnsupportedMintExtension.into());
+    }
+
+    // protocol config
+    let config = unpack_config_account_or_default(config_account, program_id)?;
+    check_not_paused(&config, CO_PAUSE_ADD)?;
+
     // log ToS signature
     msg!("All pre-checks passed. User signed terms of service: {}", signed_tos);
 
     // transfer lamports to backing account
     invoke(
         &transfer(source_account.key, backing_pda.key, lamports),
-        &accounts
+        accounts
     )?;
 
-    // pay protocol
-    pay_protocol(source_account, protocol_treasury_account, program_id, accounts, true)?;
+    // pay protocol, creator of backing and referrer take configured shares
+    let backing_account = unpack_backing_account(backing_pda, program_id)?;
+    let (backer_record_account, referrer_account) = get_backer_record_and_referrer(program_id, source_account, mint_account, trailing_accounts, &[])?;
+    let fee_shares: Vec<_> = get_creator_fee_share(&backing_account, &config, after_config_accounts)?.into_iter()
+        .chain(get_referrer_fee_share(referrer_account, &config))
+        .collect();
+    let protocol_fee = get_protocol_fee(lamports, config.add_fee, config.add_fee_bps, config.add_fee_max)?;
+    pay_protocol_with_shares(source_account, protocol_treasury_account, mint_account, program_id, accounts, protocol_fee, &fee_shares, true)?;
+
+    record_backing_activity(backing_pda, program_id, lamports, 0, None)?;
+
+    // track contribution
+    if let Some(backer_record_account) = backer_record_account
+    {
+        record_backer_contribution(program_id, source_account, mint_account, backer_record_account, lamports, accounts)?;
+    }
+
+    Event
+    {
+        kind: EventKind::AddToBalanceOfBackingAccount,
+        mint: *mint_account.key,
+        actor: *source_account.key,
+        counterparty: *backing_pda.key,
+        lamports,
+        token_amount: 0,
+        supply_before: mint_account_spl.supply,
+        supply_after: mint_account_spl.supply,
+        per_unit_payout: get_per_unit_payout(
+            mint_account_spl.decimals,
+            mint_account_spl.supply,
+            backing_pda.lamports(),
+            backing_account.rent_excemption
+        )?,
+    }.emit();
 
     Ok(())
 }
@@ -402,6 +906,8 @@ fn process_burn_token_and_free_balanace
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     amount: u64,
+    min_lamports_out: Option<u64>,
+    with_recipient: bool,
     signed_tos: String
 ) -> ProgramResult
 {
@@ -414,66 +920,138 @@ fn process_burn_token_and_free_balanace
     let protocol_treasury_account = next_account_info(account_info_iter)?;
 
     let token_program =  next_account_info(account_info_iter)?;
-    
-     // checking if payer account is the signer
-     if !owner_account.is_signer 
-     {
-         msg!("Account is not signer! Aborting.");
-         return Err(ProgramError::MissingRequiredSignature);
-     }
 
-    // token program correct?
-    if token_program.key.ne(&spl_token::ID)
+    // token program correct and owning mint?
+    if !is_token_program(token_program.key) || mint_account.owner.ne(token_program.key)
     {
         msg!("Invalid token program! Aborting.");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    // checking if accounts are writable
-    if !backing_pda.is_writable 
-        || !protocol_treasury_account.is_writable 
-        || !token_account.is_writable 
-        || !owner_account.is_writable
-    {
-        msg!("Required accounts not writable! Aborting.");
-        return Err(ProgramError::InvalidAccountData);
+        return Err(CoinbackedError::InvalidTokenProgram.into());
     }
 
     // unpack SPL-related accounts
-    let token_account_spl = Account::unpack(&mut token_account.data.borrow_mut())?;
-    let mint_account_spl = Mint::unpack(&mut mint_account.data.borrow_mut())?;
+    let token_account_spl = unpack_token_account(token_account)?;
+    let mint_account_spl = unpack_mint(mint_account)?;
     
     // make sure that token account belongs to mint
     if token_account_spl.mint.ne(mint_account.key)
     {
         msg!("Token account does not belong to mint! Aborting.");
-        return Err(ProgramError::InvalidAccountData); 
+        return Err(CoinbackedError::TokenAccountMintMismatch.into());
     }
 
-    // make sure that source account is owner of token account
-    if token_account_spl.owner.ne(owner_account.key)
+    // make sure that source account is owner of token account or approved delegate
+    let is_delegate = token_account_spl.owner.ne(owner_account.key);
+    if is_delegate
     {
-        msg!("Token account does not belong to signer! Aborting.");
-        return Err(ProgramError::InvalidInstructionData); 
+        if token_account_spl.delegate.ne(&Some(*owner_account.key).into())
+        {
+            msg!("Token account does not belong to signer! Aborting.");
+            return Err(CoinbackedError::TokenAccountOwnerMismatch.into());
+        }
+
+        if token_account_spl.delegated_amount < amount
+        {
+            msg!("Delegated amount {} too small! Aborting.", token_account_spl.delegated_amount);
+            return Err(CoinbackedError::InsufficientDelegatedAmount.into());
+        }
     }
 
-    // check backing pda
-    check_backing_account(backing_pda, mint_account, program_id, false)?;
+    // payout goes to signing owner, to token owner if signer is delegate, or to an explicit recipient
+    let recipient_account = if with_recipient || is_delegate
+    {
+        next_account_info(account_info_iter)?
+    }
+    else
+    {
+        owner_account
+    };
 
-    // log ToS signature
-    msg!("All pre-checks passed. User signed terms of service: {}", signed_tos);
+    if is_delegate && !with_recipient && recipient_account.key.ne(&token_account_spl.owner)
+    {
+        msg!("Payout of delegated burn has to go to token owner! Aborting.");
+        return Err(CoinbackedError::InvalidRecipientAccount.into());
+    }
 
-    // calculate lamports to be transfered from backing
-    let backing_account = BackingAccount::unpack(&backing_pda.try_borrow_data().unwrap()[..])?;
+    // multisig cannot hold the payout
+    if recipient_account.key.eq(owner_account.key) && is_multisig(owner_account)
+    {
+        msg!("Burning with multisig authority requires explicit recipient! Aborting.");
+        return Err(CoinbackedError::InvalidRecipientAccount.into());
+    }
 
-    let total_payout = get_payout_in_lamport(
-        amount,
-        mint_account_spl.supply, 
-        backing_pda.lamports(), 
-        backing_account.rent_excemption
-     )?;
+    // checking if authority signed, remaining accounts are multisig signers and referrer, config and optional creator follow them
+    let (trailing_accounts, config_account, after_config_accounts) = split_config_account(program_id, account_info_iter.as_slice());
+    let signer_keys = get_token_authority_signers(owner_account, trailing_accounts)?;
 
-     msg!("Calculated payout for burning {} tokens is: {}", amount, total_payout);
+    // checking if accounts are writable
+    if !backing_pda.is_writable 
+        || !protocol_treasury_account.is_writable 
+        || !token_account.is_writable 
+        || !recipient_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // explicit recipient has to be a plain system account, token owner may also be a program or vault account
+    if recipient_account.key.ne(owner_account.key)
+        && recipient_account.key.ne(&token_account_spl.owner)
+        && recipient_account.owner.ne(&system_program::ID)
+    {
+        msg!("Recipient account is not owned by system program! Aborting.");
+        return Err(CoinbackedError::InvalidRecipientAccount.into());
+    }
+
+    // check backing pda
+    check_backing_account(backing_pda, mint_account, program_id, false)?;
+    check_backing_collateral(backing_pda, program_id, None)?;
+
+    // protocol config
+    let config = unpack_config_account_or_default(config_account, program_id)?;
+    check_not_paused(&config, CO_PAUSE_BURN)?;
+
+    // log ToS signature
+    msg!("All pre-checks passed. User signed terms of service: {}", signed_tos);
+
+    // calculate lamports to be transfered from backing
+    let backing_account = unpack_backing_account(backing_pda, program_id)?;
+
+    let total_payout = get_payout_in_lamport_after_exit_fee(
+        amount,
+        mint_account_spl.supply, 
+        backing_pda.lamports(), 
+        backing_account.rent_excemption,
+        backing_account.exit_fee_bps
+     )?;
+
+     msg!("Calculated payout for burning {} tokens is: {}", amount, total_payout);
+
+    let protocol_fee = get_protocol_fee(total_payout, config.burn_fee, config.burn_fee_bps, config.burn_fee_max)?;
+
+    // protocol fee is taken from the payout, recipient balance must not decrease
+    if recipient_account.key.ne(owner_account.key) && total_payout < protocol_fee
+    {
+        msg!("Payout {} does not cover protocol fee! Aborting.", total_payout);
+        return Err(CoinbackedError::InsufficientFundsForProtocolFee.into());
+    }
+
+    // slippage protection, recipient pays the protocol fee out of the payout
+    if let Some(min_lamports_out) = min_lamports_out
+    {
+        let net_payout = total_payout.saturating_sub(protocol_fee);
+        if net_payout < min_lamports_out
+        {
+            msg!("Payout {} after protocol fee is below minimum {}! Aborting.", net_payout, min_lamports_out);
+            return Err(CoinbackedError::PayoutBelowMinimum.into());
+        }
+    }
+
+    // creator of backing and referrer take configured shares of protocol fee
+    let claimed_keys: Vec<Pubkey> = signer_keys.iter().map(|key| **key).collect();
+    let referrer_account = get_referrer_account(trailing_accounts, &claimed_keys, &[owner_account.key, &token_account_spl.owner, recipient_account.key])?;
+    let fee_shares: Vec<_> = get_creator_fee_share(&backing_account, &config, after_config_accounts)?.into_iter()
+        .chain(get_referrer_fee_share(referrer_account, &config))
+        .collect();
 
     invoke(
         &burn(
@@ -481,7 +1059,7 @@ fn process_burn_token_and_free_balanace
             token_account.key,
             mint_account.key,
             owner_account.key,
-            &[owner_account.key],
+            &signer_keys,
             amount
         )?,accounts
     )?;
@@ -489,19 +1067,43 @@ fn process_burn_token_and_free_balanace
     // transfer lamports from backing to target
     **backing_pda.try_borrow_mut_lamports()? = 
         backing_pda.lamports().checked_sub(total_payout)
-        .ok_or(ProgramError::InvalidAccountData)?;
+        .ok_or(CoinbackedError::InsufficientBackingFunds)?;
 
-    **owner_account.try_borrow_mut_lamports()? = 
-        owner_account.lamports().checked_add(total_payout)
-        .ok_or(ProgramError::InvalidAccountData)?;
+    **recipient_account.try_borrow_mut_lamports()? = 
+        recipient_account.lamports().checked_add(total_payout)
+        .ok_or(CoinbackedError::MathError)?;
 
     // pay protocol
-    pay_protocol(owner_account, protocol_treasury_account, program_id, accounts, false)?;
+    pay_protocol_with_shares(recipient_account, protocol_treasury_account, mint_account, program_id, accounts, protocol_fee, &fee_shares, false)?;
+
+    record_backing_activity(backing_pda, program_id, 0, total_payout, Some(amount))?;
+
+    let supply_after = mint_account_spl.supply.checked_sub(amount)
+        .ok_or(CoinbackedError::MathError)?;
+
+    Event
+    {
+        kind: EventKind::BurnTokenAndFreeBalance,
+        mint: *mint_account.key,
+        actor: *owner_account.key,
+        counterparty: *recipient_account.key,
+        lamports: total_payout,
+        token_amount: amount,
+        supply_before: mint_account_spl.supply,
+        supply_after,
+        per_unit_payout: get_per_unit_payout(
+            mint_account_spl.decimals,
+            supply_after,
+            backing_pda.lamports(),
+            backing_account.rent_excemption
+        )?,
+    }.emit();
 
     Ok(())
 }
 
 fn process_clean_accounts_after_burning(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult
 {
@@ -515,17 +1117,29 @@ fn process_clean_accounts_after_burning(
     let token_program =  next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
 
-    // checking if payer account is the signer
-    if !owner_account.is_signer 
+    // token program correct?
+    if !is_token_program(token_program.key)
     {
-        msg!("Account is not signer! Aborting.");
-        return Err(ProgramError::MissingRequiredSignature);
+        msg!("Invalid token program! Aborting.");
+        return Err(CoinbackedError::InvalidTokenProgram.into());
+    }
+
+    // multisig cannot hold the freed rent, needs a separate destination
+    let destination_account = if is_multisig(owner_account)
+    {
+        next_account_info(account_info_iter)?
     }
+    else
+    {
+        owner_account
+    };
+
+    // checking if authority signed, remaining accounts are multisig signers
+    let signer_keys = get_token_authority_signers(owner_account, account_info_iter.as_slice())?;
 
-    let token_account_spl = Account::unpack(&mut token_account.data.borrow_mut())?;
+    let token_account_spl = unpack_token_account(token_account)?;
 
-    let backing_account_data = &mut backing_pda.data.borrow_mut()[..];
-    let backing_account = BackingAccount::unpack(backing_account_data)?;
+    let backing_account = unpack_backing_account(backing_pda, program_id)?;
 
     // token account empty? then close...
     if token_account_spl.amount == 0
@@ -536,282 +1150,3568 @@ fn process_clean_accounts_after_burning(
             &close_account(
                 token_program.key,
                 token_account.key,
+                destination_account.key,
                 owner_account.key,
-                owner_account.key,
-                &[owner_account.key],
+                &signer_keys,
             )?,
             accounts
         )?;
     }
 
     // backing account empty? then close, protocol will receive funding...
-    if **backing_pda.lamports.borrow_mut() <= backing_account.rent_excemption
+    let mut freed_lamports = 0;
+    if !backing_account.collateral_mints.is_empty()
+    {
+        msg!("Info: Backing account is collateral backed, will be kept open.");
+    }
+    else if **backing_pda.lamports.borrow_mut() <= backing_account.rent_excemption
     {
         msg!("Backing account now empty, will close it.");
+        freed_lamports = backing_pda.lamports();
 
         **protocol_treasury_account.lamports.borrow_mut() = 
             protocol_treasury_account.lamports()
                 .checked_add(backing_pda.lamports())
-                .ok_or(ProgramError::InvalidInstructionData)?;
+                .ok_or(CoinbackedError::MathError)?;
 
         // clean account
         **backing_pda.try_borrow_mut_lamports()? = 0;
-        backing_account_data.copy_from_slice(&[0; CO_ACCOUNT_BACKING_BYTE_LEN]);
+        backing_pda.try_borrow_mut_data()?.fill(0);
         backing_pda.assign(system_program.key);
         backing_pda.realloc(0, false)?;
     }
 
+    Event
+    {
+        kind: EventKind::CleanAccountsAfterBurning,
+        mint: backing_account.token_key,
+        actor: *owner_account.key,
+        counterparty: *protocol_treasury_account.key,
+        lamports: freed_lamports,
+        token_amount: 0,
+        supply_before: 0,
+        supply_after: 0,
+        per_unit_payout: 0,
+    }.emit();
+
     Ok(())
 }
 
-fn process_admin_create_treasury_account(
+fn process_quote_payout(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    amount: u64
 ) -> ProgramResult
 {
     let account_info_iter = &mut accounts.iter();
-            
-    let owner_account = next_account_info(account_info_iter)?;
-    let protocol_treasury_account = next_account_info(account_info_iter)?;
-    let program_account = next_account_info(account_info_iter)?;
-    let program_executable_data_account =  next_account_info(account_info_iter)?;
-    let system_program = next_account_info(account_info_iter)?;
-    let rent_sysvar = next_account_info(account_info_iter)?;
 
-    // check program account pointing to current program
-    if program_account.key.ne(program_id)
-    {
-        msg!("Program account has incorrect ID. Aborting.");
-        return Err(ProgramError::InvalidAccountData);  
-    }
+    let mint_account = next_account_info(account_info_iter)?;
+    let backing_pda = next_account_info(account_info_iter)?;
 
-    // check program executable data account belongs to program account
-    if program_executable_data_account.key.ne(&get_program_executable_data_account_key(program_account)?)
+    // check backing pda
+    check_backing_account(backing_pda, mint_account, program_id, false)?;
+
+    let backing_account = unpack_backing_account(backing_pda, program_id)?;
+    let mint_account_spl = unpack_mint(mint_account)?;
+
+    if mint_account_spl.supply == 0
     {
-        msg!("Program account and program executable data account don't fit. Aborting.");
-        return Err(ProgramError::InvalidAccountData);  
+        msg!("Mint supply is 0, nothing to quote! Aborting.");
+        return Err(CoinbackedError::MintSupplyZero.into());
     }
 
-    // check that signer is update authority
-    if owner_account.key.ne(&get_update_authority(program_executable_data_account)?)
+    if amount > mint_account_spl.supply
     {
-        msg!("Signer is not update authority for protocol. Aborting.");
-        return Err(ProgramError::InvalidAccountData);  
+        msg!("Amount exceeds mint supply! Aborting.");
+        return Err(CoinbackedError::AmountExceedsSupply.into());
     }
 
-    // check protocol treasury account valid PDA
-    let bump = check_protocol_treasury_account(protocol_treasury_account, program_id)?;
-
-    // create it
-    if protocol_treasury_account.owner.ne(program_id)
+    let quote = PayoutQuote
     {
-        let rent = Rent::get()?;
-        let min_excemption_balance = rent.minimum_balance(CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN).max(1);
-
-        invoke_signed(
-            &create_account(
-                &owner_account.key,
-                &protocol_treasury_account.key,
-                min_excemption_balance,
-                CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN as u64,
-                &program_id
-            ), 
-            &[
-                owner_account.clone(), 
-                protocol_treasury_account.clone(), 
-                system_program.clone(), 
-                rent_sysvar.clone()
-            ], 
-            &[&[
-                program_id.as_ref(),
-                CO_SEED_PROTOCOL_TREASURY,
-                &[bump],
-            ]]
-        )?;
+        payout: get_payout_in_lamport_after_exit_fee(
+            amount,
+            mint_account_spl.supply,
+            backing_pda.lamports(),
+            backing_account.rent_excemption,
+            backing_account.exit_fee_bps
+        )?,
+        per_unit_payout: get_per_unit_payout(
+            mint_account_spl.decimals,
+            mint_account_spl.supply,
+            backing_pda.lamports(),
+            backing_account.rent_excemption
+        )?,
+        supply: mint_account_spl.supply,
+        backing_lamports: backing_pda.lamports().saturating_sub(backing_account.rent_excemption),
+        // collateral backed accounts quote each asset, vaults follow in basket order
+        collateral: get_collateral_quotes(
+            backing_pda,
+            &backing_account,
+            account_info_iter,
+            amount,
+            mint_account_spl.decimals,
+            mint_account_spl.supply
+        )?,
+    };
 
-        let treasure = TreasuryAccount 
-        {
-            rent_excemption: min_excemption_balance,
-            bump: bump
-        };
+    msg!("Quoted payout for {} tokens is: {}", amount, quote.payout);
 
-        treasure.pack(&mut protocol_treasury_account.try_borrow_mut_data()?);
-    }
-    else
+    for collateral_quote in quote.collateral.iter()
     {
-        msg!("Info: Protocol treasury account seems to exist already. No action needed.");
+        msg!("Quoted collateral payout for {} tokens is: {} of {}", amount, collateral_quote.payout, collateral_quote.mint);
     }
 
+    let mut data = [0u8; CO_RETURN_PAYOUT_QUOTE_BYTE_LEN + CO_RETURN_BASKET_BYTE_LEN];
+    quote.pack(&mut data);
+    set_return_data(&data);
+
     Ok(())
 }
 
-fn process_admin_transfer_from_treasury_account(
+fn process_create_collateral_backing_account(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    lamports: u64
+    amount: u64,
+    signed_tos: String,
 ) -> ProgramResult
-{      
+{
     let account_info_iter = &mut accounts.iter();
-            
-    let owner_account = next_account_info(account_info_iter)?;
-    let receiving_account = next_account_info(account_info_iter)?;
+
+    let source_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let backing_pda = next_account_info(account_info_iter)?;
     let protocol_treasury_account = next_account_info(account_info_iter)?;
-    let program_account = next_account_info(account_info_iter)?;
-    let program_executable_data_account =  next_account_info(account_info_iter)?;
+    let collateral_mint_account = next_account_info(account_info_iter)?;
+    let collateral_source_account = next_account_info(account_info_iter)?;
+    let collateral_vault_account = next_account_info(account_info_iter)?;
 
-    // check program account pointing to current program
-    if program_account.key.ne(program_id)
+    let collateral_token_program = next_account_info(account_info_iter)?;
+    let associated_token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let rent_sysvar = next_account_info(account_info_iter)?;
+
+    // config trails the accounts
+    let (_, config_account, _) = split_config_account(program_id, account_info_iter.as_slice());
+
+    // checking if payer account is the signer
+    if !source_account.is_signer
     {
-        msg!("Program account has incorrect ID. Aborting.");
-        return Err(ProgramError::InvalidAccountData);  
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
     }
 
-    // check program executable data account belongs to program account
-    if program_executable_data_account.key.ne(&get_program_executable_data_account_key(program_account)?)
+    // checking if accounts are writable
+    if !backing_pda.is_writable
+        || !protocol_treasury_account.is_writable
+        || !collateral_source_account.is_writable
+        || !collateral_vault_account.is_writable
     {
-        msg!("Program account and program executable data account don't fit. Aborting.");
-        return Err(ProgramError::InvalidAccountData);  
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
     }
 
-    // check that signer is update authority
-    if owner_account.key.ne(&get_update_authority(program_executable_data_account)?)
+    // system & rent correct?
+    if system_program.key.ne(&system_program::ID) || rent_sysvar.key.ne(&rent::ID)
     {
-        msg!("Signer is not update authority for protocol. Aborting.");
-        return Err(ProgramError::InvalidAccountData);  
+        msg!("Invalid system programs! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
     }
 
-    // check protocol treasury account valid PDA
-    check_protocol_treasury_account(protocol_treasury_account, program_id)?;            
+    // collateral token program correct and owning collateral mint?
+    if !is_token_program(collateral_token_program.key)
+        || collateral_mint_account.owner.ne(collateral_token_program.key)
+        || associated_token_program.key.ne(&spl_associated_token_account::ID)
+    {
+        msg!("Invalid token program! Aborting.");
+        return Err(CoinbackedError::InvalidTokenProgram.into());
+    }
 
-    // get stored rent excemption fee
-    let treasure = TreasuryAccount::unpack(&mut protocol_treasury_account.data.borrow_mut())?;
+    // unpack SPL-related accounts
+    let token_account_spl = unpack_token_account(token_account)?;
+    let mint_account_spl = unpack_mint(mint_account)?;
+    let collateral_mint_account_spl = unpack_mint(collateral_mint_account)?;
 
-    // make sure transfer does not deplete below rent excemption
-    if **protocol_treasury_account.lamports.borrow_mut() - lamports < treasure.rent_excemption
+    // refuse mints whose extensions break the payout guarantee, for token and collateral alike
+    if has_unsupported_mint_extension(mint_account)? || has_unsupported_mint_extension(collateral_mint_account)?
     {
-        msg!("Maximum transfer amount: {}", **protocol_treasury_account.lamports.borrow_mut()-treasure.rent_excemption);
-        msg!("Treasury does not have enough funds, transfer would cut into rent excemption. Aborting.");
-        return Err(ProgramError::InsufficientFunds);  
+        msg!("Mint has extension incompatible with backing! Aborting.");
+        return Err(CoinbackedError::UnsupportedMintExtension.into());
     }
-    else
+
+    // make sure that token account belongs to mint
+    if token_account_spl.mint.ne(mint_account.key)
     {
-        // transfer lamports from treasury to target
-        **protocol_treasury_account.try_borrow_mut_lamports()? = 
-            protocol_treasury_account.lamports().checked_sub(lamports)
-            .ok_or(ProgramError::InsufficientFunds)?;
+        msg!("Token account does not belong to mint! Aborting.");
+        return Err(CoinbackedError::TokenAccountMintMismatch.into());
+    }
 
-        **receiving_account.try_borrow_mut_lamports()? = 
-            receiving_account.lamports().checked_add(lamports)
-            .ok_or(ProgramError::InsufficientFunds)?;
-    }   
+    // make sure that source account is owner of token account
+    if token_account_spl.owner.ne(source_account.key)
+    {
+        msg!("Token account does not belong to signer! Aborting.");
+        return Err(CoinbackedError::TokenAccountOwnerMismatch.into());
+    }
 
-    Ok(())
-}
+    // make sure that token account has token amount > 0, only holders can back tokens
+    if token_account_spl.amount == 0
+    {
+        msg!("Token account does not store any token amount, but required for backing! Aborting.");
+        return Err(CoinbackedError::TokenAccountEmpty.into());
+    }
 
-// helper functions
+    // check backing pda and its vault
+    let bump = check_backing_account(backing_pda, mint_account, program_id, true)?;
+    check_collateral_vault_account(collateral_vault_account, backing_pda, collateral_mint_account.key, collateral_token_program.key)?;
 
-fn get_program_executable_data_account_key(
-    program_account: &AccountInfo
-)-> Result<Pubkey, ProgramError>
-{
-    let data = program_account.try_borrow_data()?;
-    Ok(Pubkey::new(array_ref![data, 4, 32]))
-}
+    // protocol config
+    let config = unpack_config_account_or_default(config_account, program_id)?;
+    check_not_paused(&config, CO_PAUSE_CREATE)?;
 
-fn get_update_authority(
-    program_executable_data_account: &AccountInfo
-)-> Result<Pubkey, ProgramError>
-{
-    let data = program_executable_data_account.try_borrow_data()?;
-    Ok(Pubkey::new( array_ref![data, 13, 32]))    
+    // log ToS signature
+    msg!("All pre-checks passed. User signed terms of service: {}", signed_tos);
+
+    // create backing account, holds rent excemption only, value sits in the vault
+    let slot = Clock::get()?.slot;
+    let mut actual_account_data = BackingAccount
+    {
+            version: CO_ACCOUNT_BACKING_VERSION,
+            token_key: *mint_account.key,
+            rent_excemption: 0,
+            bump,
+            stats: Some(
+                BackingStats
+                {
+                    creation_slot: slot,
+                    last_activity_slot: slot,
+                    ..BackingStats::default()
+                }
+            ),
+            exit_fee_bps: 0,
+            creator: None,
+            collateral_mints: vec![*collateral_mint_account.key],
+    };
+
+    let rent = Rent::get()?;
+    let min_excemption_balance = rent.minimum_balance(actual_account_data.byte_len()).max(1);
+    actual_account_data.rent_excemption = min_excemption_balance;
+
+    invoke_signed(
+        &create_account(
+            source_account.key,
+            backing_pda.key,
+            min_excemption_balance,
+            actual_account_data.byte_len() as u64,
+            program_id
+        ),
+        &[
+            source_account.clone(),
+            backing_pda.clone(),
+            system_program.clone(),
+            rent_sysvar.clone()
+        ],
+        &[&[
+            mint_account.key.as_ref(),
+            program_id.as_ref(),
+            CO_SEED_COINBACKED,
+            &[bump],
+        ]]
+    )?;
+
+    actual_account_data.pack(&mut backing_pda.try_borrow_mut_data()?);
+
+    // create vault owned by backing account, tolerating one created upfront, and back with collateral
+    invoke(
+        &create_associated_token_account_idempotent(
+            source_account.key,
+            backing_pda.key,
+            collateral_mint_account.key,
+            collateral_token_program.key
+        ),
+        accounts
+    )?;
+
+    invoke(
+        &transfer_checked(
+            collateral_token_program.key,
+            collateral_source_account.key,
+            collateral_mint_account.key,
+            collateral_vault_account.key,
+            source_account.key,
+            &[],
+            amount,
+            collateral_mint_account_spl.decimals
+        )?,
+        accounts
+    )?;
+
+    // pay protocol
+    pay_protocol(source_account, protocol_treasury_account, program_id, accounts, config.create_fee, true)?;
+
+    Event
+    {
+        kind: EventKind::CreateCollateralBackingAccount,
+        mint: *mint_account.key,
+        actor: *source_account.key,
+        counterparty: *collateral_vault_account.key,
+        lamports: amount,
+        token_amount: 0,
+        supply_before: mint_account_spl.supply,
+        supply_after: mint_account_spl.supply,
+        per_unit_payout: get_per_unit_payout(
+            mint_account_spl.decimals,
+            mint_account_spl.supply,
+            unpack_token_account(collateral_vault_account)?.amount,
+            0
+        )?,
+    }.emit();
+
+    Ok(())
 }
 
-fn check_backing_account(
-    backing_pda: &AccountInfo,
-    mint_account: &AccountInfo,
+fn process_add_collateral_to_balance_of_backing_account(
     program_id: &Pubkey,
-    should_be_empty: bool,
-) -> Result<u8, ProgramError>
+    accounts: &[AccountInfo],
+    amount: u64,
+    signed_tos: String
+) -> ProgramResult
 {
-     // check backing PDA
-     let seeds = &[
-        mint_account.key.as_ref(),
-        program_id.as_ref(),
-        CO_SEED_COINBACKED
-    ];
+    let account_info_iter = &mut accounts.iter();
+
+    let source_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let backing_pda = next_account_info(account_info_iter)?;
+    let protocol_treasury_account = next_account_info(account_info_iter)?;
+    let collateral_mint_account = next_account_info(account_info_iter)?;
+    let collateral_source_account = next_account_info(account_info_iter)?;
+    let collateral_vault_account = next_account_info(account_info_iter)?;
 
-    let (backing_pda_key, bump) = Pubkey::find_program_address(seeds, &program_id);
+    let collateral_token_program = next_account_info(account_info_iter)?;
 
-    // check that backing PDA is derived from mint account, etc.
-    if backing_pda_key.ne(backing_pda.key)
+    // config trails the accounts
+    let (_, config_account, _) = split_config_account(program_id, account_info_iter.as_slice());
+
+    // checking if payer account is the signer
+    if !source_account.is_signer
     {
-        msg!("Account key missmatch - PDA for backing account is not matching. Aborting.");
-        return Err(ProgramError::InvalidAccountData);  
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
     }
 
-    if should_be_empty
+    // checking if accounts are writable
+    if !backing_pda.is_writable
+        || !protocol_treasury_account.is_writable
+        || !collateral_source_account.is_writable
+        || !collateral_vault_account.is_writable
     {
-        // check backing PDA does not exist/is empty
-        if backing_pda.owner.eq(program_id) || !backing_pda.data_is_empty()
-        {
-            msg!("Backing account seems to exist already. Aborting.");
-            return Err(ProgramError::InvalidAccountData);  
-        }
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // collateral token program correct and owning collateral mint?
+    if !is_token_program(collateral_token_program.key) || collateral_mint_account.owner.ne(collateral_token_program.key)
+    {
+        msg!("Invalid token program! Aborting.");
+        return Err(CoinbackedError::InvalidTokenProgram.into());
+    }
+
+    // check backing pda, its collateral and vault
+    check_backing_account(backing_pda, mint_account, program_id, false)?;
+    check_backing_collateral(backing_pda, program_id, Some(collateral_mint_account.key))?;
+    check_collateral_vault_account(collateral_vault_account, backing_pda, collateral_mint_account.key, collateral_token_program.key)?;
+
+    // check mint, only increase balance if there is still tokens to guarantee payout
+    let mint_account_spl = unpack_mint(mint_account)?;
+    if mint_account_spl.supply == 0
+    {
+        msg!("Mint supply is 0, cannot add balance! Aborting.");
+        return Err(CoinbackedError::MintSupplyZero.into());
+    }
+
+    // do not add to backing of mints whose extensions break the payout guarantee
+    if has_unsupported_mint_extension(mint_account)? || has_unsupported_mint_extension(collateral_mint_account)?
+    {
+        msg!("Mint has extension incompatible with backing! Aborting.");
+        return Err(CoinbackedError::UnsupportedMintExtension.into());
+    }
+
+    let collateral_mint_account_spl = unpack_mint(collateral_mint_account)?;
+
+    // protocol config
+    let config = unpack_config_account_or_default(config_account, program_id)?;
+    check_not_paused(&config, CO_PAUSE_ADD)?;
+
+    // log ToS signature
+    msg!("All pre-checks passed. User signed terms of service: {}", signed_tos);
+
+    // transfer collateral to vault
+    invoke(
+        &transfer_checked(
+            collateral_token_program.key,
+            collateral_source_account.key,
+            collateral_mint_account.key,
+            collateral_vault_account.key,
+            source_account.key,
+            &[],
+            amount,
+            collateral_mint_account_spl.decimals
+        )?,
+        accounts
+    )?;
+
+    // pay protocol, fee rate applies to the collateral amount
+    let protocol_fee = get_protocol_fee(amount, config.add_fee, config.add_fee_bps, config.add_fee_max)?;
+    pay_protocol(source_account, protocol_treasury_account, program_id, accounts, protocol_fee, true)?;
+
+    record_backing_activity(backing_pda, program_id, 0, 0, None)?;
+
+    Event
+    {
+        kind: EventKind::AddCollateralToBalanceOfBackingAccount,
+        mint: *mint_account.key,
+        actor: *source_account.key,
+        counterparty: *collateral_vault_account.key,
+        lamports: amount,
+        token_amount: 0,
+        supply_before: mint_account_spl.supply,
+        supply_after: mint_account_spl.supply,
+        per_unit_payout: get_per_unit_payout(
+            mint_account_spl.decimals,
+            mint_account_spl.supply,
+            unpack_token_account(collateral_vault_account)?.amount,
+            0
+        )?,
+    }.emit();
+
+    Ok(())
+}
+
+fn process_burn_token_and_free_collateral
+(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    min_amount_out: u64,
+    signed_tos: String
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let owner_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let backing_pda = next_account_info(account_info_iter)?;
+    let protocol_treasury_account = next_account_info(account_info_iter)?;
+
+    let token_program = next_account_info(account_info_iter)?;
+
+    let collateral_mint_account = next_account_info(account_info_iter)?;
+    let collateral_vault_account = next_account_info(account_info_iter)?;
+    let collateral_destination_account = next_account_info(account_info_iter)?;
+
+    let collateral_token_program = next_account_info(account_info_iter)?;
+
+    // remaining accounts are multisig signers, config trails them
+    let (trailing_accounts, config_account, _) = split_config_account(program_id, account_info_iter.as_slice());
+
+    // token programs correct and owning mints?
+    if !is_token_program(token_program.key)
+        || mint_account.owner.ne(token_program.key)
+        || !is_token_program(collateral_token_program.key)
+        || collateral_mint_account.owner.ne(collateral_token_program.key)
+    {
+        msg!("Invalid token program! Aborting.");
+        return Err(CoinbackedError::InvalidTokenProgram.into());
+    }
+
+    // unpack SPL-related accounts
+    let token_account_spl = unpack_token_account(token_account)?;
+    let mint_account_spl = unpack_mint(mint_account)?;
+    let collateral_mint_account_spl = unpack_mint(collateral_mint_account)?;
+    let collateral_destination_account_spl = unpack_token_account(collateral_destination_account)?;
+
+    // make sure that token account belongs to mint
+    if token_account_spl.mint.ne(mint_account.key)
+    {
+        msg!("Token account does not belong to mint! Aborting.");
+        return Err(CoinbackedError::TokenAccountMintMismatch.into());
+    }
+
+    // make sure that source account is owner of token account or approved delegate
+    let is_delegate = token_account_spl.owner.ne(owner_account.key);
+    if is_delegate
+    {
+        if token_account_spl.delegate.ne(&Some(*owner_account.key).into())
+        {
+            msg!("Token account does not belong to signer! Aborting.");
+            return Err(CoinbackedError::TokenAccountOwnerMismatch.into());
+        }
+
+        if token_account_spl.delegated_amount < amount
+        {
+            msg!("Delegated amount {} too small! Aborting.", token_account_spl.delegated_amount);
+            return Err(CoinbackedError::InsufficientDelegatedAmount.into());
+        }
+    }
+
+    // destination has to hold collateral, payout of delegated burn has to go to token owner
+    if collateral_destination_account_spl.mint.ne(collateral_mint_account.key)
+    {
+        msg!("Destination account does not hold collateral! Aborting.");
+        return Err(CoinbackedError::TokenAccountMintMismatch.into());
+    }
+
+    if is_delegate && collateral_destination_account_spl.owner.ne(&token_account_spl.owner)
+    {
+        msg!("Payout of delegated burn has to go to token owner! Aborting.");
+        return Err(CoinbackedError::InvalidRecipientAccount.into());
+    }
+
+    // checking if authority signed, signer or first multisig signer pays the protocol fee
+    let signer_keys = get_token_authority_signers(owner_account, trailing_accounts)?;
+    let fee_payer_account = get_fee_payer_account(owner_account, trailing_accounts, &signer_keys)?;
+
+    // checking if accounts are writable
+    if !fee_payer_account.is_writable
+        || !backing_pda.is_writable
+        || !protocol_treasury_account.is_writable
+        || !token_account.is_writable
+        || !collateral_vault_account.is_writable
+        || !collateral_destination_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // check backing pda, its collateral and vault
+    let bump = check_backing_account(backing_pda, mint_account, program_id, false)?;
+    check_backing_collateral(backing_pda, program_id, Some(collateral_mint_account.key))?;
+    check_collateral_vault_account(collateral_vault_account, backing_pda, collateral_mint_account.key, collateral_token_program.key)?;
+
+    // single asset only, baskets holding more collateral or lamports have to be burned as a whole, lamports sent to the account unasked are ignored
+    let backing_account = unpack_backing_account(backing_pda, program_id)?;
+    if backing_account.collateral_mints.len() != 1 || has_lamport_backing(&backing_account)
+    {
+        msg!("Backing account is a basket, burn has to free whole basket! Aborting.");
+        return Err(CoinbackedError::BackingCollateralMismatch.into());
+    }
+
+    // protocol config
+    let config = unpack_config_account_or_default(config_account, program_id)?;
+    check_not_paused(&config, CO_PAUSE_BURN)?;
+
+    // log ToS signature
+    msg!("All pre-checks passed. User signed terms of service: {}", signed_tos);
+
+    // calculate collateral to be transfered from vault
+    let collateral_vault_amount = unpack_token_account(collateral_vault_account)?.amount;
+
+    let total_payout = get_payout_in_lamport_after_exit_fee(
+        amount,
+        mint_account_spl.supply,
+        collateral_vault_amount,
+        0,
+        backing_account.exit_fee_bps
+    )?;
+
+    msg!("Calculated collateral payout for burning {} tokens is: {}", amount, total_payout);
+
+    // slippage protection
+    if total_payout < min_amount_out
+    {
+        msg!("Payout {} is below minimum {}! Aborting.", total_payout, min_amount_out);
+        return Err(CoinbackedError::PayoutBelowMinimum.into());
+    }
+
+    invoke(
+        &burn(
+            token_program.key,
+            token_account.key,
+            mint_account.key,
+            owner_account.key,
+            &signer_keys,
+            amount
+        )?,accounts
+    )?;
+
+    // transfer collateral from vault to destination, signed by backing account
+    invoke_signed(
+        &transfer_checked(
+            collateral_token_program.key,
+            collateral_vault_account.key,
+            collateral_mint_account.key,
+            collateral_destination_account.key,
+            backing_pda.key,
+            &[],
+            total_payout,
+            collateral_mint_account_spl.decimals
+        )?,
+        accounts,
+        &[&[
+            mint_account.key.as_ref(),
+            program_id.as_ref(),
+            CO_SEED_COINBACKED,
+            &[bump],
+        ]]
+    )?;
+
+    // pay protocol, fee rate applies to the collateral payout
+    let protocol_fee = get_protocol_fee(total_payout, config.burn_fee, config.burn_fee_bps, config.burn_fee_max)?;
+    pay_protocol(fee_payer_account, protocol_treasury_account, program_id, accounts, protocol_fee, true)?;
+
+    record_backing_activity(backing_pda, program_id, 0, 0, Some(amount))?;
+
+    let supply_after = mint_account_spl.supply.checked_sub(amount)
+        .ok_or(CoinbackedError::MathError)?;
+
+    Event
+    {
+        kind: EventKind::BurnTokenAndFreeCollateral,
+        mint: *mint_account.key,
+        actor: *owner_account.key,
+        counterparty: *collateral_destination_account.key,
+        lamports: total_payout,
+        token_amount: amount,
+        supply_before: mint_account_spl.supply,
+        supply_after,
+        per_unit_payout: get_per_unit_payout(
+            mint_account_spl.decimals,
+            supply_after,
+            unpack_token_account(collateral_vault_account)?.amount,
+            0
+        )?,
+    }.emit();
+
+    Ok(())
+}
+
+fn process_create_basket_backing_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64,
+    amounts: [u64; CO_BASKET_MAX_COLLATERAL],
+    signed_tos: String,
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let source_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let backing_pda = next_account_info(account_info_iter)?;
+    let protocol_treasury_account = next_account_info(account_info_iter)?;
+
+    let associated_token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let rent_sysvar = next_account_info(account_info_iter)?;
+
+    // config trails the basket accounts
+    let (basket_accounts, config_account, _) = split_config_account(program_id, account_info_iter.as_slice());
+
+    // remaining accounts: collateral mint, source, vault and token program per asset
+    let basket_accounts = get_basket_accounts(basket_accounts)?;
+
+    // checking if payer account is the signer
+    if !source_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !backing_pda.is_writable || !protocol_treasury_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system & rent correct?
+    if system_program.key.ne(&system_program::ID) || rent_sysvar.key.ne(&rent::ID)
+    {
+        msg!("Invalid system programs! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    if associated_token_program.key.ne(&spl_associated_token_account::ID)
+    {
+        msg!("Invalid token program! Aborting.");
+        return Err(CoinbackedError::InvalidTokenProgram.into());
+    }
+
+    // amounts for assets not in the basket have to be zero
+    if amounts[basket_accounts.len()..].iter().any(|amount| *amount != 0)
+    {
+        msg!("Collateral amount given for asset not in basket! Aborting.");
+        return Err(CoinbackedError::InvalidBasketAccounts.into());
+    }
+
+    // unpack SPL-related accounts
+    let token_account_spl = unpack_token_account(token_account)?;
+    let mint_account_spl = unpack_mint(mint_account)?;
+
+    // refuse mints whose extensions break the payout guarantee
+    if has_unsupported_mint_extension(mint_account)?
+    {
+        msg!("Mint has extension incompatible with backing! Aborting.");
+        return Err(CoinbackedError::UnsupportedMintExtension.into());
+    }
+
+    // make sure that token account belongs to mint
+    if token_account_spl.mint.ne(mint_account.key)
+    {
+        msg!("Token account does not belong to mint! Aborting.");
+        return Err(CoinbackedError::TokenAccountMintMismatch.into());
+    }
+
+    // make sure that source account is owner of token account
+    if token_account_spl.owner.ne(source_account.key)
+    {
+        msg!("Token account does not belong to signer! Aborting.");
+        return Err(CoinbackedError::TokenAccountOwnerMismatch.into());
+    }
+
+    // make sure that token account has token amount > 0, only holders can back tokens
+    if token_account_spl.amount == 0
+    {
+        msg!("Token account does not store any token amount, but required for backing! Aborting.");
+        return Err(CoinbackedError::TokenAccountEmpty.into());
+    }
+
+    // check backing pda
+    let bump = check_backing_account(backing_pda, mint_account, program_id, true)?;
+
+    // check basket assets and their vaults
+    let mut collateral_mints = Vec::with_capacity(basket_accounts.len());
+
+    for [collateral_mint_account, collateral_source_account, collateral_vault_account, collateral_token_program] in basket_accounts.iter()
+    {
+        if !collateral_source_account.is_writable || !collateral_vault_account.is_writable
+        {
+            msg!("Required accounts not writable! Aborting.");
+            return Err(CoinbackedError::AccountNotWritable.into());
+        }
+
+        if !is_token_program(collateral_token_program.key) || collateral_mint_account.owner.ne(collateral_token_program.key)
+        {
+            msg!("Invalid token program! Aborting.");
+            return Err(CoinbackedError::InvalidTokenProgram.into());
+        }
+
+        if has_unsupported_mint_extension(collateral_mint_account)?
+        {
+            msg!("Collateral mint has extension incompatible with backing! Aborting.");
+            return Err(CoinbackedError::UnsupportedMintExtension.into());
+        }
+
+        if collateral_mints.contains(collateral_mint_account.key)
+        {
+            msg!("Collateral asset listed twice in basket! Aborting.");
+            return Err(CoinbackedError::InvalidBasketAccounts.into());
+        }
+
+        check_collateral_vault_account(collateral_vault_account, backing_pda, collateral_mint_account.key, collateral_token_program.key)?;
+        collateral_mints.push(*collateral_mint_account.key);
+    }
+
+    // protocol config
+    let config = unpack_config_account_or_default(config_account, program_id)?;
+    check_not_paused(&config, CO_PAUSE_CREATE)?;
+
+    // log ToS signature
+    msg!("All pre-checks passed. User signed terms of service: {}", signed_tos);
+
+    // create backing account and back with sol, collateral sits in the vaults
+    let slot = Clock::get()?.slot;
+    let mut actual_account_data = BackingAccount
+    {
+            version: CO_ACCOUNT_BACKING_VERSION,
+            token_key: *mint_account.key,
+            rent_excemption: 0,
+            bump,
+            stats: Some(
+                BackingStats
+                {
+                    total_deposited: lamports,
+                    creation_slot: slot,
+                    last_activity_slot: slot,
+                    ..BackingStats::default()
+                }
+            ),
+            exit_fee_bps: 0,
+            creator: None,
+            collateral_mints,
+    };
+
+    let rent = Rent::get()?;
+    let min_excemption_balance = rent.minimum_balance(actual_account_data.byte_len()).max(1);
+    actual_account_data.rent_excemption = min_excemption_balance;
+
+    invoke_signed(
+        &create_account(
+            source_account.key,
+            backing_pda.key,
+            min_excemption_balance + lamports,
+            actual_account_data.byte_len() as u64,
+            program_id
+        ),
+        &[
+            source_account.clone(),
+            backing_pda.clone(),
+            system_program.clone(),
+            rent_sysvar.clone()
+        ],
+        &[&[
+            mint_account.key.as_ref(),
+            program_id.as_ref(),
+            CO_SEED_COINBACKED,
+            &[bump],
+        ]]
+    )?;
+
+    actual_account_data.pack(&mut backing_pda.try_borrow_mut_data()?);
+
+    // create vaults owned by backing account, tolerating ones created upfront, and back with collateral
+    for ([collateral_mint_account, collateral_source_account, collateral_vault_account, collateral_token_program], amount) in basket_accounts.iter().zip(amounts.iter())
+    {
+        invoke(
+            &create_associated_token_account_idempotent(
+                source_account.key,
+                backing_pda.key,
+                collateral_mint_account.key,
+                collateral_token_program.key
+            ),
+            accounts
+        )?;
+
+        invoke(
+            &transfer_checked(
+                collateral_token_program.key,
+                collateral_source_account.key,
+                collateral_mint_account.key,
+                collateral_vault_account.key,
+                source_account.key,
+                &[],
+                *amount,
+                unpack_mint(collateral_mint_account)?.decimals
+            )?,
+            accounts
+        )?;
+    }
+
+    // pay protocol
+    pay_protocol(source_account, protocol_treasury_account, program_id, accounts, config.create_fee, true)?;
+
+    Event
+    {
+        kind: EventKind::CreateBasketBackingAccount,
+        mint: *mint_account.key,
+        actor: *source_account.key,
+        counterparty: *backing_pda.key,
+        lamports,
+        token_amount: 0,
+        supply_before: mint_account_spl.supply,
+        supply_after: mint_account_spl.supply,
+        per_unit_payout: get_per_unit_payout(
+            mint_account_spl.decimals,
+            mint_account_spl.supply,
+            backing_pda.lamports(),
+            min_excemption_balance
+        )?,
+    }.emit();
+
+    for ([_, _, collateral_vault_account, _], amount) in basket_accounts.iter().zip(amounts.iter())
+    {
+        Event
+        {
+            kind: EventKind::AddCollateralToBalanceOfBackingAccount,
+            mint: *mint_account.key,
+            actor: *source_account.key,
+            counterparty: *collateral_vault_account.key,
+            lamports: *amount,
+            token_amount: 0,
+            supply_before: mint_account_spl.supply,
+            supply_after: mint_account_spl.supply,
+            per_unit_payout: get_per_unit_payout(
+                mint_account_spl.decimals,
+                mint_account_spl.supply,
+                unpack_token_account(collateral_vault_account)?.amount,
+                0
+            )?,
+        }.emit();
+    }
+
+    Ok(())
+}
+
+fn process_burn_token_and_free_basket
+(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    min_lamports_out: u64,
+    min_amounts_out: [u64; CO_BASKET_MAX_COLLATERAL],
+    signed_tos: String
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let owner_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let backing_pda = next_account_info(account_info_iter)?;
+    let protocol_treasury_account = next_account_info(account_info_iter)?;
+
+    let token_program = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // config trails the basket accounts and multisig signers
+    let (remaining_accounts, config_account, _) = split_config_account(program_id, account_info_iter.as_slice());
+
+    // token program correct and owning mint?
+    if !is_token_program(token_program.key) || mint_account.owner.ne(token_program.key)
+    {
+        msg!("Invalid token program! Aborting.");
+        return Err(CoinbackedError::InvalidTokenProgram.into());
+    }
+
+    if system_program.key.ne(&system_program::ID)
+    {
+        msg!("Invalid system programs! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    // unpack SPL-related accounts
+    let token_account_spl = unpack_token_account(token_account)?;
+    let mint_account_spl = unpack_mint(mint_account)?;
+
+    // make sure that token account belongs to mint
+    if token_account_spl.mint.ne(mint_account.key)
+    {
+        msg!("Token account does not belong to mint! Aborting.");
+        return Err(CoinbackedError::TokenAccountMintMismatch.into());
+    }
+
+    // make sure that source account is owner of token account or approved delegate
+    if token_account_spl.owner.ne(owner_account.key)
+    {
+        if token_account_spl.delegate.ne(&Some(*owner_account.key).into())
+        {
+            msg!("Token account does not belong to signer! Aborting.");
+            return Err(CoinbackedError::TokenAccountOwnerMismatch.into());
+        }
+
+        if token_account_spl.delegated_amount < amount
+        {
+            msg!("Delegated amount {} too small! Aborting.", token_account_spl.delegated_amount);
+            return Err(CoinbackedError::InsufficientDelegatedAmount.into());
+        }
+    }
+
+    // check backing pda
+    let bump = check_backing_account(backing_pda, mint_account, program_id, false)?;
+    let backing_account = unpack_backing_account(backing_pda, program_id)?;
+
+    // remaining accounts: collateral mint, vault, destination and token program per asset, multisig signers follow them
+    let (basket_accounts, signer_accounts) = remaining_accounts.split_at(remaining_accounts.len().min(4 * backing_account.collateral_mints.len()));
+    let basket_accounts = get_basket_accounts(basket_accounts)?;
+
+    // checking if authority signed, signer or first multisig signer pays the protocol fee
+    let signer_keys = get_token_authority_signers(owner_account, signer_accounts)?;
+    let fee_payer_account = get_fee_payer_account(owner_account, signer_accounts, &signer_keys)?;
+
+    // whole basket goes to token owner, lamports of a multisig owned basket go to the paying multisig signer
+    let lamport_recipient_key = if is_multisig(owner_account) { fee_payer_account.key } else { &token_account_spl.owner };
+    if recipient_account.key.ne(lamport_recipient_key) || is_multisig(recipient_account)
+    {
+        msg!("Payout of basket has to go to token owner! Aborting.");
+        return Err(CoinbackedError::InvalidRecipientAccount.into());
+    }
+
+    // checking if accounts are writable
+    if !fee_payer_account.is_writable
+        || !backing_pda.is_writable
+        || !protocol_treasury_account.is_writable
+        || !token_account.is_writable
+        || !recipient_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // every basket asset has to be passed, in basket order
+    if backing_account.collateral_mints.is_empty() || basket_accounts.len() != backing_account.collateral_mints.len()
+    {
+        msg!("Basket accounts do not match backing account collateral! Aborting.");
+        return Err(CoinbackedError::InvalidBasketAccounts.into());
+    }
+
+    // protocol config
+    let config = unpack_config_account_or_default(config_account, program_id)?;
+    check_not_paused(&config, CO_PAUSE_BURN)?;
+
+    // log ToS signature
+    msg!("All pre-checks passed. User signed terms of service: {}", signed_tos);
+
+    // calculate lamports to be transfered from backing
+    let total_payout = get_payout_in_lamport_after_exit_fee(
+        amount,
+        mint_account_spl.supply,
+        backing_pda.lamports(),
+        backing_account.rent_excemption,
+        backing_account.exit_fee_bps
+    )?;
+
+    msg!("Calculated payout for burning {} tokens is: {}", amount, total_payout);
+
+    if total_payout < min_lamports_out
+    {
+        msg!("Payout {} is below minimum {}! Aborting.", total_payout, min_lamports_out);
+        return Err(CoinbackedError::PayoutBelowMinimum.into());
+    }
+
+    // calculate collateral to be transfered from vaults
+    let mut collateral_payouts = Vec::with_capacity(basket_accounts.len());
+
+    for (([collateral_mint_account, collateral_vault_account, collateral_destination_account, collateral_token_program], collateral_mint), min_amount_out) in basket_accounts.iter()
+        .zip(backing_account.collateral_mints.iter())
+        .zip(min_amounts_out.iter())
+    {
+        if collateral_mint_account.key.ne(collateral_mint)
+        {
+            msg!("Basket accounts do not match backing account collateral! Aborting.");
+            return Err(CoinbackedError::InvalidBasketAccounts.into());
+        }
+
+        if !is_token_program(collateral_token_program.key) || collateral_mint_account.owner.ne(collateral_token_program.key)
+        {
+            msg!("Invalid token program! Aborting.");
+            return Err(CoinbackedError::InvalidTokenProgram.into());
+        }
+
+        if !collateral_vault_account.is_writable || !collateral_destination_account.is_writable
+        {
+            msg!("Required accounts not writable! Aborting.");
+            return Err(CoinbackedError::AccountNotWritable.into());
+        }
+
+        check_collateral_vault_account(collateral_vault_account, backing_pda, collateral_mint, collateral_token_program.key)?;
+
+        // destination has to hold collateral and belong to token owner
+        let collateral_destination_account_spl = unpack_token_account(collateral_destination_account)?;
+        if collateral_destination_account_spl.mint.ne(collateral_mint) || collateral_destination_account_spl.owner.ne(&token_account_spl.owner)
+        {
+            msg!("Destination account does not hold collateral of token owner! Aborting.");
+            return Err(CoinbackedError::InvalidRecipientAccount.into());
+        }
+
+        let collateral_payout = get_payout_in_lamport_after_exit_fee(
+            amount,
+            mint_account_spl.supply,
+            unpack_token_account(collateral_vault_account)?.amount,
+            0,
+            backing_account.exit_fee_bps
+        )?;
+
+        msg!("Calculated collateral payout for burning {} tokens is: {} of {}", amount, collateral_payout, collateral_mint);
+
+        if collateral_payout < *min_amount_out
+        {
+            msg!("Payout {} is below minimum {}! Aborting.", collateral_payout, min_amount_out);
+            return Err(CoinbackedError::PayoutBelowMinimum.into());
+        }
+
+        collateral_payouts.push(collateral_payout);
+    }
+
+    invoke(
+        &burn(
+            token_program.key,
+            token_account.key,
+            mint_account.key,
+            owner_account.key,
+            &signer_keys,
+            amount
+        )?,accounts
+    )?;
+
+    // transfer collateral from vaults to destinations, signed by backing account
+    for ([collateral_mint_account, collateral_vault_account, collateral_destination_account, collateral_token_program], collateral_payout) in basket_accounts.iter().zip(collateral_payouts.iter())
+    {
+        invoke_signed(
+            &transfer_checked(
+                collateral_token_program.key,
+                collateral_vault_account.key,
+                collateral_mint_account.key,
+                collateral_destination_account.key,
+                backing_pda.key,
+                &[],
+                *collateral_payout,
+                unpack_mint(collateral_mint_account)?.decimals
+            )?,
+            accounts,
+            &[&[
+                mint_account.key.as_ref(),
+                program_id.as_ref(),
+                CO_SEED_COINBACKED,
+                &[bump],
+            ]]
+        )?;
+    }
+
+    // pay protocol, fee rate applies to lamport share
+    let protocol_fee = get_protocol_fee(total_payout, config.burn_fee, config.burn_fee_bps, config.burn_fee_max)?;
+    pay_protocol(fee_payer_account, protocol_treasury_account, program_id, accounts, protocol_fee, true)?;
+
+    // transfer lamports from backing to recipient
+    **backing_pda.try_borrow_mut_lamports()? =
+        backing_pda.lamports().checked_sub(total_payout)
+        .ok_or(CoinbackedError::InsufficientBackingFunds)?;
+
+    **recipient_account.try_borrow_mut_lamports()? =
+        recipient_account.lamports().checked_add(total_payout)
+        .ok_or(CoinbackedError::MathError)?;
+
+    record_backing_activity(backing_pda, program_id, 0, total_payout, Some(amount))?;
+
+    let supply_after = mint_account_spl.supply.checked_sub(amount)
+        .ok_or(CoinbackedError::MathError)?;
+
+    Event
+    {
+        kind: EventKind::BurnTokenAndFreeBasket,
+        mint: *mint_account.key,
+        actor: *owner_account.key,
+        counterparty: *recipient_account.key,
+        lamports: total_payout,
+        token_amount: amount,
+        supply_before: mint_account_spl.supply,
+        supply_after,
+        per_unit_payout: get_per_unit_payout(
+            mint_account_spl.decimals,
+            supply_after,
+            backing_pda.lamports(),
+            backing_account.rent_excemption
+        )?,
+    }.emit();
+
+    for ([_, collateral_vault_account, collateral_destination_account, _], collateral_payout) in basket_accounts.iter().zip(collateral_payouts.iter())
+    {
+        Event
+        {
+            kind: EventKind::FreeBasketCollateral,
+            mint: *mint_account.key,
+            actor: *owner_account.key,
+            counterparty: *collateral_destination_account.key,
+            lamports: *collateral_payout,
+            token_amount: amount,
+            supply_before: mint_account_spl.supply,
+            supply_after,
+            per_unit_payout: get_per_unit_payout(
+                mint_account_spl.decimals,
+                supply_after,
+                unpack_token_account(collateral_vault_account)?.amount,
+                0
+            )?,
+        }.emit();
+    }
+
+    Ok(())
+}
+
+fn process_migrate_backing_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let payer_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let backing_pda = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // checking if payer account is the signer
+    if !payer_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !payer_account.is_writable || !backing_pda.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system correct?
+    if system_program.key.ne(&system_program::ID)
+    {
+        msg!("Invalid system program! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    // check backing pda
+    check_backing_account(backing_pda, mint_account, program_id, false)?;
+
+    let backing_account = unpack_backing_account(backing_pda, program_id)?;
+
+    if backing_account.version >= CO_ACCOUNT_BACKING_VERSION
+    {
+        msg!("Info: Backing account has current layout version {}. No action needed.", backing_account.version);
+        return Ok(());
+    }
+
+    msg!("Migrating backing account from layout version {} to {}.", backing_account.version, CO_ACCOUNT_BACKING_VERSION);
+
+    // version 0 accounts start without statistics, totals before migration are unknown
+    let mut migrated_account_data = BackingAccount
+    {
+        version: CO_ACCOUNT_BACKING_VERSION,
+        stats: Some(backing_account.stats.unwrap_or_default()),
+        ..backing_account
+    };
+
+    // payer funds the rent of the grown layout, backing balance above rent excemption stays untouched
+    migrated_account_data.rent_excemption = realloc_account_layout(
+        payer_account,
+        backing_pda,
+        backing_account.rent_excemption,
+        migrated_account_data.byte_len(),
+        accounts
+    )?;
+
+    migrated_account_data.pack(&mut backing_pda.try_borrow_mut_data()?);
+
+    let mint_account_spl = unpack_mint(mint_account)?;
+
+    Event
+    {
+        kind: EventKind::MigrateBackingAccount,
+        mint: *mint_account.key,
+        actor: *payer_account.key,
+        counterparty: *backing_pda.key,
+        lamports: migrated_account_data.rent_excemption - backing_account.rent_excemption,
+        token_amount: 0,
+        supply_before: mint_account_spl.supply,
+        supply_after: mint_account_spl.supply,
+        per_unit_payout: get_per_unit_payout(
+            mint_account_spl.decimals,
+            mint_account_spl.supply,
+            backing_pda.lamports(),
+            migrated_account_data.rent_excemption
+        )?,
+    }.emit();
+
+    Ok(())
+}
+
+fn process_admin_create_treasury_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+            
+    let owner_account = next_account_info(account_info_iter)?;
+    let protocol_treasury_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let rent_sysvar = next_account_info(account_info_iter)?;
+
+    // checking if owner account is the signer
+    if !owner_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // check that signer is config admin, independent of the update authority
+    let config = unpack_config_account(config_account, program_id)?;
+    if owner_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    // check protocol treasury account valid PDA
+    let bump = check_protocol_treasury_account(protocol_treasury_account, program_id)?;
+
+    // create it
+    if protocol_treasury_account.owner.ne(program_id)
+    {
+        let mut treasure = TreasuryAccount
+        {
+            version: CO_ACCOUNT_PROTOCOL_TREASURY_VERSION,
+            rent_excemption: 0,
+            bump,
+            queued_lamports: 0,
+            queued_recipient: Pubkey::default(),
+            queued_slot: 0,
+            epoch: 0,
+            epoch_withdrawn: 0,
+        };
+
+        let rent = Rent::get()?;
+        let min_excemption_balance = rent.minimum_balance(treasure.byte_len()).max(1);
+        treasure.rent_excemption = min_excemption_balance;
+
+        invoke_signed(
+            &create_account(
+                owner_account.key,
+                protocol_treasury_account.key,
+                min_excemption_balance,
+                treasure.byte_len() as u64,
+                program_id
+            ), 
+            &[
+                owner_account.clone(), 
+                protocol_treasury_account.clone(), 
+                system_program.clone(), 
+                rent_sysvar.clone()
+            ], 
+            &[&[
+                program_id.as_ref(),
+                CO_SEED_PROTOCOL_TREASURY,
+                &[bump],
+            ]]
+        )?;
+
+        treasure.pack(&mut protocol_treasury_account.try_borrow_mut_data()?);
+    }
+    else
+    {
+        let treasure = unpack_treasury_account(protocol_treasury_account, program_id)?;
+
+        if treasure.version < CO_ACCOUNT_PROTOCOL_TREASURY_VERSION
+        {
+            // migrate in place, admin funds the rent of the grown layout
+            msg!("Info: Protocol treasury account has layout version {}, will migrate it.", treasure.version);
+            let mut migrated_treasure = TreasuryAccount
+            {
+                version: CO_ACCOUNT_PROTOCOL_TREASURY_VERSION,
+                ..treasure
+            };
+
+            migrated_treasure.rent_excemption = realloc_account_layout(
+                owner_account,
+                protocol_treasury_account,
+                treasure.rent_excemption,
+                migrated_treasure.byte_len(),
+                accounts
+            )?;
+
+            migrated_treasure.pack(&mut protocol_treasury_account.try_borrow_mut_data()?);
+        }
+        else
+        {
+            msg!("Info: Protocol treasury account seems to exist already. No action needed.");
+        }
+    }
+
+    Ok(())
+}
+
+fn process_admin_transfer_from_treasury_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64
+) -> ProgramResult
+{      
+    let account_info_iter = &mut accounts.iter();
+            
+    let owner_account = next_account_info(account_info_iter)?;
+    let receiving_account = next_account_info(account_info_iter)?;
+    let protocol_treasury_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+
+    // checking if owner account is the signer
+    if !owner_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // check that signer is config admin, independent of the update authority
+    let config = unpack_config_account(config_account, program_id)?;
+    if owner_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    // once treasury signers are set, withdrawals have to be approved by them
+    if config.treasury_threshold > 0
+    {
+        msg!("Treasury signers are set, withdrawal has to be proposed and approved. Aborting.");
+        return Err(CoinbackedError::TreasuryMultisigRequired.into());
+    }
+
+    // check protocol treasury account valid PDA
+    check_protocol_treasury_account(protocol_treasury_account, program_id)?;            
+
+    // get stored rent excemption fee
+    let mut treasure = unpack_treasury_account(protocol_treasury_account, program_id)?;
+
+    // with a delay set, transfer has to be queued and delay passed
+    if config.treasury_delay_slots > 0
+    {
+        if lamports == 0 || treasure.queued_lamports != lamports || treasure.queued_recipient.ne(receiving_account.key)
+        {
+            msg!("No transfer of {} lamports to {} queued. Aborting.", lamports, receiving_account.key);
+            return Err(CoinbackedError::TreasuryTransferNotQueued.into());
+        }
+
+        let executable_slot = treasure.queued_slot.saturating_add(config.treasury_delay_slots);
+        if Clock::get()?.slot < executable_slot
+        {
+            msg!("Queued transfer is executable from slot {}. Aborting.", executable_slot);
+            return Err(CoinbackedError::TreasuryWithdrawalDelayed.into());
+        }
+
+        treasure.queued_lamports = 0;
+        treasure.queued_recipient = Pubkey::default();
+        treasure.queued_slot = 0;
+    }
+
+    // make sure transfer does not deplete below rent excemption
+    if protocol_treasury_account.lamports().saturating_sub(lamports) < treasure.rent_excemption
+    {
+        msg!("Maximum transfer amount: {}", protocol_treasury_account.lamports().saturating_sub(treasure.rent_excemption));
+        msg!("Treasury does not have enough funds, transfer would cut into rent excemption. Aborting.");
+        return Err(CoinbackedError::InsufficientTreasuryFunds.into());
+    }
+
+    let epoch_withdrawn = record_treasury_withdrawal(&mut treasure, &config, lamports)?;
+
+    // transfer lamports from treasury to target
+    **protocol_treasury_account.try_borrow_mut_lamports()? = 
+        protocol_treasury_account.lamports().checked_sub(lamports)
+        .ok_or(CoinbackedError::InsufficientTreasuryFunds)?;
+
+    **receiving_account.try_borrow_mut_lamports()? = 
+        receiving_account.lamports().checked_add(lamports)
+        .ok_or(CoinbackedError::MathError)?;
+
+    treasure.pack(&mut protocol_treasury_account.try_borrow_mut_data()?);
+
+    TreasuryEvent
+    {
+        kind: EventKind::AdminTransferFromTreasuryAccount,
+        actor: *owner_account.key,
+        counterparty: *receiving_account.key,
+        lamports,
+        epoch_withdrawn,
+        epoch_cap: config.treasury_epoch_cap,
+        delay_slots: config.treasury_delay_slots,
+    }.emit();
+
+    Ok(())
+}
+
+fn process_admin_init_config_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    create_fee: u64,
+    validate_fee: u64,
+    add_fee: u64,
+    burn_fee: u64,
+    min_backing_lamports: u64
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let owner_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let program_account = next_account_info(account_info_iter)?;
+    let program_executable_data_account =  next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let rent_sysvar = next_account_info(account_info_iter)?;
+
+    // checking if owner account is the signer
+    if !owner_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // check program account pointing to current program
+    if program_account.key.ne(program_id)
+    {
+        msg!("Program account has incorrect ID. Aborting.");
+        return Err(CoinbackedError::InvalidProgramAccount.into());
+    }
+
+    // check program executable data account belongs to program account
+    if program_executable_data_account.key.ne(&get_program_executable_data_account_key(program_account)?)
+    {
+        msg!("Program account and program executable data account don't fit. Aborting.");
+        return Err(CoinbackedError::InvalidProgramExecutableDataAccount.into());
+    }
+
+    // check that signer is update authority
+    if owner_account.key.ne(&get_update_authority(program_executable_data_account)?)
+    {
+        msg!("Signer is not update authority for protocol. Aborting.");
+        return Err(CoinbackedError::NotUpdateAuthority.into());
+    }
+
+    check_burn_fee_caps(burn_fee, 0, 0)?;
+
+    // check config account valid PDA and not yet initialised
+    let bump = check_config_account(config_account, program_id)?;
+    if config_account.owner.eq(program_id)
+    {
+        msg!("Config account seems to exist already. Aborting.");
+        return Err(CoinbackedError::ConfigAccountAlreadyExists.into());
+    }
+
+    // update authority becomes initial admin
+    let config = ConfigAccount
+    {
+        version: CO_ACCOUNT_CONFIG_VERSION,
+        admin: *owner_account.key,
+        create_fee,
+        validate_fee,
+        add_fee,
+        burn_fee,
+        min_backing_lamports,
+        bump,
+        burn_fee_bps: 0,
+        burn_fee_max: 0,
+        add_fee_bps: 0,
+        add_fee_max: 0,
+        pending_admin: None,
+        treasury_threshold: 0,
+        treasury_signers: Vec::new(),
+        treasury_delay_slots: 0,
+        treasury_epoch_cap: 0,
+        pause_flags: 0,
+        burn_pause_max_slots: 0,
+        burn_paused_slot: 0,
+        creator_fee_share_bps: 0,
+        referrer_fee_share_bps: 0,
+        pending_treasury_delay_slots: 0,
+        pending_treasury_epoch_cap: 0,
+        pending_treasury_limits_slot: 0,
+    };
+
+    let rent = Rent::get()?;
+
+    invoke_signed(
+        &create_account(
+            owner_account.key,
+            config_account.key,
+            rent.minimum_balance(config.byte_len()).max(1),
+            config.byte_len() as u64,
+            program_id
+        ),
+        &[
+            owner_account.clone(),
+            config_account.clone(),
+            system_program.clone(),
+            rent_sysvar.clone()
+        ],
+        &[&[
+            program_id.as_ref(),
+            CO_SEED_CONFIG,
+            &[bump],
+        ]]
+    )?;
+
+    config.pack(&mut config_account.try_borrow_mut_data()?);
+
+    msg!("Config initialised. Fees create: {}, validate: {}, add: {}, burn: {}. Minimum backing: {}", create_fee, validate_fee, add_fee, burn_fee, min_backing_lamports);
+
+    Ok(())
+}
+
+fn process_admin_update_config_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    create_fee: u64,
+    validate_fee: u64,
+    add_fee: u64,
+    burn_fee: u64,
+    min_backing_lamports: u64
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+
+    // checking if admin account is the signer
+    if !admin_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if config account is writable
+    if !config_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    let mut config = unpack_config_account(config_account, program_id)?;
+
+    // check that signer is config admin
+    if admin_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    check_burn_fee_caps(burn_fee, config.burn_fee_bps, config.burn_fee_max)?;
+
+    config.create_fee = create_fee;
+    config.validate_fee = validate_fee;
+    config.add_fee = add_fee;
+    config.burn_fee = burn_fee;
+    config.min_backing_lamports = min_backing_lamports;
+
+    config.pack(&mut config_account.try_borrow_mut_data()?);
+
+    msg!("Config updated. Fees create: {}, validate: {}, add: {}, burn: {}. Minimum backing: {}", create_fee, validate_fee, add_fee, burn_fee, min_backing_lamports);
+
+    Ok(())
+}
+
+fn process_admin_update_config_fee_rates(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    burn_fee_bps: u16,
+    burn_fee_max: u64,
+    add_fee_bps: u16,
+    add_fee_max: u64
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // checking if admin account is the signer
+    if !admin_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !admin_account.is_writable || !config_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system correct?
+    if system_program.key.ne(&system_program::ID)
+    {
+        msg!("Invalid system program! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    let mut config = unpack_config_account(config_account, program_id)?;
+
+    // check that signer is config admin
+    if admin_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    if burn_fee_bps as u64 > CO_BPS_DENOMINATOR || add_fee_bps as u64 > CO_BPS_DENOMINATOR
+    {
+        msg!("Fee rate exceeds {} basis points. Aborting.", CO_BPS_DENOMINATOR);
+        return Err(CoinbackedError::InvalidFeeRate.into());
+    }
+    check_burn_fee_caps(config.burn_fee, burn_fee_bps, burn_fee_max)?;
+
+    // older layouts have no room for fee rates, admin funds the rent of the grown layout
+    if config.version < CO_ACCOUNT_CONFIG_VERSION
+    {
+        msg!("Info: Config account has layout version {}, will migrate it.", config.version);
+        config.version = CO_ACCOUNT_CONFIG_VERSION;
+
+        realloc_account_layout(
+            admin_account,
+            config_account,
+            config_account.lamports(),
+            config.byte_len(),
+            accounts
+        )?;
+    }
+
+    config.burn_fee_bps = burn_fee_bps;
+    config.burn_fee_max = burn_fee_max;
+    config.add_fee_bps = add_fee_bps;
+    config.add_fee_max = add_fee_max;
+
+    config.pack(&mut config_account.try_borrow_mut_data()?);
+
+    msg!("Config fee rates updated. Burn: {} bps, max {}. Add: {} bps, max {}", burn_fee_bps, burn_fee_max, add_fee_bps, add_fee_max);
+
+    Ok(())
+}
+
+fn process_admin_propose_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_admin: Pubkey
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // checking if admin account is the signer
+    if !admin_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !admin_account.is_writable || !config_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system correct?
+    if system_program.key.ne(&system_program::ID)
+    {
+        msg!("Invalid system program! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    let mut config = unpack_config_account(config_account, program_id)?;
+
+    // check that signer is config admin
+    if admin_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    // older layouts have no room for a pending admin, admin funds the rent of the grown layout
+    if config.version < CO_ACCOUNT_CONFIG_VERSION
+    {
+        msg!("Info: Config account has layout version {}, will migrate it.", config.version);
+        config.version = CO_ACCOUNT_CONFIG_VERSION;
+
+        realloc_account_layout(
+            admin_account,
+            config_account,
+            config_account.lamports(),
+            config.byte_len(),
+            accounts
+        )?;
+    }
+
+    // proposing the current admin cancels a pending handover
+    if new_admin.eq(&config.admin)
+    {
+        config.pending_admin = None;
+        msg!("Pending admin handover cancelled.");
+    }
+    else
+    {
+        config.pending_admin = Some(new_admin);
+        msg!("Admin {} proposed, handover completes once accepted.", new_admin);
+    }
+
+    config.pack(&mut config_account.try_borrow_mut_data()?);
+
+    Ok(())
+}
+
+fn process_admin_accept_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo]
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let new_admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+
+    // checking if new admin account is the signer
+    if !new_admin_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if config account is writable
+    if !config_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    let mut config = unpack_config_account(config_account, program_id)?;
+
+    // check that signer is the proposed admin
+    if config.pending_admin.ne(&Some(*new_admin_account.key))
+    {
+        msg!("Signer is not pending admin of config. Aborting.");
+        return Err(CoinbackedError::NotPendingAdmin.into());
+    }
+
+    msg!("Admin handed over from {} to {}.", config.admin, new_admin_account.key);
+
+    config.admin = *new_admin_account.key;
+    config.pending_admin = None;
+
+    config.pack(&mut config_account.try_borrow_mut_data()?);
+
+    Ok(())
+}
+
+fn process_admin_make_program_immutable(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo]
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let owner_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let program_account = next_account_info(account_info_iter)?;
+    let program_executable_data_account = next_account_info(account_info_iter)?;
+    let loader_program = next_account_info(account_info_iter)?;
+
+    // checking if owner account is the signer
+    if !owner_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // check program account pointing to current program
+    if program_account.key.ne(program_id)
+    {
+        msg!("Program account has incorrect ID. Aborting.");
+        return Err(CoinbackedError::InvalidProgramAccount.into());
+    }
+
+    // check program executable data account belongs to program account
+    if program_executable_data_account.key.ne(&get_program_executable_data_account_key(program_account)?)
+    {
+        msg!("Program account and program executable data account don't fit. Aborting.");
+        return Err(CoinbackedError::InvalidProgramExecutableDataAccount.into());
+    }
+
+    // check that signer is update authority
+    if owner_account.key.ne(&get_update_authority(program_executable_data_account)?)
+    {
+        msg!("Signer is not update authority for protocol. Aborting.");
+        return Err(CoinbackedError::NotUpdateAuthority.into());
+    }
+
+    // loader correct?
+    if loader_program.key.ne(&bpf_loader_upgradeable::ID)
+    {
+        msg!("Invalid loader program! Aborting.");
+        return Err(CoinbackedError::InvalidLoaderProgram.into());
+    }
+
+    // config has to exist, otherwise nobody could administer treasury afterwards
+    let config = unpack_config_account(config_account, program_id)?;
+
+    invoke(
+        &bpf_loader_upgradeable::set_upgrade_authority(
+            program_id,
+            owner_account.key,
+            None
+        ),
+        &[
+            program_executable_data_account.clone(),
+            owner_account.clone(),
+            loader_program.clone(),
+        ]
+    )?;
+
+    msg!("Program is immutable now. Config and treasury stay administered by {}.", config.admin);
+
+    Ok(())
+}
+
+fn process_admin_set_treasury_signers(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold: u8,
+    signers: Vec<Pubkey>
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // checking if admin account is the signer
+    if !admin_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !admin_account.is_writable || !config_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system correct?
+    if system_program.key.ne(&system_program::ID)
+    {
+        msg!("Invalid system program! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    let mut config = unpack_config_account(config_account, program_id)?;
+
+    // check that signer is config admin
+    if admin_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    // once treasury signers are set, changing them needs threshold of current signers co-signing
+    if config.treasury_threshold > 0
+    {
+        // admin counts as approver if among the signers, further approvers trail the accounts
+        let mut approvals: Vec<&Pubkey> = Vec::new();
+        for approver_account in std::iter::once(admin_account).chain(account_info_iter)
+        {
+            if approver_account.is_signer
+                && config.treasury_signer_index(approver_account.key).is_some()
+                && !approvals.contains(&approver_account.key)
+            {
+                approvals.push(approver_account.key);
+            }
+        }
+
+        if approvals.len() < config.treasury_threshold as usize
+        {
+            msg!("Changing treasury signers has {} of {} approvals. Aborting.", approvals.len(), config.treasury_threshold);
+            return Err(CoinbackedError::TreasuryMultisigRequired.into());
+        }
+    }
+
+    // threshold has to be reachable, no signers for threshold zero, no duplicates
+    let has_duplicates = signers.iter().enumerate().any(|(i, signer)| signers[..i].contains(signer));
+    if signers.len() > CO_TREASURY_MAX_SIGNERS
+        || threshold as usize > signers.len()
+        || (threshold == 0) != signers.is_empty()
+        || has_duplicates
+    {
+        msg!("Threshold {} does not fit {} treasury signers or signers duplicated. Aborting.", threshold, signers.len());
+        return Err(CoinbackedError::InvalidTreasurySigners.into());
+    }
+
+    // older layouts have no room for treasury signers, admin funds the rent of the grown layout
+    if config.version < CO_ACCOUNT_CONFIG_VERSION
+    {
+        msg!("Info: Config account has layout version {}, will migrate it.", config.version);
+        config.version = CO_ACCOUNT_CONFIG_VERSION;
+
+        realloc_account_layout(
+            admin_account,
+            config_account,
+            config_account.lamports(),
+            config.byte_len(),
+            accounts
+        )?;
+    }
+
+    msg!("Treasury signers set, {} of {} approvals required for withdrawals.", threshold, signers.len());
+
+    config.treasury_threshold = threshold;
+    config.treasury_signers = signers;
+
+    config.pack(&mut config_account.try_borrow_mut_data()?);
+
+    Ok(())
+}
+
+fn process_propose_treasury_withdrawal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+    lamports: u64,
+    expiry_slot: u64
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let proposer_account = next_account_info(account_info_iter)?;
+    let proposal_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let rent_sysvar = next_account_info(account_info_iter)?;
+
+    // checking if proposer account is the signer
+    if !proposer_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !proposer_account.is_writable || !proposal_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system & rent correct?
+    if system_program.key.ne(&system_program::ID) || rent_sysvar.key.ne(&rent::ID)
+    {
+        msg!("Invalid system programs! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    // check that signer is treasury signer
+    let config = unpack_config_account(config_account, program_id)?;
+    if config.treasury_signer_index(proposer_account.key).is_none()
+    {
+        msg!("Signer is not treasury signer. Aborting.");
+        return Err(CoinbackedError::NotTreasurySigner.into());
+    }
+
+    // recipient has to be a plain system account
+    if recipient_account.owner.ne(&system_program::ID)
+    {
+        msg!("Recipient account is not owned by system program! Aborting.");
+        return Err(CoinbackedError::InvalidRecipientAccount.into());
+    }
+
+    let slot = Clock::get()?.slot;
+    if expiry_slot <= slot
+    {
+        msg!("Expiry slot {} is not after current slot {}. Aborting.", expiry_slot, slot);
+        return Err(CoinbackedError::WithdrawalProposalExpired.into());
+    }
+
+    // check proposal pda and that it does not exist yet
+    let bump = check_withdrawal_proposal_account(proposal_account, proposal_id, program_id)?;
+    if proposal_account.owner.eq(program_id)
+    {
+        msg!("Withdrawal proposal {} exists already. Aborting.", proposal_id);
+        return Err(CoinbackedError::InvalidWithdrawalProposalData.into());
+    }
+
+    // proposing counts as first approval
+    let proposal = WithdrawalProposal
+    {
+        version: CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION,
+        proposal_id,
+        proposer: *proposer_account.key,
+        recipient: *recipient_account.key,
+        lamports,
+        expiry_slot,
+        approvals: vec![*proposer_account.key],
+        bump,
+        created_slot: slot,
+    };
+
+    let rent = Rent::get()?;
+
+    invoke_signed(
+        &create_account(
+            proposer_account.key,
+            proposal_account.key,
+            rent.minimum_balance(proposal.byte_len()).max(1),
+            proposal.byte_len() as u64,
+            program_id
+        ),
+        &[
+            proposer_account.clone(),
+            proposal_account.clone(),
+            system_program.clone(),
+            rent_sysvar.clone()
+        ],
+        &[&[
+            program_id.as_ref(),
+            CO_SEED_WITHDRAWAL_PROPOSAL,
+            &proposal_id.to_le_bytes(),
+            &[bump],
+        ]]
+    )?;
+
+    proposal.pack(&mut proposal_account.try_borrow_mut_data()?);
+
+    msg!("Withdrawal {} of {} lamports to {} proposed, 1 of {} approvals.", proposal_id, lamports, recipient_account.key, config.treasury_threshold);
+
+    Ok(())
+}
+
+fn process_approve_treasury_withdrawal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo]
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let signer_account = next_account_info(account_info_iter)?;
+    let proposal_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+
+    // checking if signer account is the signer
+    if !signer_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if proposal account is writable
+    if !proposal_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // check that signer is treasury signer
+    let config = unpack_config_account(config_account, program_id)?;
+    if config.treasury_signer_index(signer_account.key).is_none()
+    {
+        msg!("Signer is not treasury signer. Aborting.");
+        return Err(CoinbackedError::NotTreasurySigner.into());
+    }
+
+    let mut proposal = unpack_withdrawal_proposal(proposal_account, program_id)?;
+
+    let slot = Clock::get()?.slot;
+    if proposal.expiry_slot < slot
+    {
+        msg!("Withdrawal proposal expired at slot {}. Aborting.", proposal.expiry_slot);
+        return Err(CoinbackedError::WithdrawalProposalExpired.into());
+    }
+
+    if proposal.approvals.contains(signer_account.key)
+    {
+        msg!("Info: Signer approved withdrawal {} already. No action needed.", proposal.proposal_id);
+        return Ok(());
+    }
+
+    // approvals of removed signers may take up room, only current signers are kept
+    proposal.approvals.retain(|approval| config.treasury_signer_index(approval).is_some());
+    proposal.approvals.push(*signer_account.key);
+
+    proposal.pack(&mut proposal_account.try_borrow_mut_data()?);
+
+    msg!("Withdrawal {} approved, {} of {} approvals.", proposal.proposal_id, proposal.approvals.len(), config.treasury_threshold);
+
+    Ok(())
+}
+
+fn process_execute_treasury_withdrawal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo]
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    let proposer_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let protocol_treasury_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+
+    // checking if accounts are writable
+    if !proposal_account.is_writable
+        || !proposer_account.is_writable
+        || !recipient_account.is_writable
+        || !protocol_treasury_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    let proposal = unpack_withdrawal_proposal(proposal_account, program_id)?;
+
+    // proposer gets rent back, recipient the withdrawal
+    if proposer_account.key.ne(&proposal.proposer) || recipient_account.key.ne(&proposal.recipient)
+    {
+        msg!("Proposer or recipient account does not match proposal. Aborting.");
+        return Err(CoinbackedError::InvalidRecipientAccount.into());
+    }
+
+    let slot = Clock::get()?.slot;
+    if proposal.expiry_slot < slot
+    {
+        msg!("Withdrawal proposal expired at slot {}. Aborting.", proposal.expiry_slot);
+        return Err(CoinbackedError::WithdrawalProposalExpired.into());
+    }
+
+    // only approvals of current signers count, signers might have changed since proposing
+    let config = unpack_config_account(config_account, program_id)?;
+    let approvals = proposal.approvals.iter().filter(|approval| config.treasury_signer_index(approval).is_some()).count();
+    if config.treasury_threshold == 0 || approvals < config.treasury_threshold as usize
+    {
+        msg!("Withdrawal has {} of {} approvals. Aborting.", approvals, config.treasury_threshold);
+        return Err(CoinbackedError::MultisigThresholdNotMet.into());
+    }
+
+    // delay counts from proposing
+    let executable_slot = proposal.created_slot.saturating_add(config.treasury_delay_slots);
+    if slot < executable_slot
+    {
+        msg!("Withdrawal is executable from slot {}. Aborting.", executable_slot);
+        return Err(CoinbackedError::TreasuryWithdrawalDelayed.into());
+    }
+
+    // check protocol treasury account valid PDA
+    check_protocol_treasury_account(protocol_treasury_account, program_id)?;
+
+    // get stored rent excemption fee
+    let mut treasure = unpack_treasury_account(protocol_treasury_account, program_id)?;
+
+    // make sure transfer does not deplete below rent excemption
+    if protocol_treasury_account.lamports().saturating_sub(proposal.lamports) < treasure.rent_excemption
+    {
+        msg!("Maximum transfer amount: {}", protocol_treasury_account.lamports().saturating_sub(treasure.rent_excemption));
+        msg!("Treasury does not have enough funds, transfer would cut into rent excemption. Aborting.");
+        return Err(CoinbackedError::InsufficientTreasuryFunds.into());
+    }
+
+    let epoch_withdrawn = record_treasury_withdrawal(&mut treasure, &config, proposal.lamports)?;
+    treasure.pack(&mut protocol_treasury_account.try_borrow_mut_data()?);
+
+    // transfer lamports from treasury to recipient
+    **protocol_treasury_account.try_borrow_mut_lamports()? =
+        protocol_treasury_account.lamports().checked_sub(proposal.lamports)
+        .ok_or(CoinbackedError::InsufficientTreasuryFunds)?;
+
+    **recipient_account.try_borrow_mut_lamports()? =
+        recipient_account.lamports().checked_add(proposal.lamports)
+        .ok_or(CoinbackedError::MathError)?;
+
+    // close proposal, rent goes back to proposer
+    **proposer_account.try_borrow_mut_lamports()? =
+        proposer_account.lamports().checked_add(proposal_account.lamports())
+        .ok_or(CoinbackedError::MathError)?;
+
+    **proposal_account.try_borrow_mut_lamports()? = 0;
+    proposal_account.try_borrow_mut_data()?.fill(0);
+    proposal_account.assign(&system_program::ID);
+    proposal_account.realloc(0, false)?;
+
+    TreasuryEvent
+    {
+        kind: EventKind::ExecuteTreasuryWithdrawal,
+        actor: proposal.proposer,
+        counterparty: proposal.recipient,
+        lamports: proposal.lamports,
+        epoch_withdrawn,
+        epoch_cap: config.treasury_epoch_cap,
+        delay_slots: config.treasury_delay_slots,
+    }.emit();
+
+    Ok(())
+}
+
+fn process_close_expired_treasury_withdrawal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo]
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let signer_account = next_account_info(account_info_iter)?;
+    let proposal_account = next_account_info(account_info_iter)?;
+    let proposer_account = next_account_info(account_info_iter)?;
+
+    // any signer may close, rent only ever goes to proposer
+    if !signer_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !proposal_account.is_writable || !proposer_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    let proposal = unpack_withdrawal_proposal(proposal_account, program_id)?;
+
+    if proposer_account.key.ne(&proposal.proposer)
+    {
+        msg!("Proposer account does not match proposal. Aborting.");
+        return Err(CoinbackedError::InvalidRecipientAccount.into());
+    }
+
+    // proposal stays executable up to and including its expiry slot
+    let slot = Clock::get()?.slot;
+    if slot <= proposal.expiry_slot
+    {
+        msg!("Withdrawal proposal expires at slot {}. Aborting.", proposal.expiry_slot);
+        return Err(CoinbackedError::WithdrawalProposalNotExpired.into());
+    }
+
+    // close proposal, rent goes back to proposer
+    **proposer_account.try_borrow_mut_lamports()? =
+        proposer_account.lamports().checked_add(proposal_account.lamports())
+        .ok_or(CoinbackedError::MathError)?;
+
+    **proposal_account.try_borrow_mut_lamports()? = 0;
+    proposal_account.try_borrow_mut_data()?.fill(0);
+    proposal_account.assign(&system_program::ID);
+    proposal_account.realloc(0, false)?;
+
+    msg!("Expired withdrawal {} closed.", proposal.proposal_id);
+
+    Ok(())
+}
+
+fn process_admin_update_treasury_limits(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    delay_slots: u64,
+    epoch_cap: u64
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // checking if admin account is the signer
+    if !admin_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !admin_account.is_writable || !config_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system correct?
+    if system_program.key.ne(&system_program::ID)
+    {
+        msg!("Invalid system program! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    let mut config = unpack_config_account(config_account, program_id)?;
+
+    // check that signer is config admin
+    if admin_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    // older layouts have no room for treasury limits, admin funds the rent of the grown layout
+    if config.version < CO_ACCOUNT_CONFIG_VERSION
+    {
+        msg!("Info: Config account has layout version {}, will migrate it.", config.version);
+        config.version = CO_ACCOUNT_CONFIG_VERSION;
+
+        realloc_account_layout(
+            admin_account,
+            config_account,
+            config_account.lamports(),
+            config.byte_len(),
+            accounts
+        )?;
+    }
+
+    // tightening applies immediately, loosening is queued and applied by repeating it once the current delay passed
+    let slot = Clock::get()?.slot;
+    if config.loosens_treasury_limits(delay_slots, epoch_cap)
+    {
+        let is_queued = config.pending_treasury_limits_slot > 0
+            && config.pending_treasury_delay_slots == delay_slots
+            && config.pending_treasury_epoch_cap == epoch_cap;
+        let applicable_slot = config.pending_treasury_limits_slot.saturating_add(config.treasury_delay_slots);
+
+        if !is_queued || slot < applicable_slot
+        {
+            if !is_queued
+            {
+                config.pending_treasury_delay_slots = delay_slots;
+                config.pending_treasury_epoch_cap = epoch_cap;
+                config.pending_treasury_limits_slot = slot;
+                config.pack(&mut config_account.try_borrow_mut_data()?);
+            }
+
+            msg!("Looser treasury limits queued. Delay: {} slots, epoch cap: {}, applicable from slot {}", delay_slots, epoch_cap, config.pending_treasury_limits_slot.saturating_add(config.treasury_delay_slots));
+
+            return Ok(());
+        }
+    }
+
+    config.treasury_delay_slots = delay_slots;
+    config.treasury_epoch_cap = epoch_cap;
+    config.pending_treasury_delay_slots = 0;
+    config.pending_treasury_epoch_cap = 0;
+    config.pending_treasury_limits_slot = 0;
+
+    config.pack(&mut config_account.try_borrow_mut_data()?);
+
+    msg!("Treasury limits updated. Delay: {} slots, epoch cap: {}", delay_slots, epoch_cap);
+
+    Ok(())
+}
+
+fn process_admin_queue_treasury_transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let owner_account = next_account_info(account_info_iter)?;
+    let receiving_account = next_account_info(account_info_iter)?;
+    let protocol_treasury_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // checking if owner account is the signer
+    if !owner_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !owner_account.is_writable || !protocol_treasury_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system correct?
+    if system_program.key.ne(&system_program::ID)
+    {
+        msg!("Invalid system program! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    // check that signer is config admin
+    let config = unpack_config_account(config_account, program_id)?;
+    if owner_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    // once treasury signers are set, withdrawals have to be approved by them
+    if config.treasury_threshold > 0
+    {
+        msg!("Treasury signers are set, withdrawal has to be proposed and approved. Aborting.");
+        return Err(CoinbackedError::TreasuryMultisigRequired.into());
+    }
+
+    // check protocol treasury account valid PDA
+    check_protocol_treasury_account(protocol_treasury_account, program_id)?;
+
+    let treasure = unpack_treasury_account(protocol_treasury_account, program_id)?;
+
+    // older layouts have no room for the queue, admin funds the rent of the grown layout
+    let slot = Clock::get()?.slot;
+    let mut queued_treasure = TreasuryAccount
+    {
+        version: CO_ACCOUNT_PROTOCOL_TREASURY_VERSION,
+        queued_lamports: lamports,
+        queued_recipient: *receiving_account.key,
+        queued_slot: slot,
+        ..treasure
+    };
+
+    if treasure.version < CO_ACCOUNT_PROTOCOL_TREASURY_VERSION
+    {
+        msg!("Info: Protocol treasury account has layout version {}, will migrate it.", treasure.version);
+        queued_treasure.rent_excemption = realloc_account_layout(
+            owner_account,
+            protocol_treasury_account,
+            treasure.rent_excemption,
+            queued_treasure.byte_len(),
+            accounts
+        )?;
+    }
+
+    queued_treasure.pack(&mut protocol_treasury_account.try_borrow_mut_data()?);
+
+    msg!("Transfer of {} lamports to {} queued, executable from slot {}.", lamports, receiving_account.key, slot.saturating_add(config.treasury_delay_slots));
+
+    // withdrawn lamports of a past epoch do not count anymore
+    let epoch_withdrawn = if queued_treasure.epoch == Clock::get()?.epoch { queued_treasure.epoch_withdrawn } else { 0 };
+
+    TreasuryEvent
+    {
+        kind: EventKind::QueueTreasuryTransfer,
+        actor: *owner_account.key,
+        counterparty: *receiving_account.key,
+        lamports,
+        epoch_withdrawn,
+        epoch_cap: config.treasury_epoch_cap,
+        delay_slots: config.treasury_delay_slots,
+    }.emit();
+
+    Ok(())
+}
+
+fn process_admin_set_pause(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    pause_flags: u8,
+    burn_pause_max_slots: u64
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // checking if admin account is the signer
+    if !admin_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !admin_account.is_writable || !config_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system correct?
+    if system_program.key.ne(&system_program::ID)
+    {
+        msg!("Invalid system program! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    let mut config = unpack_config_account(config_account, program_id)?;
+
+    // check that signer is config admin
+    if admin_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    if pause_flags & !CO_PAUSE_ALL != 0 || burn_pause_max_slots > CO_PAUSE_BURN_MAX_SLOTS
+    {
+        msg!("Unknown pause flags or burn pause window exceeds {} slots. Aborting.", CO_PAUSE_BURN_MAX_SLOTS);
+        return Err(CoinbackedError::InvalidPause.into());
+    }
+
+    // older layouts have no room for pause flags, admin funds the rent of the grown layout
+    if config.version < CO_ACCOUNT_CONFIG_VERSION
+    {
+        msg!("Info: Config account has layout version {}, will migrate it.", config.version);
+        config.version = CO_ACCOUNT_CONFIG_VERSION;
+
+        realloc_account_layout(
+            admin_account,
+            config_account,
+            config_account.lamports(),
+            config.byte_len(),
+            accounts
+        )?;
+    }
+
+    let slot = Clock::get()?.slot;
+    let burn_paused = config.is_paused(CO_PAUSE_BURN, slot);
+
+    // window of a running burn pause is fixed, extending it would lock holders longer
+    if burn_paused && burn_pause_max_slots != config.burn_pause_max_slots
+    {
+        msg!("Burn pause window cannot change while burns are paused. Aborting.");
+        return Err(CoinbackedError::InvalidPause.into());
+    }
+
+    // cooldown is derived from the window, shrinking it before the cooldown ended would allow an early pause
+    let earliest_slot = config.burn_pause_cooldown_end();
+    if slot < earliest_slot && burn_pause_max_slots < config.burn_pause_max_slots
+    {
+        msg!("Burn pause window cannot shrink before slot {}. Aborting.", earliest_slot);
+        return Err(CoinbackedError::InvalidPause.into());
+    }
+
+    // setting the burn flag starts a window, burns stay open for at least a window in between; a lapsed flag kept set does not restart it
+    if pause_flags & CO_PAUSE_BURN != 0 && config.pause_flags & CO_PAUSE_BURN == 0
+    {
+        if slot < earliest_slot
+        {
+            msg!("Burns can be paused again from slot {}. Aborting.", earliest_slot);
+            return Err(CoinbackedError::InvalidPause.into());
+        }
+
+        config.burn_paused_slot = slot;
+    }
+
+    config.pause_flags = pause_flags;
+    config.burn_pause_max_slots = burn_pause_max_slots;
+
+    config.pack(&mut config_account.try_borrow_mut_data()?);
+
+    msg!("Pause flags set to {}. Burn pause window: {} slots", pause_flags, burn_pause_max_slots);
+
+    Ok(())
+}
+
+fn process_admin_update_creator_fee_share(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    creator_fee_share_bps: u16
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // checking if admin account is the signer
+    if !admin_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !admin_account.is_writable || !config_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system correct?
+    if system_program.key.ne(&system_program::ID)
+    {
+        msg!("Invalid system program! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    let mut config = unpack_config_account(config_account, program_id)?;
+
+    // check that signer is config admin
+    if admin_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    // creator and referrer shares are both taken from the same protocol fee
+    if creator_fee_share_bps as u64 + config.referrer_fee_share_bps as u64 > CO_BPS_DENOMINATOR
+    {
+        msg!("Creator and referrer fee shares exceed {} basis points. Aborting.", CO_BPS_DENOMINATOR);
+        return Err(CoinbackedError::InvalidFeeRate.into());
+    }
+
+    // older layouts have no room for creator fee share, admin funds the rent of the grown layout
+    if config.version < CO_ACCOUNT_CONFIG_VERSION
+    {
+        msg!("Info: Config account has layout version {}, will migrate it.", config.version);
+        config.version = CO_ACCOUNT_CONFIG_VERSION;
+
+        realloc_account_layout(
+            admin_account,
+            config_account,
+            config_account.lamports(),
+            config.byte_len(),
+            accounts
+        )?;
+    }
+
+    config.creator_fee_share_bps = creator_fee_share_bps;
+
+    config.pack(&mut config_account.try_borrow_mut_data()?);
+
+    msg!("Creator fee share set to {} bps", creator_fee_share_bps);
+
+    Ok(())
+}
+
+fn process_admin_update_referrer_fee_share(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    referrer_fee_share_bps: u16
+) -> ProgramResult
+{
+    let account_info_iter = &mut accounts.iter();
+
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // checking if admin account is the signer
+    if !admin_account.is_signer
+    {
+        msg!("Account is not signer! Aborting.");
+        return Err(CoinbackedError::AccountNotSigner.into());
+    }
+
+    // checking if accounts are writable
+    if !admin_account.is_writable || !config_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    // system correct?
+    if system_program.key.ne(&system_program::ID)
+    {
+        msg!("Invalid system program! Aborting.");
+        return Err(CoinbackedError::InvalidSystemProgram.into());
+    }
+
+    let mut config = unpack_config_account(config_account, program_id)?;
+
+    // check that signer is config admin
+    if admin_account.key.ne(&config.admin)
+    {
+        msg!("Signer is not admin of config. Aborting.");
+        return Err(CoinbackedError::NotAdmin.into());
+    }
+
+    // creator and referrer shares are both taken from the same protocol fee
+    if referrer_fee_share_bps as u64 + config.creator_fee_share_bps as u64 > CO_BPS_DENOMINATOR
+    {
+        msg!("Creator and referrer fee shares exceed {} basis points. Aborting.", CO_BPS_DENOMINATOR);
+        return Err(CoinbackedError::InvalidFeeRate.into());
+    }
+
+    // older layouts have no room for referrer fee share, admin funds the rent of the grown layout
+    if config.version < CO_ACCOUNT_CONFIG_VERSION
+    {
+        msg!("Info: Config account has layout version {}, will migrate it.", config.version);
+        config.version = CO_ACCOUNT_CONFIG_VERSION;
+
+        realloc_account_layout(
+            admin_account,
+            config_account,
+            config_account.lamports(),
+            config.byte_len(),
+            accounts
+        )?;
+    }
+
+    config.referrer_fee_share_bps = referrer_fee_share_bps;
+
+    config.pack(&mut config_account.try_borrow_mut_data()?);
+
+    msg!("Referrer fee share set to {} bps", referrer_fee_share_bps);
+
+    Ok(())
+}
+
+// helper functions
+
+fn get_program_executable_data_account_key(
+    program_account: &AccountInfo
+)-> Result<Pubkey, ProgramError>
+{
+    let data = program_account.try_borrow_data()?;
+    Ok(Pubkey::new_from_array(*array_ref![data, 4, 32]))
+}
+
+fn get_update_authority(
+    program_executable_data_account: &AccountInfo
+)-> Result<Pubkey, ProgramError>
+{
+    let data = program_executable_data_account.try_borrow_data()?;
+    Ok(Pubkey::new_from_array(*array_ref![data, 13, 32]))    
+}
+
+fn check_backing_account(
+    backing_pda: &AccountInfo,
+    mint_account: &AccountInfo,
+    program_id: &Pubkey,
+    should_be_empty: bool,
+) -> Result<u8, ProgramError>
+{
+     // check backing PDA
+     let seeds = &[
+        mint_account.key.as_ref(),
+        program_id.as_ref(),
+        CO_SEED_COINBACKED
+    ];
+
+    let (backing_pda_key, bump) = Pubkey::find_program_address(seeds, program_id);
+
+    // check that backing PDA is derived from mint account, etc.
+    if backing_pda_key.ne(backing_pda.key)
+    {
+        msg!("Account key missmatch - PDA for backing account is not matching. Aborting.");
+        return Err(CoinbackedError::InvalidBackingAccountAddress.into());
+    }
+
+    if should_be_empty
+    {
+        // check backing PDA does not exist/is empty
+        if backing_pda.owner.eq(program_id) || !backing_pda.data_is_empty()
+        {
+            msg!("Backing account seems to exist already. Aborting.");
+            return Err(CoinbackedError::BackingAccountAlreadyExists.into());
+        }
+    }
+    else
+    {
+        // check content correct 
+        let backing_account = unpack_backing_account(backing_pda, program_id)?;
+    
+        // backing account belongs to mint
+        if backing_account.token_key.ne(mint_account.key)
+        {
+            msg!("Backing account not pointing to mint account. Aborting.");
+            return Err(CoinbackedError::BackingAccountMintMismatch.into());
+        }
+    
+        // seed bump check
+        if backing_account.bump != bump
+        {
+            msg!("Backing account bump is incorrect. Aborting.");
+            return Err(CoinbackedError::InvalidBackingAccountBump.into());
+        }
+    }
+
+    Ok(bump)
+}
+
+fn get_basket_accounts<'a, 'b>(
+    accounts: &'a [AccountInfo<'b>]
+) -> Result<Vec<&'a [AccountInfo<'b>; 4]>, ProgramError>
+{
+    // four accounts per basket asset
+    if accounts.is_empty() || !accounts.len().is_multiple_of(4) || accounts.len() / 4 > CO_BASKET_MAX_COLLATERAL
+    {
+        msg!("Basket needs 1 to {} collateral assets, 4 accounts each! Aborting.", CO_BASKET_MAX_COLLATERAL);
+        return Err(CoinbackedError::InvalidBasketAccounts.into());
+    }
+
+    Ok(accounts.chunks_exact(4).map(|asset_accounts| array_ref![asset_accounts, 0, 4]).collect())
+}
+
+fn check_backing_collateral(
+    backing_pda: &AccountInfo,
+    program_id: &Pubkey,
+    collateral_mint: Option<&Pubkey>
+) -> ProgramResult
+{
+    let backing_account = unpack_backing_account(backing_pda, program_id)?;
+
+    // lamport backed accounts have no collateral mint, collateral backed ones have to include it
+    let matching = match collateral_mint
+    {
+        Some(collateral_mint) => backing_account.collateral_mints.contains(collateral_mint),
+        None => backing_account.collateral_mints.is_empty(),
+    };
+
+    if !matching
+    {
+        msg!("Backing account collateral does not match! Aborting.");
+        return Err(CoinbackedError::BackingCollateralMismatch.into());
+    }
+
+    Ok(())
+}
+
+fn get_collateral_quotes<'a, 'b: 'a>(
+    backing_pda: &AccountInfo,
+    backing_account: &BackingAccount,
+    collateral_vault_accounts: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+    amount: u64,
+    decimals: u8,
+    supply: u64
+) -> Result<Vec<CollateralQuote>, ProgramError>
+{
+    let mut collateral = Vec::with_capacity(backing_account.collateral_mints.len());
+
+    for collateral_mint in backing_account.collateral_mints.iter()
+    {
+        let collateral_vault_account = next_account_info(collateral_vault_accounts)?;
+
+        // vault owner is checked to be a token program by unpacking
+        let backing_amount = unpack_token_account(collateral_vault_account)?.amount;
+        check_collateral_vault_account(collateral_vault_account, backing_pda, collateral_mint, collateral_vault_account.owner)?;
+
+        let per_unit_payout = get_per_unit_payout(decimals, supply, backing_amount, 0)?;
+        let payout = if supply == 0
+        {
+            0
+        }
+        else
+        {
+            get_payout_in_lamport_after_exit_fee(amount, supply, backing_amount, 0, backing_account.exit_fee_bps)?
+        };
+
+        collateral.push(
+            CollateralQuote
+            {
+                mint: *collateral_mint,
+                payout,
+                per_unit_payout,
+                backing_amount,
+            }
+        );
+    }
+
+    Ok(collateral)
+}
+
+fn check_collateral_vault_account(
+    collateral_vault_account: &AccountInfo,
+    backing_pda: &AccountInfo,
+    collateral_mint: &Pubkey,
+    collateral_token_program_id: &Pubkey
+) -> ProgramResult
+{
+    // vault is the associated token account of the backing account
+    let collateral_vault_key = get_associated_token_address_with_program_id(
+        backing_pda.key,
+        collateral_mint,
+        collateral_token_program_id
+    );
+
+    if collateral_vault_key.ne(collateral_vault_account.key)
+    {
+        msg!("Account key missmatch - collateral vault is not associated token account of backing account. Aborting.");
+        return Err(CoinbackedError::InvalidCollateralVaultAccount.into());
+    }
+
+    Ok(())
+}
+
+fn record_backing_activity(
+    backing_pda: &AccountInfo,
+    program_id: &Pubkey,
+    deposited: u64,
+    paid_out: u64,
+    burned: Option<u64>
+) -> ProgramResult
+{
+    let mut backing_account = unpack_backing_account(backing_pda, program_id)?;
+
+    // version 0 accounts have no room for statistics
+    let stats = match backing_account.stats.as_mut()
+    {
+        Some(stats) => stats,
+        None =>
+        {
+            msg!("Info: Backing account without statistics, not tracked. Migrate account to track.");
+            return Ok(());
+        }
+    };
+
+    stats.total_deposited = stats.total_deposited.checked_add(deposited)
+        .ok_or(CoinbackedError::MathError)?;
+    stats.total_paid_out = stats.total_paid_out.checked_add(paid_out)
+        .ok_or(CoinbackedError::MathError)?;
+
+    if let Some(burned) = burned
+    {
+        stats.total_burned = stats.total_burned.checked_add(burned)
+            .ok_or(CoinbackedError::MathError)?;
+        stats.burn_count = stats.burn_count.checked_add(1)
+            .ok_or(CoinbackedError::MathError)?;
+    }
+
+    stats.last_activity_slot = Clock::get()?.slot;
+
+    backing_account.pack(&mut backing_pda.try_borrow_mut_data()?);
+
+    Ok(())
+}
+
+fn record_backer_contribution(
+    program_id: &Pubkey,
+    contributor_account: &AccountInfo,
+    mint_account: &AccountInfo,
+    backer_record_account: &AccountInfo,
+    lamports: u64,
+    accounts: &[AccountInfo]
+) -> ProgramResult
+{
+    let seeds = &[
+        mint_account.key.as_ref(),
+        contributor_account.key.as_ref(),
+        program_id.as_ref(),
+        CO_SEED_BACKER_RECORD
+    ];
+
+    let (backer_record_key, bump) = Pubkey::find_program_address(seeds, program_id);
+
+    // check pda
+    if backer_record_key.ne(backer_record_account.key)
+    {
+        msg!("Account key missmatch - PDA for backer record is not matching. Aborting.");
+        return Err(CoinbackedError::InvalidBackerRecordAddress.into());
+    }
+
+    if !backer_record_account.is_writable
+    {
+        msg!("Required accounts not writable! Aborting.");
+        return Err(CoinbackedError::AccountNotWritable.into());
+    }
+
+    let slot = Clock::get()?.slot;
+
+    let backer_record = if backer_record_account.owner.ne(program_id)
+    {
+        // first contribution, create record paid by contributor
+        let backer_record = BackerRecord
+        {
+            version: CO_ACCOUNT_BACKER_RECORD_VERSION,
+            token_key: *mint_account.key,
+            contributor: *contributor_account.key,
+            total_lamports: lamports,
+            contribution_count: 1,
+            first_slot: slot,
+            last_slot: slot,
+            bump
+        };
+
+        let rent = Rent::get()?;
+        let min_excemption_balance = rent.minimum_balance(backer_record.byte_len()).max(1);
+        let signer_seeds: &[&[u8]] = &[
+            mint_account.key.as_ref(),
+            contributor_account.key.as_ref(),
+            program_id.as_ref(),
+            CO_SEED_BACKER_RECORD,
+            &[bump],
+        ];
+
+        if backer_record_account.lamports() == 0
+        {
+            invoke_signed(
+                &create_account(
+                    contributor_account.key,
+                    backer_record_account.key,
+                    min_excemption_balance,
+                    backer_record.byte_len() as u64,
+                    program_id
+                ),
+                accounts,
+                &[signer_seeds]
+            )?;
+        }
+        else
+        {
+            // lamports sent to the address upfront would make create_account fail, top up to rent exemption instead
+            let top_up = min_excemption_balance.saturating_sub(backer_record_account.lamports());
+            if top_up > 0
+            {
+                invoke(
+                    &transfer(contributor_account.key, backer_record_account.key, top_up),
+                    accounts
+                )?;
+            }
+
+            invoke_signed(
+                &allocate(backer_record_account.key, backer_record.byte_len() as u64),
+                accounts,
+                &[signer_seeds]
+            )?;
+
+            invoke_signed(
+                &assign(backer_record_account.key, program_id),
+                accounts,
+                &[signer_seeds]
+            )?;
+        }
+
+        backer_record
+    }
+    else
+    {
+        let mut backer_record = unpack_backer_record(backer_record_account, program_id)?;
+
+        if backer_record.token_key.ne(mint_account.key)
+            || backer_record.contributor.ne(contributor_account.key)
+            || backer_record.bump != bump
+        {
+            msg!("Backer record not pointing to mint and contributor. Aborting.");
+            return Err(CoinbackedError::InvalidBackerRecordData.into());
+        }
+
+        backer_record.total_lamports = backer_record.total_lamports.checked_add(lamports)
+            .ok_or(CoinbackedError::MathError)?;
+        backer_record.contribution_count = backer_record.contribution_count.checked_add(1)
+            .ok_or(CoinbackedError::MathError)?;
+        backer_record.last_slot = slot;
+
+        backer_record
+    };
+
+    msg!("Backer record updated: {} lamports in {} contributions.", backer_record.total_lamports, backer_record.contribution_count);
+    backer_record.pack(&mut backer_record_account.try_borrow_mut_data()?);
+
+    Ok(())
+}
+
+fn realloc_account_layout(
+    payer_account: &AccountInfo,
+    program_account: &AccountInfo,
+    rent_excemption: u64,
+    byte_len: usize,
+    accounts: &[AccountInfo]
+) -> Result<u64, ProgramError>
+{
+    // stored rent excemption only grows, lamports above it keep their meaning
+    let rent = Rent::get()?;
+    let additional_rent = rent.minimum_balance(byte_len).saturating_sub(rent_excemption);
+
+    if additional_rent > 0
+    {
+        invoke(
+            &transfer(payer_account.key, program_account.key, additional_rent),
+            accounts
+        )?;
+    }
+
+    program_account.realloc(byte_len, true)?;
+
+    msg!("Account reallocated to {} bytes, additional rent: {}", byte_len, additional_rent);
+
+    rent_excemption.checked_add(additional_rent)
+        .ok_or(CoinbackedError::MathError.into())
+}
+
+fn is_token_program(
+    key: &Pubkey
+) -> bool
+{
+    key.eq(&spl_token::ID) || key.eq(&spl_token_2022::ID)
+}
+
+fn unpack_mint(
+    mint_account: &AccountInfo
+) -> Result<Mint, ProgramError>
+{
+    if !is_token_program(mint_account.owner)
+    {
+        msg!("Mint not owned by token program! Aborting.");
+        return Err(CoinbackedError::InvalidTokenProgram.into());
+    }
+
+    // base state is shared by token and token-2022, extensions are skipped
+    let data = mint_account.try_borrow_data()?;
+    Ok(StateWithExtensions::<Mint>::unpack(&data)?.base)
+}
+
+fn unpack_token_account(
+    token_account: &AccountInfo
+) -> Result<Account, ProgramError>
+{
+    if !is_token_program(token_account.owner)
+    {
+        msg!("Token account not owned by token program! Aborting.");
+        return Err(CoinbackedError::InvalidTokenProgram.into());
+    }
+
+    let data = token_account.try_borrow_data()?;
+    Ok(StateWithExtensions::<Account>::unpack(&data)?.base)
+}
+
+fn unpack_backing_account(
+    backing_pda: &AccountInfo,
+    program_id: &Pubkey
+) -> Result<BackingAccount, ProgramError>
+{
+    // data of accounts not owned by program cannot be trusted
+    if backing_pda.owner.ne(program_id)
+    {
+        msg!("Backing account not owned by program! Aborting.");
+        return Err(CoinbackedError::InvalidAccountOwner.into());
+    }
+
+    BackingAccount::unpack(&backing_pda.try_borrow_data()?)
+}
+
+fn unpack_treasury_account(
+    protocol_treasury_account: &AccountInfo,
+    program_id: &Pubkey
+) -> Result<TreasuryAccount, ProgramError>
+{
+    if protocol_treasury_account.owner.ne(program_id)
+    {
+        msg!("Treasury account not owned by program! Aborting.");
+        return Err(CoinbackedError::InvalidAccountOwner.into());
+    }
+
+    TreasuryAccount::unpack(&protocol_treasury_account.try_borrow_data()?)
+}
+
+fn unpack_backer_record(
+    backer_record_account: &AccountInfo,
+    program_id: &Pubkey
+) -> Result<BackerRecord, ProgramError>
+{
+    if backer_record_account.owner.ne(program_id)
+    {
+        msg!("Backer record not owned by program! Aborting.");
+        return Err(CoinbackedError::InvalidAccountOwner.into());
+    }
+
+    BackerRecord::unpack(&backer_record_account.try_borrow_data()?)
+}
+
+fn has_unsupported_mint_extension(
+    mint_account: &AccountInfo
+) -> Result<bool, ProgramError>
+{
+    if mint_account.owner.ne(&spl_token_2022::ID)
+    {
+        return Ok(false);
+    }
+
+    // permanent delegate can take tokens from holders, non-transferable tokens and closable mints break payout invariant
+    let data = mint_account.try_borrow_data()?;
+    let extension_types = StateWithExtensions::<Mint>::unpack(&data)?.get_extension_types()?;
+
+    Ok(extension_types.iter().any(|extension_type| matches!(
+        extension_type,
+        ExtensionType::PermanentDelegate
+            | ExtensionType::NonTransferable
+            | ExtensionType::MintCloseAuthority
+    )))
+}
+
+fn is_multisig(
+    account: &AccountInfo
+) -> bool
+{
+    is_token_program(account.owner) && account.data_len() == Multisig::LEN
+}
+
+fn get_fee_payer_account<'a, 'b>(
+    authority_account: &'a AccountInfo<'b>,
+    signer_accounts: &'a [AccountInfo<'b>],
+    signer_keys: &[&Pubkey],
+) -> Result<&'a AccountInfo<'b>, ProgramError>
+{
+    if !is_multisig(authority_account)
+    {
+        return Ok(authority_account);
+    }
+
+    // multisig cannot pay, first of its signers does
+    signer_keys.first()
+        .and_then(|signer_key| signer_accounts.iter().find(|signer_account| signer_account.key.eq(signer_key)))
+        .ok_or_else(|| CoinbackedError::AccountNotSigner.into())
+}
+
+fn has_lamport_backing(
+    backing_account: &BackingAccount
+) -> bool
+{
+    // collateral accounts are created without lamports and cannot be topped up, surplus above rent is unasked dust
+    backing_account.stats.as_ref().is_some_and(|stats| stats.total_deposited > 0)
+}
+
+fn get_token_authority_signers<'a>(
+    authority_account: &AccountInfo<'a>,
+    signer_accounts: &[AccountInfo<'a>],
+) -> Result<Vec<&'a Pubkey>, ProgramError>
+{
+    // single authority has to sign itself
+    if !is_multisig(authority_account)
+    {
+        if !authority_account.is_signer
+        {
+            msg!("Account is not signer! Aborting.");
+            return Err(CoinbackedError::AccountNotSigner.into());
+        }
+
+        return Ok(vec![authority_account.key]);
+    }
+
+    // multisig authority, collect distinct signers of multisig
+    let multisig = Multisig::unpack(&authority_account.try_borrow_data()?)?;
+    let mut matched = [false; MAX_SIGNERS];
+    let mut signer_keys = Vec::with_capacity(multisig.m as usize);
+
+    for signer_account in signer_accounts.iter()
+    {
+        for (position, key) in multisig.signers[0..multisig.n as usize].iter().enumerate()
+        {
+            if key.eq(signer_account.key) && !matched[position]
+            {
+                if !signer_account.is_signer
+                {
+                    msg!("Multisig signer account is not signer! Aborting.");
+                    return Err(CoinbackedError::AccountNotSigner.into());
+                }
+
+                matched[position] = true;
+                signer_keys.push(signer_account.key);
+            }
+        }
+    }
+
+    if signer_keys.len() < multisig.m as usize
+    {
+        msg!("Multisig requires {} signers, got {}! Aborting.", multisig.m, signer_keys.len());
+        return Err(CoinbackedError::MultisigThresholdNotMet.into());
+    }
+
+    Ok(signer_keys)
+}
+
+fn check_protocol_treasury_account(
+    protocol_treasury_account: &AccountInfo,
+    program_id: &Pubkey
+) -> Result<u8, ProgramError>
+{
+    let seeds = &[
+        program_id.as_ref(),
+        CO_SEED_PROTOCOL_TREASURY
+    ];
+
+    let (treasury_pda_key, bump) = Pubkey::find_program_address(seeds, program_id);
+
+    // check pda
+    if treasury_pda_key.ne(protocol_treasury_account.key)
+    {
+        msg!("Account key missmatch - PDA for treasury account is not matching. Aborting.");
+        return Err(CoinbackedError::InvalidTreasuryAccountAddress.into());
+    }
+
+    // if exist, check owner and bump...
+    if !protocol_treasury_account.data_is_empty() && protocol_treasury_account.owner.eq(program_id)
+    {
+        let treasure = unpack_treasury_account(protocol_treasury_account, program_id)?;
+        if treasure.bump != bump
+        {
+            msg!("Account key missmatch - PDA bump for treasury account is not matching. Aborting.");
+            return Err(CoinbackedError::InvalidTreasuryAccountBump.into());
+        }
+    }
+
+    Ok(bump)
+}
+
+fn check_config_account(
+    config_account: &AccountInfo,
+    program_id: &Pubkey
+) -> Result<u8, ProgramError>
+{
+    let seeds = &[
+        program_id.as_ref(),
+        CO_SEED_CONFIG
+    ];
+
+    let (config_pda_key, bump) = Pubkey::find_program_address(seeds, program_id);
+
+    // check pda
+    if config_pda_key.ne(config_account.key)
+    {
+        msg!("Account key missmatch - PDA for config account is not matching. Aborting.");
+        return Err(CoinbackedError::InvalidConfigAccountAddress.into());
+    }
+
+    Ok(bump)
+}
+
+fn split_config_account<'a, 'b>(
+    program_id: &Pubkey,
+    trailing_accounts: &'a [AccountInfo<'b>]
+) -> (&'a [AccountInfo<'b>], Option<&'a AccountInfo<'b>>, &'a [AccountInfo<'b>])
+{
+    // config is found by address, optional accounts like signers, referrer and creator are placed around it
+    let (config_pda_key, _) = Pubkey::find_program_address(&[program_id.as_ref(), CO_SEED_CONFIG], program_id);
+
+    match trailing_accounts.iter().position(|account| account.key.eq(&config_pda_key))
+    {
+        Some(position) => (&trailing_accounts[..position], Some(&trailing_accounts[position]), &trailing_accounts[position + 1..]),
+        None => (trailing_accounts, None, &[]),
+    }
+}
+
+fn unpack_config_account_or_default(
+    config_account: Option<&AccountInfo>,
+    program_id: &Pubkey
+) -> Result<ConfigAccount, ProgramError>
+{
+    // config pda has to be passed, skipping it would skip fees and pauses set by the admin
+    let config_account = match config_account
+    {
+        Some(config_account) => config_account,
+        None =>
+        {
+            msg!("Config account not passed. Aborting.");
+            return Err(CoinbackedError::ConfigAccountMissing.into());
+        }
+    };
+
+    // operations do not depend on the admin having initialised the config
+    if config_account.owner.eq(&system_program::ID) && config_account.data_is_empty()
+    {
+        msg!("Info: Config account not initialised, default protocol fees apply.");
+        return Ok(ConfigAccount::with_default_fees());
+    }
+
+    if config_account.owner.ne(program_id)
+    {
+        msg!("Config account is not owned by program. Aborting.");
+        return Err(CoinbackedError::InvalidConfigAccountData.into());
+    }
+
+    let config = ConfigAccount::unpack(&config_account.try_borrow_data()?)?;
+    let config_pda_key = Pubkey::create_program_address(&[program_id.as_ref(), CO_SEED_CONFIG, &[config.bump]], program_id)
+        .map_err(|_| CoinbackedError::InvalidConfigAccountData)?;
+    if config_pda_key.ne(config_account.key)
+    {
+        msg!("Account key missmatch - PDA bump for config account is not matching. Aborting.");
+        return Err(CoinbackedError::InvalidConfigAccountData.into());
+    }
+
+    Ok(config)
+}
+
+fn unpack_config_account(
+    config_account: &AccountInfo,
+    program_id: &Pubkey
+) -> Result<ConfigAccount, ProgramError>
+{
+    let bump = check_config_account(config_account, program_id)?;
+
+    if config_account.owner.ne(program_id)
+    {
+        msg!("Config account does not seem to exist. Aborting.");
+        return Err(CoinbackedError::ConfigAccountMissing.into());
     }
-    else
+
+    let config = ConfigAccount::unpack(&config_account.try_borrow_data()?)?;
+    if config.bump != bump
     {
-        // check content correct 
-        let backing_account = BackingAccount::unpack(&backing_pda.try_borrow_data().unwrap()[..])?;
-    
-        // backing account belongs to mint
-        if backing_account.token_key.ne(mint_account.key)
-        {
-            msg!("Backing account not pointing to mint account. Aborting.");
-            return Err(ProgramError::InvalidAccountData); 
-        }
-    
-        // seed bump check
-        if backing_account.bump != bump
+        msg!("Account key missmatch - PDA bump for config account is not matching. Aborting.");
+        return Err(CoinbackedError::InvalidConfigAccountData.into());
+    }
+
+    Ok(config)
+}
+
+fn check_not_paused(
+    config: &ConfigAccount,
+    flag: u8
+) -> ProgramResult
+{
+    if config.is_paused(flag, Clock::get()?.slot)
+    {
+        msg!("Operation is paused by admin (flag {}). Aborting.", flag);
+        return Err(CoinbackedError::OperationPaused.into());
+    }
+
+    Ok(())
+}
+
+fn record_treasury_withdrawal(
+    treasure: &mut TreasuryAccount,
+    config: &ConfigAccount,
+    lamports: u64
+) -> Result<u64, ProgramError>
+{
+    // older layouts cannot count withdrawals, limits require migration
+    if treasure.version < CO_ACCOUNT_PROTOCOL_TREASURY_VERSION_LIMITS
+    {
+        if config.treasury_delay_slots > 0 || config.treasury_epoch_cap > 0
         {
-            msg!("Backing account bump is incorrect. Aborting.");
-            return Err(ProgramError::InvalidAccountData); 
+            msg!("Protocol treasury account has layout version {}, has to be migrated for withdrawal limits. Aborting.", treasure.version);
+            return Err(CoinbackedError::InvalidTreasuryAccountData.into());
         }
+
+        return Ok(lamports);
     }
 
-    Ok(bump)
+    // counter restarts every epoch
+    let epoch = Clock::get()?.epoch;
+    if treasure.epoch != epoch
+    {
+        treasure.epoch = epoch;
+        treasure.epoch_withdrawn = 0;
+    }
+
+    let epoch_withdrawn = treasure.epoch_withdrawn.checked_add(lamports).ok_or(CoinbackedError::MathError)?;
+    if config.treasury_epoch_cap > 0 && epoch_withdrawn > config.treasury_epoch_cap
+    {
+        msg!("Maximum transfer amount in epoch: {}", config.treasury_epoch_cap.saturating_sub(treasure.epoch_withdrawn));
+        msg!("Withdrawal would exceed treasury epoch cap. Aborting.");
+        return Err(CoinbackedError::TreasuryEpochCapExceeded.into());
+    }
+
+    treasure.epoch_withdrawn = epoch_withdrawn;
+
+    Ok(epoch_withdrawn)
 }
 
-fn check_protocol_treasury_account(
-    protocol_treasury_account: &AccountInfo,
+fn check_withdrawal_proposal_account(
+    proposal_account: &AccountInfo,
+    proposal_id: u64,
     program_id: &Pubkey
 ) -> Result<u8, ProgramError>
 {
+    let proposal_id_data = proposal_id.to_le_bytes();
     let seeds = &[
         program_id.as_ref(),
-        CO_SEED_PROTOCOL_TREASURY
+        CO_SEED_WITHDRAWAL_PROPOSAL,
+        &proposal_id_data
     ];
 
-    let (treasury_pda_key, bump) = Pubkey::find_program_address(seeds, &program_id);
+    let (proposal_pda_key, bump) = Pubkey::find_program_address(seeds, program_id);
 
     // check pda
-    if treasury_pda_key.ne(protocol_treasury_account.key)
+    if proposal_pda_key.ne(proposal_account.key)
     {
-        msg!("Account key missmatch - PDA for treasury account is not matching. Aborting.");
-        return Err(ProgramError::InvalidAccountData);  
+        msg!("Account key missmatch - PDA for withdrawal proposal is not matching. Aborting.");
+        return Err(CoinbackedError::InvalidWithdrawalProposalAddress.into());
     }
 
-    // if exist, check owner and bump...
-    if !protocol_treasury_account.data_is_empty() && protocol_treasury_account.owner.eq(program_id)
+    Ok(bump)
+}
+
+fn unpack_withdrawal_proposal(
+    proposal_account: &AccountInfo,
+    program_id: &Pubkey
+) -> Result<WithdrawalProposal, ProgramError>
+{
+    if proposal_account.owner.ne(program_id)
     {
-        let treasure = TreasuryAccount::unpack(&protocol_treasury_account.try_borrow_data()?)?;
-        if treasure.bump != bump
-        {
-            msg!("Account key missmatch - PDA bump for treasury account is not matching. Aborting.");
-            return Err(ProgramError::InvalidAccountData);  
-        }
+        msg!("Withdrawal proposal not owned by program! Aborting.");
+        return Err(CoinbackedError::InvalidAccountOwner.into());
     }
 
-    Ok(bump)
+    let proposal = WithdrawalProposal::unpack(&proposal_account.try_borrow_data()?)?;
+
+    // stored id and bump have to derive the account
+    let bump = check_withdrawal_proposal_account(proposal_account, proposal.proposal_id, program_id)?;
+    if proposal.bump != bump
+    {
+        msg!("Account key missmatch - PDA bump for withdrawal proposal is not matching. Aborting.");
+        return Err(CoinbackedError::InvalidWithdrawalProposalData.into());
+    }
+
+    Ok(proposal)
 }
 
 fn pay_protocol(
@@ -819,6 +4719,7 @@ fn pay_protocol(
     protocol_treasury_account: &AccountInfo,
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    fee: u64,
     invoke_transfer: bool
 ) -> ProgramResult 
 {
@@ -828,33 +4729,277 @@ fn pay_protocol(
     if protocol_treasury_account.owner.ne(program_id)
     {
         msg!("Treasury account does not seem to exist. No treasury payment executed. Aborting.");
-        return Err(ProgramError::InvalidAccountData);
+        return Err(CoinbackedError::TreasuryAccountMissing.into());
+    }
+
+    if fee == 0
+    {
+        msg!("Info: No protocol fee configured for operation.");
+        return Ok(());
+    }
+
+    msg!("Protocol fee applied: {} lamports", fee);
+
+    transfer_fee(source_account, protocol_treasury_account, accounts, fee, invoke_transfer)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn pay_protocol_with_shares(
+    source_account: &AccountInfo,
+    protocol_treasury_account: &AccountInfo,
+    mint_account: &AccountInfo,
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fee: u64,
+    fee_shares: &[(&AccountInfo, u16)],
+    invoke_transfer: bool
+) -> ProgramResult
+{
+    let mut treasury_fee = fee;
+    let rent = Rent::get()?;
+
+    for (share_account, share_bps) in fee_shares.iter()
+    {
+        let share = get_fee_share(fee, *share_bps)?;
+        if share == 0
+        {
+            continue;
+        }
+
+        // a share that would leave the receiver below rent exemption stays with the treasury
+        let balance_after = share_account.lamports().checked_add(share)
+            .ok_or(CoinbackedError::MathError)?;
+        if balance_after < rent.minimum_balance(share_account.data_len())
+        {
+            msg!("Info: Fee share receiver {} would not be rent exempt, share goes to treasury.", share_account.key);
+            continue;
+        }
+
+        treasury_fee = treasury_fee.checked_sub(share)
+            .ok_or(CoinbackedError::MathError)?;
+
+        msg!("Protocol fee share applied: {} lamports to {}", share, share_account.key);
+
+        transfer_fee(source_account, share_account, accounts, share, invoke_transfer)?;
+
+        Event
+        {
+            kind: EventKind::ProtocolFeeShare,
+            mint: *mint_account.key,
+            actor: *source_account.key,
+            counterparty: *share_account.key,
+            lamports: share,
+            token_amount: fee,
+            supply_before: 0,
+            supply_after: 0,
+            per_unit_payout: 0,
+        }.emit();
     }
 
-    // actual transfer to treasury - two versions due to different borrowing behaviour
+    pay_protocol(source_account, protocol_treasury_account, program_id, accounts, treasury_fee, invoke_transfer)
+}
+
+fn transfer_fee(
+    source_account: &AccountInfo,
+    destination_account: &AccountInfo,
+    accounts: &[AccountInfo],
+    fee: u64,
+    invoke_transfer: bool
+) -> ProgramResult
+{
+    // actual transfer of fee - two versions due to different borrowing behaviour
     if invoke_transfer
     {
         invoke(
-            &transfer(source_account.key, protocol_treasury_account.key, CO_PROTOCOL_FEE),
-            &accounts
+            &transfer(source_account.key, destination_account.key, fee),
+            accounts
         )?;
     }
     else
     {   
-        //**source_account.lamports.borrow_mut() -= CO_PROTOCOL_FEE;
         **source_account.try_borrow_mut_lamports()? = 
-            source_account.lamports().checked_sub(CO_PROTOCOL_FEE)
-            .ok_or(ProgramError::InvalidAccountData)?;
+            source_account.lamports().checked_sub(fee)
+            .ok_or(CoinbackedError::InsufficientFundsForProtocolFee)?;
         
-        //**protocol_treasury_account.lamports.borrow_mut() += CO_PROTOCOL_FEE;
-        **protocol_treasury_account.try_borrow_mut_lamports()? = 
-            protocol_treasury_account.lamports().checked_add(CO_PROTOCOL_FEE)
-            .ok_or(ProgramError::InvalidAccountData)?;
+        **destination_account.try_borrow_mut_lamports()? = 
+            destination_account.lamports().checked_add(fee)
+            .ok_or(CoinbackedError::MathError)?;
+    }
+
+    Ok(())
+}
+
+fn get_fee_share(
+    fee: u64,
+    share_bps: u16
+) -> Result<u64, ProgramError>
+{
+    // fee * share_bps / 10000, rounded down in favour of the protocol
+    Decimal::from(fee).try_mul(Decimal::from(share_bps as u64))?
+        .try_div(Decimal::from(CO_BPS_DENOMINATOR))?
+        .try_floor_u64()
+}
+
+fn get_creator_fee_share<'a, 'b>(
+    backing_account: &BackingAccount,
+    config: &ConfigAccount,
+    after_config_accounts: &'a [AccountInfo<'b>]
+) -> Result<Option<(&'a AccountInfo<'b>, u16)>, ProgramError>
+{
+    let creator = match backing_account.creator
+    {
+        Some(creator) if config.creator_fee_share_bps > 0 => creator,
+        _ => return Ok(None),
+    };
+
+    // creator is the first account after config and has to be passed, omitting it would hand the share to treasury
+    let creator_account = match after_config_accounts.first()
+    {
+        Some(creator_account) if creator_account.key.eq(&creator) => creator_account,
+        _ =>
+        {
+            msg!("Creator account {} not passed after config! Aborting.", creator);
+            return Err(CoinbackedError::InvalidCreatorAccount.into());
+        }
+    };
+
+    // runtime demotes programs and sysvars to read-only, they cannot receive a share
+    if creator_account.executable || creator_account.owner.eq(&sysvar::ID)
+    {
+        msg!("Info: Creator account {} cannot receive lamports, share goes to treasury.", creator);
+        return Ok(None);
+    }
+
+    if !creator_account.is_writable
+    {
+        msg!("Creator account {} not writable! Aborting.", creator);
+        return Err(CoinbackedError::InvalidCreatorAccount.into());
+    }
+
+    Ok(Some((creator_account, config.creator_fee_share_bps)))
+}
+
+fn get_referrer_fee_share<'a, 'b>(
+    referrer_account: Option<&'a AccountInfo<'b>>,
+    config: &ConfigAccount
+) -> Option<(&'a AccountInfo<'b>, u16)>
+{
+    referrer_account
+        .filter(|_| config.referrer_fee_share_bps > 0)
+        .map(|referrer_account| (referrer_account, config.referrer_fee_share_bps))
+}
+
+fn get_referrer_account<'a, 'b>(
+    trailing_accounts: &'a [AccountInfo<'b>],
+    claimed_keys: &[Pubkey],
+    party_keys: &[&Pubkey]
+) -> Result<Option<&'a AccountInfo<'b>>, ProgramError>
+{
+    // referrer is the last account, unless it is taken by another optional account
+    let referrer_account = match trailing_accounts.last()
+    {
+        Some(account) if !claimed_keys.contains(account.key) => account,
+        _ => return Ok(None),
+    };
+
+    // referrer has to be an existing plain system account
+    if !referrer_account.is_writable
+        || referrer_account.owner.ne(&system_program::ID)
+        || referrer_account.executable
+        || !referrer_account.data_is_empty()
+        || referrer_account.lamports() == 0
+    {
+        msg!("Referrer account {} is not a writable system account! Aborting.", referrer_account.key);
+        return Err(CoinbackedError::InvalidReferrerAccount.into());
+    }
+
+    // no self-referral by payer, token owner or recipient
+    if party_keys.contains(&referrer_account.key)
+    {
+        msg!("Referrer account {} is a party of the operation! Aborting.", referrer_account.key);
+        return Err(CoinbackedError::InvalidReferrerAccount.into());
+    }
+
+    Ok(Some(referrer_account))
+}
+
+fn get_backer_record_and_referrer<'a, 'b>(
+    program_id: &Pubkey,
+    contributor_account: &AccountInfo,
+    mint_account: &AccountInfo,
+    trailing_accounts: &'a [AccountInfo<'b>],
+    claimed_keys: &[Pubkey]
+) -> Result<(Option<&'a AccountInfo<'b>>, Option<&'a AccountInfo<'b>>), ProgramError>
+{
+    if trailing_accounts.is_empty()
+    {
+        return Ok((None, None));
+    }
+
+    // record is found by address, so a referrer can be passed without it
+    let (backer_record_key, _) = Pubkey::find_program_address(
+        &[
+            mint_account.key.as_ref(),
+            contributor_account.key.as_ref(),
+            program_id.as_ref(),
+            CO_SEED_BACKER_RECORD
+        ],
+        program_id
+    );
+    let backer_record_account = trailing_accounts.iter().find(|account| account.key.eq(&backer_record_key));
+
+    let mut claimed_keys = claimed_keys.to_vec();
+    claimed_keys.push(backer_record_key);
+    let referrer_account = get_referrer_account(trailing_accounts, &claimed_keys, &[contributor_account.key])?;
+
+    Ok((backer_record_account, referrer_account))
+}
+
+fn check_burn_fee_caps(
+    burn_fee: u64,
+    burn_fee_bps: u16,
+    burn_fee_max: u64
+) -> ProgramResult
+{
+    // burns must stay cheap whatever the admin configures
+    if burn_fee > CO_BURN_FEE_MAX_LAMPORTS || burn_fee_max > CO_BURN_FEE_MAX_LAMPORTS || burn_fee_bps > CO_BURN_FEE_MAX_BPS
+    {
+        msg!("Burn fee exceeds cap of {} bps and {} lamports! Aborting.", CO_BURN_FEE_MAX_BPS, CO_BURN_FEE_MAX_LAMPORTS);
+        return Err(CoinbackedError::BurnFeeAboveCap.into());
     }
 
     Ok(())
 }
 
+fn get_protocol_fee(
+    amount: u64,
+    flat_fee: u64,
+    fee_bps: u16,
+    max_fee: u64
+) -> Result<u64, ProgramError>
+{
+    // no fee rate, flat fee only
+    if fee_bps == 0
+    {
+        return Ok(flat_fee);
+    }
+
+    // amount * fee_bps / 10000, rounded up in favour of the protocol; flat fee is the minimum
+    let fee = Decimal::from(amount).try_mul(Decimal::from(fee_bps as u64))?
+        .try_div(Decimal::from(CO_BPS_DENOMINATOR))?
+        .try_ceil_u64()?
+        .max(flat_fee);
+
+    if max_fee > 0
+    {
+        Ok(fee.min(max_fee))
+    }
+    else
+    {
+        Ok(fee)
+    }
+}
+
 fn token_amount_one_unit(
     decimals: u8
 ) -> u64
@@ -862,6 +5007,52 @@ fn token_amount_one_unit(
     10_usize.pow(decimals as u32) as u64
 }
 
+fn get_per_unit_payout(
+    decimals: u8,
+    supply: u64,
+    backing_lamports: u64,
+    backing_rent_excemption: u64
+) -> Result<u64, ProgramError>
+{
+    // no supply left, nothing to pay out per unit
+    if supply == 0
+    {
+        return Ok(0);
+    }
+
+    get_payout_in_lamport(
+        token_amount_one_unit(decimals),
+        supply,
+        backing_lamports,
+        backing_rent_excemption
+    )
+}
+
+fn get_payout_in_lamport_after_exit_fee(
+    token_amount: u64,
+    supply: u64,
+    backing_lamports: u64,
+    backing_rent_excemption: u64,
+    exit_fee_bps: u16
+) -> Result<u64, ProgramError>
+{
+    // last holder takes all, nothing would be left to benefit from the fee
+    if exit_fee_bps == 0 || token_amount >= supply
+    {
+        return get_payout_in_lamport(token_amount, supply, backing_lamports, backing_rent_excemption);
+    }
+
+    // token_amount * (backing_lamports - backing_rent_excemption) / supply * (10000 - exit_fee_bps) / 10000
+    Decimal::from(token_amount).try_mul(
+        Decimal::from(backing_lamports).try_sub(
+                Decimal::from(backing_rent_excemption)
+            )?
+        )?
+        .try_div(Decimal::from(supply))?
+        .try_mul(Decimal::from(CO_BPS_DENOMINATOR - exit_fee_bps as u64))?
+        .try_div(Decimal::from(CO_BPS_DENOMINATOR))?.try_floor_u64()
+}
+
 fn get_payout_in_lamport(
     token_amount: u64,
     supply: u64,
@@ -877,3 +5068,227 @@ fn get_payout_in_lamport(
         )?
         .try_div(Decimal::from(supply))?.try_floor_u64()
 }
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn test_get_protocol_fee()
+    {
+        // flat fee only
+        assert_eq!(get_protocol_fee(1_000_000_000, 5_000, 0, 0).unwrap(), 5_000);
+
+        // rate rounded up in favour of the protocol
+        assert_eq!(get_protocol_fee(1_000_000_000, 5_000, 100, 0).unwrap(), 10_000_000);
+        assert_eq!(get_protocol_fee(10_001, 0, 100, 0).unwrap(), 101);
+
+        // flat fee is the minimum, maximum caps the rate
+        assert_eq!(get_protocol_fee(100_000, 5_000, 100, 0).unwrap(), 5_000);
+        assert_eq!(get_protocol_fee(1_000_000_000, 5_000, 100, 1_000_000).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn test_check_burn_fee_caps()
+    {
+        assert!(check_burn_fee_caps(CO_BURN_FEE_MAX_LAMPORTS, CO_BURN_FEE_MAX_BPS, CO_BURN_FEE_MAX_LAMPORTS).is_ok());
+        assert!(check_burn_fee_caps(CO_BURN_FEE_MAX_LAMPORTS + 1, 0, 0).is_err());
+        assert!(check_burn_fee_caps(0, CO_BURN_FEE_MAX_BPS + 1, 0).is_err());
+        assert!(check_burn_fee_caps(0, 0, CO_BURN_FEE_MAX_LAMPORTS + 1).is_err());
+    }
+
+    #[test]
+    fn test_get_payout_in_lamport_after_exit_fee()
+    {
+        let rent_excemption = 1_000;
+
+        // no exit fee, pro-rata share of backing above rent excemption
+        assert_eq!(get_payout_in_lamport_after_exit_fee(100, 1_000, 1_001_000, rent_excemption, 0).unwrap(), 100_000);
+
+        // exit fee retained in backing, rounded down
+        assert_eq!(get_payout_in_lamport_after_exit_fee(100, 1_000, 1_001_000, rent_excemption, 250).unwrap(), 97_500);
+        assert_eq!(get_payout_in_lamport_after_exit_fee(1, 3, 1_001_000, rent_excemption, 1).unwrap(), 333_299);
+
+        // last holder takes all
+        assert_eq!(get_payout_in_lamport_after_exit_fee(1_000, 1_000, 1_001_000, rent_excemption, 250).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn test_get_creator_fee_share()
+    {
+        let creator = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let owner = system_program::ID;
+        let mut creator_lamports = 0;
+        let mut creator_data = vec![];
+        let mut other_lamports = 0;
+        let mut other_data = vec![];
+
+        let backing_account = BackingAccount
+        {
+            version: CO_ACCOUNT_BACKING_VERSION,
+            token_key: Pubkey::new_unique(),
+            rent_excemption: 0,
+            bump: 255,
+            stats: None,
+            exit_fee_bps: 0,
+            creator: Some(creator),
+            collateral_mints: Vec::new(),
+        };
+        let config = ConfigAccount { creator_fee_share_bps: 2_000, ..ConfigAccount::with_default_fees() };
+
+        let creator_account = AccountInfo::new(&creator, false, true, &mut creator_lamports, &mut creator_data, &owner, false, 0);
+        let other_account = AccountInfo::new(&other, false, true, &mut other_lamports, &mut other_data, &owner, false, 0);
+
+        // creator takes the slot right after config
+        let trailing_accounts = [creator_account.clone()];
+        let (share_account, share_bps) = get_creator_fee_share(&backing_account, &config, &trailing_accounts).unwrap().unwrap();
+        assert_eq!(share_account.key, &creator);
+        assert_eq!(share_bps, 2_000);
+
+        // missing, misplaced or read-only creator fails instead of leaving the share with the treasury
+        for trailing_accounts in [&[][..], &[other_account.clone(), creator_account.clone()][..], &[AccountInfo { is_writable: false, ..creator_account.clone() }][..]]
+        {
+            assert_eq!(
+                get_creator_fee_share(&backing_account, &config, trailing_accounts).unwrap_err(),
+                CoinbackedError::InvalidCreatorAccount.into()
+            );
+        }
+
+        // creator that cannot hold lamports leaves the share with the treasury
+        let program_creator_account = AccountInfo { is_writable: false, executable: true, ..creator_account.clone() };
+        assert!(get_creator_fee_share(&backing_account, &config, &[program_creator_account]).unwrap().is_none());
+
+        // no share configured or no creator
+        let no_creator_backing_account = BackingAccount { creator: None, ..backing_account };
+        assert!(get_creator_fee_share(&no_creator_backing_account, &config, &[]).unwrap().is_none());
+        let backing_account = BackingAccount { creator: Some(creator), ..no_creator_backing_account };
+        let no_share_config = ConfigAccount { creator_fee_share_bps: 0, ..config };
+        assert!(get_creator_fee_share(&backing_account, &no_share_config, &[]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_get_fee_share()
+    {
+        assert_eq!(get_fee_share(10_000, 2_500).unwrap(), 2_500);
+        assert_eq!(get_fee_share(10_001, 2_500).unwrap(), 2_500);
+        assert_eq!(get_fee_share(3, 3_333).unwrap(), 0);
+        assert_eq!(get_fee_share(5_000, 0).unwrap(), 0);
+        assert_eq!(get_fee_share(5_000, CO_BPS_DENOMINATOR as u16).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn test_get_referrer_account()
+    {
+        let referrer = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let owner = system_program::ID;
+        let mut referrer_lamports = 1_000_000;
+        let mut referrer_data = vec![];
+        let mut payer_lamports = 1_000_000;
+        let mut payer_data = vec![];
+
+        let referrer_account = AccountInfo::new(&referrer, false, true, &mut referrer_lamports, &mut referrer_data, &owner, false, 0);
+        let payer_account = AccountInfo::new(&payer, true, true, &mut payer_lamports, &mut payer_data, &owner, false, 0);
+
+        // referrer is the last trailing account
+        let trailing_accounts = [payer_account.clone(), referrer_account.clone()];
+        let found = get_referrer_account(&trailing_accounts, &[], &[&payer]).unwrap().unwrap();
+        assert_eq!(found.key, &referrer);
+
+        // last account claimed by another optional account, or no trailing accounts
+        assert!(get_referrer_account(&trailing_accounts, &[referrer], &[&payer]).unwrap().is_none());
+        assert!(get_referrer_account(&[], &[], &[&payer]).unwrap().is_none());
+
+        // self-referral by a party of the operation
+        assert_eq!(
+            get_referrer_account(&trailing_accounts, &[], &[&payer, &referrer]).unwrap_err(),
+            CoinbackedError::InvalidReferrerAccount.into()
+        );
+
+        // read-only referrer
+        let read_only_accounts = [AccountInfo { is_writable: false, ..referrer_account }];
+        assert_eq!(
+            get_referrer_account(&read_only_accounts, &[], &[&payer]).unwrap_err(),
+            CoinbackedError::InvalidReferrerAccount.into()
+        );
+    }
+
+    #[test]
+    fn test_split_config_account()
+    {
+        let program_id = Pubkey::new_unique();
+        let (config_key, _) = Pubkey::find_program_address(&[program_id.as_ref(), CO_SEED_CONFIG], &program_id);
+        let keys = [Pubkey::new_unique(), config_key, Pubkey::new_unique()];
+        let owner = system_program::ID;
+        let mut lamports = [0u64; 3];
+        let mut data: [Vec<u8>; 3] = Default::default();
+
+        let accounts: Vec<AccountInfo> = keys.iter().zip(lamports.iter_mut()).zip(data.iter_mut())
+            .map(|((key, lamports), data)| AccountInfo::new(key, false, false, lamports, data, &owner, false, 0))
+            .collect();
+
+        let (before, config_account, after) = split_config_account(&program_id, &accounts);
+        assert_eq!(before.len(), 1);
+        assert_eq!(config_account.unwrap().key, &config_key);
+        assert_eq!(after.len(), 1);
+
+        // missing config leaves the accounts untouched, unpacking rejects it
+        let (before, config_account, after) = split_config_account(&program_id, &accounts[..1]);
+        assert_eq!(before.len(), 1);
+        assert!(config_account.is_none());
+        assert!(after.is_empty());
+    }
+
+    #[test]
+    fn test_unpack_config_account_or_default()
+    {
+        let program_id = Pubkey::new_unique();
+        let (config_key, bump) = Pubkey::find_program_address(&[program_id.as_ref(), CO_SEED_CONFIG], &program_id);
+
+        // missing config would skip fees and pauses
+        assert_eq!(
+            unpack_config_account_or_default(None, &program_id).unwrap_err(),
+            CoinbackedError::ConfigAccountMissing.into()
+        );
+
+        // uninitialised config applies default fees
+        let owner = system_program::ID;
+        let mut lamports = 0;
+        let mut empty_data = vec![];
+        let config_account = AccountInfo::new(&config_key, false, false, &mut lamports, &mut empty_data, &owner, false, 0);
+        let config = unpack_config_account_or_default(Some(&config_account), &program_id).unwrap();
+        assert_eq!(config.burn_fee, CO_PROTOCOL_FEE);
+
+        let stored_config = ConfigAccount
+        {
+            burn_fee: 1_234,
+            bump,
+            pause_flags: CO_PAUSE_BURN,
+            ..ConfigAccount::with_default_fees()
+        };
+        let mut data = vec![0u8; stored_config.byte_len()];
+        stored_config.pack(&mut data);
+        let mut lamports = 1_000_000;
+
+        // config data not owned by program is rejected, not defaulted
+        let config_account = AccountInfo::new(&config_key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert_eq!(
+            unpack_config_account_or_default(Some(&config_account), &program_id).unwrap_err(),
+            CoinbackedError::InvalidConfigAccountData.into()
+        );
+
+        let other_program_id = Pubkey::new_unique();
+        let config_account = AccountInfo { owner: &other_program_id, ..config_account };
+        assert_eq!(
+            unpack_config_account_or_default(Some(&config_account), &program_id).unwrap_err(),
+            CoinbackedError::InvalidConfigAccountData.into()
+        );
+
+        let config_account = AccountInfo { owner: &program_id, ..config_account };
+        let config = unpack_config_account_or_default(Some(&config_account), &program_id).unwrap();
+        assert_eq!(config.burn_fee, 1_234);
+        assert_eq!(config.pause_flags, CO_PAUSE_BURN);
+    }
+}
@@ -0,0 +1,377 @@
+//! Data returned by read-only instructions via `set_return_data`
+
+use solana_program::
+{
+    msg, pubkey::Pubkey, program_error::ProgramError,
+};
+
+use arrayref::
+{
+    array_ref, array_refs, mut_array_refs, array_mut_ref
+};
+
+use crate::
+{
+    utils::*,
+    error::CoinbackedError,
+};
+
+/// Validation check: backing account is PDA of mint
+pub const CO_VALIDATION_CHECK_PDA: u8 = 1 << 0;
+/// Validation check: backing account points to mint
+pub const CO_VALIDATION_CHECK_MINT: u8 = 1 << 1;
+/// Validation check: backing account holds at least rent excemption
+pub const CO_VALIDATION_CHECK_RENT_EXCEMPTION: u8 = 1 << 2;
+/// Validation check: backing account bump is correct
+pub const CO_VALIDATION_CHECK_BUMP: u8 = 1 << 3;
+/// Validation check: mint has no token-2022 extension breaking the payout guarantee
+pub const CO_VALIDATION_CHECK_MINT_EXTENSIONS: u8 = 1 << 4;
+/// All validation checks
+pub const CO_VALIDATION_CHECK_ALL: u8 = CO_VALIDATION_CHECK_PDA | CO_VALIDATION_CHECK_MINT | CO_VALIDATION_CHECK_RENT_EXCEMPTION | CO_VALIDATION_CHECK_BUMP | CO_VALIDATION_CHECK_MINT_EXTENSIONS;
+
+/// Quote for one collateral asset of a basket backed account
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralQuote
+{
+    /// mint of the collateral
+    pub mint: Pubkey,
+
+    /// payout in collateral base units for the quoted token amount, one token unit in validation reports
+    pub payout: u64,
+
+    /// payout in collateral base units for one token unit
+    pub per_unit_payout: u64,
+
+    /// collateral held in the vault
+    pub backing_amount: u64,
+}
+
+impl CollateralQuote
+{
+    /// Packs the collateral quote into a byte buffer.
+    pub fn pack(&self, dst: &mut [u8])
+    {
+        let dst = array_mut_ref![dst, 0, CO_RETURN_COLLATERAL_QUOTE_BYTE_LEN];
+        let (mint_dst, payout_dst, per_unit_payout_dst, backing_amount_dst) =
+            mut_array_refs![dst, CO_PUBKEY_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN];
+        mint_dst.copy_from_slice(self.mint.as_ref());
+        *payout_dst = self.payout.to_le_bytes();
+        *per_unit_payout_dst = self.per_unit_payout.to_le_bytes();
+        *backing_amount_dst = self.backing_amount.to_le_bytes();
+    }
+
+    /// Unpacks a collateral quote from a byte buffer.
+    pub fn unpack(source: &[u8]) -> Result<CollateralQuote, ProgramError>
+    {
+        if source.len() < CO_RETURN_COLLATERAL_QUOTE_BYTE_LEN
+        {
+            msg!("Invalid collateral quote data. Aborting");
+            return Err(CoinbackedError::InvalidReturnData.into());
+        }
+
+        let (mint_data, payout_data, per_unit_payout_data, backing_amount_data) =
+            array_refs![array_ref![source, 0, CO_RETURN_COLLATERAL_QUOTE_BYTE_LEN], CO_PUBKEY_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN];
+
+        Ok(
+            CollateralQuote
+            {
+                mint: Pubkey::new_from_array(*mint_data),
+                payout: u64::from_le_bytes(*payout_data),
+                per_unit_payout: u64::from_le_bytes(*per_unit_payout_data),
+                backing_amount: u64::from_le_bytes(*backing_amount_data),
+            }
+        )
+    }
+}
+
+/// Payout quote returned by `QuotePayout`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutQuote
+{
+    /// payout in lamports for the quoted token amount
+    pub payout: u64,
+
+    /// payout in lamports for one token unit
+    pub per_unit_payout: u64,
+
+    /// current supply of the mint
+    pub supply: u64,
+
+    /// lamports backing the supply, excluding rent excemption
+    pub backing_lamports: u64,
+
+    /// quotes for the collateral assets, empty if backed by lamports only
+    pub collateral: Vec<CollateralQuote>,
+}
+
+impl PayoutQuote
+{
+    /// Packs the quote into a byte buffer, collateral quotes are appended after the lamport quote.
+    pub fn pack(&self, dst: &mut [u8])
+    {
+        let (dst, collateral_dst) = dst.split_at_mut(CO_RETURN_PAYOUT_QUOTE_BYTE_LEN);
+        let dst = array_mut_ref![dst, 0, CO_RETURN_PAYOUT_QUOTE_BYTE_LEN];
+        let (payout_dst, per_unit_payout_dst, supply_dst, backing_lamports_dst) =
+            mut_array_refs![dst, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+        *payout_dst = self.payout.to_le_bytes();
+        *per_unit_payout_dst = self.per_unit_payout.to_le_bytes();
+        *supply_dst = self.supply.to_le_bytes();
+        *backing_lamports_dst = self.backing_lamports.to_le_bytes();
+        pack_collateral(&self.collateral, collateral_dst);
+    }
+
+    /// Unpacks a quote from the return data of a simulated transaction.
+    pub fn unpack(source: &[u8]) -> Result<PayoutQuote, ProgramError>
+    {
+        if source.len() < CO_RETURN_PAYOUT_QUOTE_BYTE_LEN
+        {
+            msg!("Invalid payout quote data. Aborting");
+            return Err(CoinbackedError::InvalidReturnData.into());
+        }
+
+        let (payout_data, per_unit_payout_data, supply_data, backing_lamports_data) =
+            array_refs![array_ref![source, 0, CO_RETURN_PAYOUT_QUOTE_BYTE_LEN], CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+
+        Ok(
+            PayoutQuote
+            {
+                payout: u64::from_le_bytes(*payout_data),
+                per_unit_payout: u64::from_le_bytes(*per_unit_payout_data),
+                supply: u64::from_le_bytes(*supply_data),
+                backing_lamports: u64::from_le_bytes(*backing_lamports_data),
+                collateral: unpack_collateral(&source[CO_RETURN_PAYOUT_QUOTE_BYTE_LEN..])?,
+            }
+        )
+    }
+}
+
+/// Validation report returned by `ValidateBackingAccount`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationReport
+{
+    /// bitmask of passed checks, see `CO_VALIDATION_CHECK_*`
+    pub checks: u8,
+
+    /// payout in lamports for one token unit
+    pub per_unit_payout: u64,
+
+    /// mint authority is revoked, i.e. supply is fixed and payout floor cannot decrease
+    pub mint_authority_revoked: bool,
+
+    /// per unit payout of the collateral assets, empty if backed by lamports only
+    pub collateral: Vec<CollateralQuote>,
+
+    /// exit fee in basis points retained in backing on burns
+    pub exit_fee_bps: u16,
+}
+
+impl ValidationReport
+{
+    /// All checks passed
+    pub fn is_valid(&self) -> bool
+    {
+        self.checks & CO_VALIDATION_CHECK_ALL == CO_VALIDATION_CHECK_ALL
+    }
+
+    /// Packs the report into a byte buffer, collateral quotes and exit fee are appended after the report.
+    pub fn pack(&self, dst: &mut [u8])
+    {
+        let (dst, collateral_dst) = dst.split_at_mut(CO_RETURN_VALIDATION_REPORT_BYTE_LEN);
+        let (collateral_dst, exit_fee_dst) = collateral_dst.split_at_mut(CO_RETURN_BASKET_BYTE_LEN);
+        let dst = array_mut_ref![dst, 0, CO_RETURN_VALIDATION_REPORT_BYTE_LEN];
+        let (checks_dst, per_unit_payout_dst, mint_authority_revoked_dst) =
+            mut_array_refs![dst, 1, CO_LAMPORTS_BYTE_LEN, 1];
+        *checks_dst = self.checks.to_le_bytes();
+        *per_unit_payout_dst = self.per_unit_payout.to_le_bytes();
+        *mint_authority_revoked_dst = (self.mint_authority_revoked as u8).to_le_bytes();
+        pack_collateral(&self.collateral, collateral_dst);
+        *array_mut_ref![exit_fee_dst, 0, CO_RETURN_EXIT_FEE_BYTE_LEN] = self.exit_fee_bps.to_le_bytes();
+    }
+
+    /// Unpacks a report from the return data of a transaction.
+    pub fn unpack(source: &[u8]) -> Result<ValidationReport, ProgramError>
+    {
+        if source.len() < CO_RETURN_VALIDATION_REPORT_BYTE_LEN
+        {
+            msg!("Invalid validation report data. Aborting");
+            return Err(CoinbackedError::InvalidReturnData.into());
+        }
+
+        let (checks_data, per_unit_payout_data, mint_authority_revoked_data) =
+            array_refs![array_ref![source, 0, CO_RETURN_VALIDATION_REPORT_BYTE_LEN], 1, CO_LAMPORTS_BYTE_LEN, 1];
+
+        // return data of earlier versions ends before the exit fee
+        let exit_fee_offset = CO_RETURN_VALIDATION_REPORT_BYTE_LEN + CO_RETURN_BASKET_BYTE_LEN;
+        let exit_fee_bps = if source.len() >= exit_fee_offset + CO_RETURN_EXIT_FEE_BYTE_LEN
+        {
+            u16::from_le_bytes(*array_ref![source, exit_fee_offset, CO_RETURN_EXIT_FEE_BYTE_LEN])
+        }
+        else
+        {
+            0
+        };
+
+        Ok(
+            ValidationReport
+            {
+                checks: u8::from_le_bytes(*checks_data),
+                per_unit_payout: u64::from_le_bytes(*per_unit_payout_data),
+                mint_authority_revoked: u8::from_le_bytes(*mint_authority_revoked_data) != 0,
+                collateral: unpack_collateral(&source[CO_RETURN_VALIDATION_REPORT_BYTE_LEN..])?,
+                exit_fee_bps,
+            }
+        )
+    }
+}
+
+// helper functions
+
+fn pack_collateral(
+    collateral: &[CollateralQuote],
+    dst: &mut [u8]
+)
+{
+    let dst = array_mut_ref![dst, 0, CO_RETURN_BASKET_BYTE_LEN];
+    dst[0] = collateral.len() as u8;
+
+    for (collateral_quote, collateral_dst) in collateral.iter().zip(dst[1..].chunks_exact_mut(CO_RETURN_COLLATERAL_QUOTE_BYTE_LEN))
+    {
+        collateral_quote.pack(collateral_dst);
+    }
+}
+
+fn unpack_collateral(
+    source: &[u8]
+) -> Result<Vec<CollateralQuote>, ProgramError>
+{
+    // return data of lamport backed accounts may end before the collateral section
+    if source.is_empty()
+    {
+        return Ok(Vec::new());
+    }
+
+    let count = source[0] as usize;
+    if count > CO_BASKET_MAX_COLLATERAL || source.len() < 1 + count * CO_RETURN_COLLATERAL_QUOTE_BYTE_LEN
+    {
+        msg!("Invalid collateral quote data. Aborting");
+        return Err(CoinbackedError::InvalidReturnData.into());
+    }
+
+    source[1..].chunks_exact(CO_RETURN_COLLATERAL_QUOTE_BYTE_LEN)
+        .take(count)
+        .map(CollateralQuote::unpack)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn sample_collateral_quote() -> CollateralQuote
+    {
+        CollateralQuote
+        {
+            mint: Pubkey::new_unique(),
+            payout: 500,
+            per_unit_payout: 5,
+            backing_amount: 10_000,
+        }
+    }
+
+    #[test]
+    fn test_payout_quote_round_trip()
+    {
+        let quote = PayoutQuote
+        {
+            payout: 2_500_000,
+            per_unit_payout: 25_000,
+            supply: 1_000,
+            backing_lamports: 25_000_000,
+            collateral: Vec::new(),
+        };
+
+        let mut data = [0u8; CO_RETURN_PAYOUT_QUOTE_BYTE_LEN + CO_RETURN_BASKET_BYTE_LEN];
+        quote.pack(&mut data);
+        assert_eq!(PayoutQuote::unpack(&data).unwrap(), quote);
+
+        // lamport quotes may end before the collateral section
+        assert_eq!(PayoutQuote::unpack(&data[..CO_RETURN_PAYOUT_QUOTE_BYTE_LEN]).unwrap(), quote);
+    }
+
+    #[test]
+    fn test_payout_quote_with_collateral_round_trip()
+    {
+        let quote = PayoutQuote
+        {
+            payout: 0,
+            per_unit_payout: 0,
+            supply: 1_000,
+            backing_lamports: 0,
+            collateral: vec![sample_collateral_quote(), sample_collateral_quote()],
+        };
+
+        let mut data = [0u8; CO_RETURN_PAYOUT_QUOTE_BYTE_LEN + CO_RETURN_BASKET_BYTE_LEN];
+        quote.pack(&mut data);
+        assert_eq!(PayoutQuote::unpack(&data).unwrap(), quote);
+    }
+
+    #[test]
+    fn test_payout_quote_unpack_rejects_invalid_data()
+    {
+        let data = [0u8; CO_RETURN_PAYOUT_QUOTE_BYTE_LEN - 1];
+        assert!(PayoutQuote::unpack(&data).is_err());
+
+        // more collateral than a basket can hold
+        let mut data = [0u8; CO_RETURN_PAYOUT_QUOTE_BYTE_LEN + CO_RETURN_BASKET_BYTE_LEN];
+        data[CO_RETURN_PAYOUT_QUOTE_BYTE_LEN] = CO_BASKET_MAX_COLLATERAL as u8 + 1;
+        assert!(PayoutQuote::unpack(&data).is_err());
+    }
+
+    #[test]
+    fn test_validation_report_round_trip()
+    {
+        let report = ValidationReport
+        {
+            checks: CO_VALIDATION_CHECK_ALL,
+            per_unit_payout: 1_000,
+            mint_authority_revoked: true,
+            collateral: vec![sample_collateral_quote()],
+            exit_fee_bps: 250,
+        };
+
+        let mut data = [0u8; CO_RETURN_VALIDATION_REPORT_BYTE_LEN + CO_RETURN_BASKET_BYTE_LEN + CO_RETURN_EXIT_FEE_BYTE_LEN];
+        report.pack(&mut data);
+
+        let unpacked = ValidationReport::unpack(&data).unwrap();
+        assert_eq!(unpacked, report);
+        assert!(unpacked.is_valid());
+    }
+
+    #[test]
+    fn test_validation_report_of_earlier_versions()
+    {
+        let report = ValidationReport
+        {
+            checks: CO_VALIDATION_CHECK_PDA | CO_VALIDATION_CHECK_MINT,
+            per_unit_payout: 7,
+            mint_authority_revoked: false,
+            collateral: Vec::new(),
+            exit_fee_bps: 0,
+        };
+
+        let mut data = [0u8; CO_RETURN_VALIDATION_REPORT_BYTE_LEN + CO_RETURN_BASKET_BYTE_LEN + CO_RETURN_EXIT_FEE_BYTE_LEN];
+        report.pack(&mut data);
+
+        // reports without collateral section and exit fee
+        let unpacked = ValidationReport::unpack(&data[..CO_RETURN_VALIDATION_REPORT_BYTE_LEN]).unwrap();
+        assert_eq!(unpacked, report);
+        assert!(!unpacked.is_valid());
+
+        // reports without exit fee
+        let unpacked = ValidationReport::unpack(&data[..CO_RETURN_VALIDATION_REPORT_BYTE_LEN + CO_RETURN_BASKET_BYTE_LEN]).unwrap();
+        assert_eq!(unpacked, report);
+
+        assert!(ValidationReport::unpack(&data[..CO_RETURN_VALIDATION_REPORT_BYTE_LEN - 1]).is_err());
+    }
+}
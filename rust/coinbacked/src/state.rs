@@ -11,12 +11,47 @@ use arrayref::
 use crate::
 {
     utils::*,
+    instruction::CO_PAUSE_BURN,
+    error::CoinbackedError,
 };
 
+/// Lifetime statistics of a backing account, lamport totals exclude collateral
+#[derive(Debug, Default)]
+pub struct BackingStats
+{
+    /// total lamports deposited, including initial backing
+    pub total_deposited: u64,
+
+    /// total lamports paid out for burned tokens
+    pub total_paid_out: u64,
+
+    /// total token amount burned
+    pub total_burned: u64,
+
+    /// number of burns
+    pub burn_count: u64,
+
+    /// slot the backing account was created in
+    pub creation_slot: u64,
+
+    /// slot of last create, add or burn
+    pub last_activity_slot: u64,
+}
+
 /// Data for the backing account
+///
+/// Layouts, versions 0 and 1 have no header and are told apart by length:
+/// - version 0: base | collateral mints
+/// - version 1: base | statistics | collateral mints
+/// - version 2: header | base | statistics | collateral mints
+/// - version 3: header | base | statistics | exit fee | collateral mints
+/// - version 4: header | base | statistics | exit fee | creator | collateral mints
 #[derive(Debug)]
 pub struct BackingAccount 
 {
+    /// layout version, accounts below `CO_ACCOUNT_BACKING_VERSION` can be migrated
+    pub version: u8,
+
     /// pub key of the token this account is backing
     pub token_key: Pubkey,
 
@@ -25,13 +60,114 @@ pub struct BackingAccount
 
     /// account seed bump for validation
     pub bump: u8,
+
+    /// lifetime statistics, none for version 0 accounts
+    pub stats: Option<BackingStats>,
+
+    /// exit fee in basis points of payouts set by the creator, retained in backing on burns; zero before version 3
+    pub exit_fee_bps: u16,
+
+    /// creator receiving a share of protocol fees of burns and top-ups, stored as zeroes if unset; none before version 4
+    pub creator: Option<Pubkey>,
+
+    /// mints of the SPL collateral held in vaults, in basket order; empty if backed by lamports only
+    pub collateral_mints: Vec<Pubkey>,
 }
 
 impl BackingAccount
 {
+    /// size of account data for the layout of this account
+    pub fn byte_len(&self) -> usize
+    {
+        self.collateral_offset() + self.collateral_mints.len() * CO_PUBKEY_BYTE_LEN
+    }
+
+    fn header_len(&self) -> usize
+    {
+        if self.version >= CO_ACCOUNT_BACKING_VERSION_HEADER
+        {
+            CO_ACCOUNT_HEADER_BYTE_LEN
+        }
+        else
+        {
+            0
+        }
+    }
+
+    fn exit_fee_offset(&self) -> usize
+    {
+        if self.stats.is_some()
+        {
+            self.header_len() + CO_ACCOUNT_BACKING_BYTE_LEN + CO_ACCOUNT_BACKING_STATS_BYTE_LEN
+        }
+        else
+        {
+            self.header_len() + CO_ACCOUNT_BACKING_BYTE_LEN
+        }
+    }
+
+    fn creator_offset(&self) -> usize
+    {
+        if self.version >= CO_ACCOUNT_BACKING_VERSION_EXIT_FEE
+        {
+            self.exit_fee_offset() + CO_BPS_BYTE_LEN
+        }
+        else
+        {
+            self.exit_fee_offset()
+        }
+    }
+
+    fn collateral_offset(&self) -> usize
+    {
+        if self.version >= CO_ACCOUNT_BACKING_VERSION_CREATOR
+        {
+            self.creator_offset() + CO_PUBKEY_BYTE_LEN
+        }
+        else
+        {
+            self.creator_offset()
+        }
+    }
+
     pub fn pack(&self, dst: &mut [u8])
     {
-        let dst = array_mut_ref![dst, 0, CO_ACCOUNT_BACKING_BYTE_LEN];
+        let header_len = self.header_len();
+        if header_len > 0
+        {
+            pack_account_header(dst, CO_ACCOUNT_DISCRIMINATOR_BACKING, self.version);
+        }
+
+        let collateral_offset = self.collateral_offset();
+        for (position, collateral_mint) in self.collateral_mints.iter().enumerate()
+        {
+            array_mut_ref![dst, collateral_offset + position * CO_PUBKEY_BYTE_LEN, CO_PUBKEY_BYTE_LEN].copy_from_slice(collateral_mint.as_ref());
+        }
+
+        if self.version >= CO_ACCOUNT_BACKING_VERSION_EXIT_FEE
+        {
+            *array_mut_ref![dst, self.exit_fee_offset(), CO_BPS_BYTE_LEN] = self.exit_fee_bps.to_le_bytes();
+        }
+
+        if self.version >= CO_ACCOUNT_BACKING_VERSION_CREATOR
+        {
+            array_mut_ref![dst, self.creator_offset(), CO_PUBKEY_BYTE_LEN].copy_from_slice(self.creator.unwrap_or_default().as_ref());
+        }
+
+        if let Some(stats) = &self.stats
+        {
+            let stats_dst = array_mut_ref![dst, header_len + CO_ACCOUNT_BACKING_BYTE_LEN, CO_ACCOUNT_BACKING_STATS_BYTE_LEN];
+            let (total_deposited_dst, total_paid_out_dst, total_burned_dst, burn_count_dst, creation_slot_dst, last_activity_slot_dst) =
+                mut_array_refs![stats_dst, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN, CO_COUNT_BYTE_LEN, CO_SLOT_BYTE_LEN, CO_SLOT_BYTE_LEN];
+            *total_deposited_dst = stats.total_deposited.to_le_bytes();
+            *total_paid_out_dst = stats.total_paid_out.to_le_bytes();
+            *total_burned_dst = stats.total_burned.to_le_bytes();
+            *burn_count_dst = stats.burn_count.to_le_bytes();
+            *creation_slot_dst = stats.creation_slot.to_le_bytes();
+            *last_activity_slot_dst = stats.last_activity_slot.to_le_bytes();
+        }
+
+        let dst = array_mut_ref![dst, header_len, CO_ACCOUNT_BACKING_BYTE_LEN];
         let (token_key_dest, rent_excemption_dst, bump_dst) = mut_array_refs![dst, 32, CO_LAMPORTS_BYTE_LEN, 1];
         token_key_dest.copy_from_slice(self.token_key.as_ref());
         *rent_excemption_dst = self.rent_excemption.to_le_bytes();
@@ -43,37 +179,228 @@ impl BackingAccount
         if source.len() < CO_ACCOUNT_BACKING_BYTE_LEN
         {
             msg!("No backing account data found. Aborting");
-            return Err(ProgramError::InvalidAccountData);
+            return Err(CoinbackedError::InvalidBackingAccountData.into());
+        }
+
+        // layouts without header are only accepted at the exact lengths they were written with, other accounts of the program must not pass as them
+        let headerless_version = (0..=CO_BASKET_MAX_COLLATERAL).find_map(|collateral_count|
+        {
+            let collateral_len = collateral_count * CO_PUBKEY_BYTE_LEN;
+            if source.len() == CO_ACCOUNT_BACKING_BYTE_LEN + collateral_len
+            {
+                Some(0)
+            }
+            else if source.len() == CO_ACCOUNT_BACKING_BYTE_LEN + CO_ACCOUNT_BACKING_STATS_BYTE_LEN + collateral_len
+            {
+                Some(CO_ACCOUNT_BACKING_VERSION_STATS)
+            }
+            else
+            {
+                None
+            }
+        });
+
+        let (version, header_len) = if let Some(version) = headerless_version
+        {
+            (version, 0)
+        }
+        else
+        {
+            let version = unpack_account_header(source, CO_ACCOUNT_DISCRIMINATOR_BACKING, CoinbackedError::InvalidBackingAccountData)?;
+            if !(CO_ACCOUNT_BACKING_VERSION_HEADER..=CO_ACCOUNT_BACKING_VERSION).contains(&version)
+            {
+                msg!("Unsupported backing account version {}. Aborting", version);
+                return Err(CoinbackedError::InvalidBackingAccountData.into());
+            }
+
+            let min_len = if version >= CO_ACCOUNT_BACKING_VERSION_CREATOR
+            {
+                CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_BACKING_BYTE_LEN + CO_ACCOUNT_BACKING_STATS_BYTE_LEN + CO_BPS_BYTE_LEN + CO_PUBKEY_BYTE_LEN
+            }
+            else if version >= CO_ACCOUNT_BACKING_VERSION_EXIT_FEE
+            {
+                CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_BACKING_BYTE_LEN + CO_ACCOUNT_BACKING_STATS_BYTE_LEN + CO_BPS_BYTE_LEN
+            }
+            else
+            {
+                CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_BACKING_BYTE_LEN + CO_ACCOUNT_BACKING_STATS_BYTE_LEN
+            };
+            if source.len() < min_len || !(source.len() - min_len).is_multiple_of(CO_PUBKEY_BYTE_LEN)
+            {
+                msg!("Invalid backing account data length. Aborting");
+                return Err(CoinbackedError::InvalidBackingAccountData.into());
+            }
+
+            (version, CO_ACCOUNT_HEADER_BYTE_LEN)
+        };
+
+        let (token_key_data, rent_excemption_data, bump_data) = array_refs![array_ref![source, header_len, CO_ACCOUNT_BACKING_BYTE_LEN], CO_PUBKEY_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_BUMP_BYTE_LEN];
+
+        let stats = if version >= CO_ACCOUNT_BACKING_VERSION_STATS
+        {
+            let (total_deposited_data, total_paid_out_data, total_burned_data, burn_count_data, creation_slot_data, last_activity_slot_data) =
+                array_refs![array_ref![source, header_len + CO_ACCOUNT_BACKING_BYTE_LEN, CO_ACCOUNT_BACKING_STATS_BYTE_LEN], CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_TOKEN_AMOUNT_BYTE_LEN, CO_COUNT_BYTE_LEN, CO_SLOT_BYTE_LEN, CO_SLOT_BYTE_LEN];
+
+            Some(
+                BackingStats
+                {
+                    total_deposited: u64::from_le_bytes(*total_deposited_data),
+                    total_paid_out: u64::from_le_bytes(*total_paid_out_data),
+                    total_burned: u64::from_le_bytes(*total_burned_data),
+                    burn_count: u64::from_le_bytes(*burn_count_data),
+                    creation_slot: u64::from_le_bytes(*creation_slot_data),
+                    last_activity_slot: u64::from_le_bytes(*last_activity_slot_data),
+                }
+            )
+        }
+        else
+        {
+            None
+        };
+
+        let exit_fee_offset = if stats.is_some()
+        {
+            header_len + CO_ACCOUNT_BACKING_BYTE_LEN + CO_ACCOUNT_BACKING_STATS_BYTE_LEN
+        }
+        else
+        {
+            header_len + CO_ACCOUNT_BACKING_BYTE_LEN
+        };
+
+        let (exit_fee_bps, creator_offset) = if version >= CO_ACCOUNT_BACKING_VERSION_EXIT_FEE
+        {
+            (u16::from_le_bytes(*array_ref![source, exit_fee_offset, CO_BPS_BYTE_LEN]), exit_fee_offset + CO_BPS_BYTE_LEN)
+        }
+        else
+        {
+            (0, exit_fee_offset)
+        };
+
+        let (creator, collateral_offset) = if version >= CO_ACCOUNT_BACKING_VERSION_CREATOR
+        {
+            let creator = Pubkey::new_from_array(*array_ref![source, creator_offset, CO_PUBKEY_BYTE_LEN]);
+            (Some(creator).filter(|creator| creator.ne(&Pubkey::default())), creator_offset + CO_PUBKEY_BYTE_LEN)
+        }
+        else
+        {
+            (None, creator_offset)
+        };
+
+        // collateral mints only present on collateral backed accounts, appended after base layout, statistics, exit fee and creator
+        if (source.len() - collateral_offset) / CO_PUBKEY_BYTE_LEN > CO_BASKET_MAX_COLLATERAL
+        {
+            msg!("Invalid backing account data length. Aborting");
+            return Err(CoinbackedError::InvalidBackingAccountData.into());
         }
 
-        let (token_key_data, rent_excemption_data, bump_data) = array_refs![array_ref![source, 0, CO_ACCOUNT_BACKING_BYTE_LEN], CO_PUBKEY_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_BUMP_BYTE_LEN];
+        let collateral_mints = source[collateral_offset..]
+            .chunks_exact(CO_PUBKEY_BYTE_LEN)
+            .map(|collateral_mint_data| Pubkey::new_from_array(*array_ref![collateral_mint_data, 0, CO_PUBKEY_BYTE_LEN]))
+            .collect();
         
         Ok(
             BackingAccount
             {
-                token_key: Pubkey::new(token_key_data), 
+                version,
+                token_key: Pubkey::new_from_array(*token_key_data), 
                 rent_excemption: u64::from_le_bytes(*rent_excemption_data), 
-                bump: u8::from_le_bytes(*bump_data)
+                bump: u8::from_le_bytes(*bump_data),
+                stats,
+                exit_fee_bps,
+                creator,
+                collateral_mints
             }
         )
     }
 }
 
+/// Data for the protocol treasury account
+///
+/// Layouts, version 0 has no header and is told apart by length:
+/// - version 0: base
+/// - version 1: header | base
+/// - version 2: header | base | withdrawal limits
 #[derive(Debug)]
 pub struct TreasuryAccount
 {
+    /// layout version
+    pub version: u8,
+
     /// initial rent excemption, will just be used to figure out if/when account is empty in cases of rent excemption chanhing over time
     pub rent_excemption: u64,
 
     /// account seed bump for validation
     pub bump: u8,
+
+    /// lamports of admin transfer queued for execution after the delay, zero if none is queued
+    pub queued_lamports: u64,
+
+    /// receiver of the queued admin transfer
+    pub queued_recipient: Pubkey,
+
+    /// slot the admin transfer was queued in
+    pub queued_slot: u64,
+
+    /// epoch the withdrawn lamports are counted for
+    pub epoch: u64,
+
+    /// lamports withdrawn in epoch
+    pub epoch_withdrawn: u64,
 }
 
 impl TreasuryAccount
 {
+    /// size of account data for the layout of this account
+    pub fn byte_len(&self) -> usize
+    {
+        self.header_len() + CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN + self.limits_len()
+    }
+
+    fn header_len(&self) -> usize
+    {
+        if self.version >= CO_ACCOUNT_PROTOCOL_TREASURY_VERSION_HEADER
+        {
+            CO_ACCOUNT_HEADER_BYTE_LEN
+        }
+        else
+        {
+            0
+        }
+    }
+
+    fn limits_len(&self) -> usize
+    {
+        if self.version >= CO_ACCOUNT_PROTOCOL_TREASURY_VERSION_LIMITS
+        {
+            CO_ACCOUNT_PROTOCOL_TREASURY_LIMITS_BYTE_LEN
+        }
+        else
+        {
+            0
+        }
+    }
+
     pub fn pack(&self, dst: &mut [u8])
     {
-        let dst = array_mut_ref![dst, 0, CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN];
+        let header_len = self.header_len();
+        if header_len > 0
+        {
+            pack_account_header(dst, CO_ACCOUNT_DISCRIMINATOR_TREASURY, self.version);
+        }
+
+        if self.limits_len() > 0
+        {
+            let limits_dst = array_mut_ref![dst, header_len + CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN, CO_ACCOUNT_PROTOCOL_TREASURY_LIMITS_BYTE_LEN];
+            let (queued_lamports_dst, queued_recipient_dst, queued_slot_dst, epoch_dst, epoch_withdrawn_dst) =
+                mut_array_refs![limits_dst, CO_LAMPORTS_BYTE_LEN, CO_PUBKEY_BYTE_LEN, CO_SLOT_BYTE_LEN, CO_COUNT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+            *queued_lamports_dst = self.queued_lamports.to_le_bytes();
+            queued_recipient_dst.copy_from_slice(self.queued_recipient.as_ref());
+            *queued_slot_dst = self.queued_slot.to_le_bytes();
+            *epoch_dst = self.epoch.to_le_bytes();
+            *epoch_withdrawn_dst = self.epoch_withdrawn.to_le_bytes();
+        }
+
+        let dst = array_mut_ref![dst, header_len, CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN];
         let (rent_excemption_dst, bump_dst) = mut_array_refs![dst, CO_LAMPORTS_BYTE_LEN, 1];
         *rent_excemption_dst = self.rent_excemption.to_le_bytes();
         *bump_dst = self.bump.to_le_bytes();
@@ -81,20 +408,1370 @@ impl TreasuryAccount
 
     pub fn unpack(source: &[u8]) -> Result<TreasuryAccount, ProgramError>
     {
-        if source.len() < CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN
+        // version 0 has no header and exactly the base length
+        let (version, header_len) = if source.len() == CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN
+        {
+            (0, 0)
+        }
+        else
+        {
+            let version = unpack_account_header(source, CO_ACCOUNT_DISCRIMINATOR_TREASURY, CoinbackedError::InvalidTreasuryAccountData)?;
+            if !(CO_ACCOUNT_PROTOCOL_TREASURY_VERSION_HEADER..=CO_ACCOUNT_PROTOCOL_TREASURY_VERSION).contains(&version)
+            {
+                msg!("Unsupported treasury account version {}. Aborting", version);
+                return Err(CoinbackedError::InvalidTreasuryAccountData.into());
+            }
+
+            (version, CO_ACCOUNT_HEADER_BYTE_LEN)
+        };
+
+        let has_limits = version >= CO_ACCOUNT_PROTOCOL_TREASURY_VERSION_LIMITS;
+        let limits_len = if has_limits { CO_ACCOUNT_PROTOCOL_TREASURY_LIMITS_BYTE_LEN } else { 0 };
+        if source.len() != header_len + CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN + limits_len
         {
             msg!("No or invalid treasure account data found. Aborting");
-            return Err(ProgramError::InvalidAccountData);
+            return Err(CoinbackedError::InvalidTreasuryAccountData.into());
+        }
+
+        let (rent_excemption_data, bump_data) = array_refs![array_ref![source, header_len, CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN], CO_LAMPORTS_BYTE_LEN, CO_BUMP_BYTE_LEN];
+
+        // older layouts have nothing queued and nothing counted
+        let mut treasure = TreasuryAccount
+        {
+            version,
+            rent_excemption: u64::from_le_bytes(*rent_excemption_data),
+            bump: u8::from_le_bytes(*bump_data),
+            queued_lamports: 0,
+            queued_recipient: Pubkey::default(),
+            queued_slot: 0,
+            epoch: 0,
+            epoch_withdrawn: 0,
+        };
+
+        if has_limits
+        {
+            let (queued_lamports_data, queued_recipient_data, queued_slot_data, epoch_data, epoch_withdrawn_data) =
+                array_refs![array_ref![source, header_len + CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN, CO_ACCOUNT_PROTOCOL_TREASURY_LIMITS_BYTE_LEN], CO_LAMPORTS_BYTE_LEN, CO_PUBKEY_BYTE_LEN, CO_SLOT_BYTE_LEN, CO_COUNT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+
+            treasure.queued_lamports = u64::from_le_bytes(*queued_lamports_data);
+            treasure.queued_recipient = Pubkey::new_from_array(*queued_recipient_data);
+            treasure.queued_slot = u64::from_le_bytes(*queued_slot_data);
+            treasure.epoch = u64::from_le_bytes(*epoch_data);
+            treasure.epoch_withdrawn = u64::from_le_bytes(*epoch_withdrawn_data);
+        }
+
+        Ok(treasure)
+    }
+}
+
+/// Data for the record of a contributor backing a token
+///
+/// Layouts, version 0 has no header and is told apart by length:
+/// - version 0: base
+/// - version 1: header | base
+#[derive(Debug)]
+pub struct BackerRecord
+{
+    /// layout version
+    pub version: u8,
+
+    /// pub key of the token backed
+    pub token_key: Pubkey,
+
+    /// pub key of the contributor
+    pub contributor: Pubkey,
+
+    /// total lamports contributed by contributor, excluding rent and protocol fees
+    pub total_lamports: u64,
+
+    /// number of contributions
+    pub contribution_count: u64,
+
+    /// slot of first contribution
+    pub first_slot: u64,
+
+    /// slot of last contribution
+    pub last_slot: u64,
+
+    /// account seed bump for validation
+    pub bump: u8,
+}
+
+impl BackerRecord
+{
+    /// size of account data for the layout of this record
+    pub fn byte_len(&self) -> usize
+    {
+        self.header_len() + CO_ACCOUNT_BACKER_RECORD_BYTE_LEN
+    }
+
+    fn header_len(&self) -> usize
+    {
+        if self.version >= CO_ACCOUNT_BACKER_RECORD_VERSION_HEADER
+        {
+            CO_ACCOUNT_HEADER_BYTE_LEN
+        }
+        else
+        {
+            0
+        }
+    }
+
+    pub fn pack(&self, dst: &mut [u8])
+    {
+        let header_len = self.header_len();
+        if header_len > 0
+        {
+            pack_account_header(dst, CO_ACCOUNT_DISCRIMINATOR_BACKER_RECORD, self.version);
+        }
+
+        let dst = array_mut_ref![dst, header_len, CO_ACCOUNT_BACKER_RECORD_BYTE_LEN];
+        let (token_key_dst, contributor_dst, total_lamports_dst, contribution_count_dst, first_slot_dst, last_slot_dst, bump_dst) =
+            mut_array_refs![dst, CO_PUBKEY_BYTE_LEN, CO_PUBKEY_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_COUNT_BYTE_LEN, CO_SLOT_BYTE_LEN, CO_SLOT_BYTE_LEN, CO_BUMP_BYTE_LEN];
+        token_key_dst.copy_from_slice(self.token_key.as_ref());
+        contributor_dst.copy_from_slice(self.contributor.as_ref());
+        *total_lamports_dst = self.total_lamports.to_le_bytes();
+        *contribution_count_dst = self.contribution_count.to_le_bytes();
+        *first_slot_dst = self.first_slot.to_le_bytes();
+        *last_slot_dst = self.last_slot.to_le_bytes();
+        *bump_dst = self.bump.to_le_bytes();
+    }
+
+    pub fn unpack(source: &[u8]) -> Result<BackerRecord, ProgramError>
+    {
+        // version 0 has no header and exactly the base length
+        let (version, header_len) = if source.len() == CO_ACCOUNT_BACKER_RECORD_BYTE_LEN
+        {
+            (0, 0)
         }
+        else
+        {
+            if source.len() != CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_BACKER_RECORD_BYTE_LEN
+            {
+                msg!("No or invalid backer record data found. Aborting");
+                return Err(CoinbackedError::InvalidBackerRecordData.into());
+            }
+
+            let version = unpack_account_header(source, CO_ACCOUNT_DISCRIMINATOR_BACKER_RECORD, CoinbackedError::InvalidBackerRecordData)?;
+            if !(CO_ACCOUNT_BACKER_RECORD_VERSION_HEADER..=CO_ACCOUNT_BACKER_RECORD_VERSION).contains(&version)
+            {
+                msg!("Unsupported backer record version {}. Aborting", version);
+                return Err(CoinbackedError::InvalidBackerRecordData.into());
+            }
+
+            (version, CO_ACCOUNT_HEADER_BYTE_LEN)
+        };
 
-        let (rent_excemption_data, bump_data) = array_refs![array_ref![source, 0, CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN], CO_LAMPORTS_BYTE_LEN, CO_BUMP_BYTE_LEN];
+        let (token_key_data, contributor_data, total_lamports_data, contribution_count_data, first_slot_data, last_slot_data, bump_data) =
+            array_refs![array_ref![source, header_len, CO_ACCOUNT_BACKER_RECORD_BYTE_LEN], CO_PUBKEY_BYTE_LEN, CO_PUBKEY_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_COUNT_BYTE_LEN, CO_SLOT_BYTE_LEN, CO_SLOT_BYTE_LEN, CO_BUMP_BYTE_LEN];
 
         Ok(
-            TreasuryAccount
+            BackerRecord
             {
-                rent_excemption: u64::from_le_bytes(*rent_excemption_data),
+                version,
+                token_key: Pubkey::new_from_array(*token_key_data),
+                contributor: Pubkey::new_from_array(*contributor_data),
+                total_lamports: u64::from_le_bytes(*total_lamports_data),
+                contribution_count: u64::from_le_bytes(*contribution_count_data),
+                first_slot: u64::from_le_bytes(*first_slot_data),
+                last_slot: u64::from_le_bytes(*last_slot_data),
                 bump: u8::from_le_bytes(*bump_data)
             }
         )
     }
-}
\ No newline at end of file
+}
+
+/// Data for the protocol config account, fees are in lamports
+///
+/// Layouts:
+/// - version 1: header | base
+/// - version 2: header | base | fee rates
+/// - version 3: header | base | fee rates | pending admin
+/// - version 4: header | base | fee rates | pending admin | treasury signers
+/// - version 5: header | base | fee rates | pending admin | treasury signers | treasury limits
+/// - version 6: header | base | fee rates | pending admin | treasury signers | treasury limits | pause
+/// - version 7: header | base | fee rates | pending admin | treasury signers | treasury limits | pause | creator fee share
+/// - version 8: header | base | fee rates | pending admin | treasury signers | treasury limits | pause | creator fee share | referrer fee share
+/// - version 9: header | base | fee rates | pending admin | treasury signers | treasury limits | pause | creator fee share | referrer fee share | pending treasury limits
+#[derive(Debug)]
+pub struct ConfigAccount
+{
+    /// layout version
+    pub version: u8,
+
+    /// admin allowed to update the config
+    pub admin: Pubkey,
+
+    /// protocol fee for creating a backing account
+    pub create_fee: u64,
+
+    /// protocol fee for validating a backing account
+    pub validate_fee: u64,
+
+    /// protocol fee for adding to the balance of a backing account, minimum if a fee rate is set
+    pub add_fee: u64,
+
+    /// protocol fee for burning, taken from the payout, minimum if a fee rate is set
+    pub burn_fee: u64,
+
+    /// minimum lamports to create a lamport backed account with
+    pub min_backing_lamports: u64,
+
+    /// account seed bump for validation
+    pub bump: u8,
+
+    /// fee rate in basis points of the lamport payout of burns, zero for flat fee only
+    pub burn_fee_bps: u16,
+
+    /// maximum fee for burns if a fee rate is set, zero for no maximum
+    pub burn_fee_max: u64,
+
+    /// fee rate in basis points of the lamports added to backing, zero for flat fee only
+    pub add_fee_bps: u16,
+
+    /// maximum fee for adding if a fee rate is set, zero for no maximum
+    pub add_fee_max: u64,
+
+    /// admin proposed by the current admin, takes over once accepted
+    pub pending_admin: Option<Pubkey>,
+
+    /// approvals required for treasury withdrawals, zero if admin withdraws alone
+    pub treasury_threshold: u8,
+
+    /// signers allowed to propose and approve treasury withdrawals
+    pub treasury_signers: Vec<Pubkey>,
+
+    /// slots between queueing or proposing a treasury withdrawal and its execution, zero for none
+    pub treasury_delay_slots: u64,
+
+    /// maximum lamports withdrawn from treasury per epoch, zero for no maximum
+    pub treasury_epoch_cap: u64,
+
+    /// paused operations, combination of `CO_PAUSE_*` flags
+    pub pause_flags: u8,
+
+    /// slots a burn pause lasts at most, burns succeed afterwards regardless of flags
+    pub burn_pause_max_slots: u64,
+
+    /// slot the current or last burn pause started in
+    pub burn_paused_slot: u64,
+
+    /// share of burn and top-up protocol fees in basis points routed to the creator of the backing account
+    pub creator_fee_share_bps: u16,
+
+    /// share of create, top-up and burn protocol fees in basis points routed to the referrer passed with the operation
+    pub referrer_fee_share_bps: u16,
+
+    /// looser withdrawal delay waiting for the current delay to pass
+    pub pending_treasury_delay_slots: u64,
+
+    /// looser epoch cap waiting for the current delay to pass
+    pub pending_treasury_epoch_cap: u64,
+
+    /// slot the looser treasury limits were queued in, zero if none are queued
+    pub pending_treasury_limits_slot: u64,
+}
+
+impl ConfigAccount
+{
+    /// config applying while no config account is initialised, flat default fee on every operation
+    pub fn with_default_fees() -> ConfigAccount
+    {
+        ConfigAccount
+        {
+            version: CO_ACCOUNT_CONFIG_VERSION,
+            admin: Pubkey::default(),
+            create_fee: CO_PROTOCOL_FEE,
+            validate_fee: CO_PROTOCOL_FEE,
+            add_fee: CO_PROTOCOL_FEE,
+            burn_fee: CO_PROTOCOL_FEE,
+            min_backing_lamports: 0,
+            bump: 0,
+            burn_fee_bps: 0,
+            burn_fee_max: 0,
+            add_fee_bps: 0,
+            add_fee_max: 0,
+            pending_admin: None,
+            treasury_threshold: 0,
+            treasury_signers: Vec::new(),
+            treasury_delay_slots: 0,
+            treasury_epoch_cap: 0,
+            pause_flags: 0,
+            burn_pause_max_slots: 0,
+            burn_paused_slot: 0,
+            creator_fee_share_bps: 0,
+            referrer_fee_share_bps: 0,
+            pending_treasury_delay_slots: 0,
+            pending_treasury_epoch_cap: 0,
+            pending_treasury_limits_slot: 0,
+        }
+    }
+
+    /// size of account data for the layout of this account
+    pub fn byte_len(&self) -> usize
+    {
+        Self::byte_len_of_version(self.version).unwrap_or(0)
+    }
+
+    fn byte_len_of_version(version: u8) -> Option<usize>
+    {
+        match version
+        {
+            CO_ACCOUNT_CONFIG_VERSION_FLAT_FEES => Some(CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_CONFIG_BYTE_LEN),
+            CO_ACCOUNT_CONFIG_VERSION_FEE_RATES => Some(CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_CONFIG_BYTE_LEN + CO_ACCOUNT_CONFIG_FEE_RATES_BYTE_LEN),
+            CO_ACCOUNT_CONFIG_VERSION_PENDING_ADMIN => Some(Self::treasury_signers_offset()),
+            CO_ACCOUNT_CONFIG_VERSION_TREASURY_SIGNERS => Some(Self::treasury_limits_offset()),
+            CO_ACCOUNT_CONFIG_VERSION_TREASURY_LIMITS => Some(Self::pause_offset()),
+            CO_ACCOUNT_CONFIG_VERSION_PAUSE => Some(Self::creator_fee_share_offset()),
+            CO_ACCOUNT_CONFIG_VERSION_CREATOR_FEE_SHARE => Some(Self::referrer_fee_share_offset()),
+            CO_ACCOUNT_CONFIG_VERSION_REFERRER_FEE_SHARE => Some(Self::pending_treasury_limits_offset()),
+            CO_ACCOUNT_CONFIG_VERSION_PENDING_TREASURY_LIMITS => Some(Self::pending_treasury_limits_offset() + CO_PENDING_TREASURY_LIMITS_BYTE_LEN),
+            _ => None,
+        }
+    }
+
+    fn pending_admin_offset() -> usize
+    {
+        CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_CONFIG_BYTE_LEN + CO_ACCOUNT_CONFIG_FEE_RATES_BYTE_LEN
+    }
+
+    fn treasury_signers_offset() -> usize
+    {
+        Self::pending_admin_offset() + CO_PUBKEY_BYTE_LEN
+    }
+
+    fn treasury_limits_offset() -> usize
+    {
+        Self::treasury_signers_offset() + CO_TREASURY_SIGNERS_BYTE_LEN
+    }
+
+    fn pause_offset() -> usize
+    {
+        Self::treasury_limits_offset() + CO_TREASURY_LIMITS_BYTE_LEN
+    }
+
+    fn creator_fee_share_offset() -> usize
+    {
+        Self::pause_offset() + CO_PAUSE_BYTE_LEN
+    }
+
+    fn referrer_fee_share_offset() -> usize
+    {
+        Self::creator_fee_share_offset() + CO_BPS_BYTE_LEN
+    }
+
+    fn pending_treasury_limits_offset() -> usize
+    {
+        Self::referrer_fee_share_offset() + CO_BPS_BYTE_LEN
+    }
+
+    /// whether operation is paused at slot, burn pauses lapse after their window
+    pub fn is_paused(&self, flag: u8, slot: u64) -> bool
+    {
+        if self.pause_flags & flag == 0
+        {
+            return false;
+        }
+
+        flag != CO_PAUSE_BURN || slot < self.burn_paused_slot.saturating_add(self.burn_pause_max_slots)
+    }
+
+    /// first slot burns can be paused again, leaves burns open for at least a window after the last pause
+    pub fn burn_pause_cooldown_end(&self) -> u64
+    {
+        if self.burn_paused_slot == 0
+        {
+            return 0;
+        }
+
+        self.burn_paused_slot.saturating_add(self.burn_pause_max_slots.saturating_mul(2))
+    }
+
+    /// whether new treasury limits are looser than the current ones in delay or epoch cap, zero cap meaning no cap
+    pub fn loosens_treasury_limits(&self, delay_slots: u64, epoch_cap: u64) -> bool
+    {
+        let looser_cap = self.treasury_epoch_cap > 0 && (epoch_cap == 0 || epoch_cap > self.treasury_epoch_cap);
+
+        delay_slots < self.treasury_delay_slots || looser_cap
+    }
+
+    /// index of key in treasury signers, if it is one
+    pub fn treasury_signer_index(&self, key: &Pubkey) -> Option<usize>
+    {
+        self.treasury_signers.iter().position(|signer| signer.eq(key))
+    }
+
+    pub fn pack(&self, dst: &mut [u8])
+    {
+        pack_account_header(dst, CO_ACCOUNT_DISCRIMINATOR_CONFIG, self.version);
+
+        if self.version >= CO_ACCOUNT_CONFIG_VERSION_FEE_RATES
+        {
+            let fee_rates_dst = array_mut_ref![dst, CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_CONFIG_BYTE_LEN, CO_ACCOUNT_CONFIG_FEE_RATES_BYTE_LEN];
+            let (burn_fee_bps_dst, burn_fee_max_dst, add_fee_bps_dst, add_fee_max_dst) =
+                mut_array_refs![fee_rates_dst, CO_BPS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_BPS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+            *burn_fee_bps_dst = self.burn_fee_bps.to_le_bytes();
+            *burn_fee_max_dst = self.burn_fee_max.to_le_bytes();
+            *add_fee_bps_dst = self.add_fee_bps.to_le_bytes();
+            *add_fee_max_dst = self.add_fee_max.to_le_bytes();
+        }
+
+        // unset pending admin is stored as zeroes
+        if self.version >= CO_ACCOUNT_CONFIG_VERSION_PENDING_ADMIN
+        {
+            let pending_admin_dst = array_mut_ref![dst, Self::pending_admin_offset(), CO_PUBKEY_BYTE_LEN];
+            pending_admin_dst.copy_from_slice(self.pending_admin.unwrap_or_default().as_ref());
+        }
+
+        if self.version >= CO_ACCOUNT_CONFIG_VERSION_TREASURY_SIGNERS
+        {
+            let treasury_signers_dst = array_mut_ref![dst, Self::treasury_signers_offset(), CO_TREASURY_SIGNERS_BYTE_LEN];
+            let (threshold_dst, count_dst, signers_dst) = mut_array_refs![treasury_signers_dst, 1, 1, CO_TREASURY_MAX_SIGNERS * CO_PUBKEY_BYTE_LEN];
+            threshold_dst[0] = self.treasury_threshold;
+            count_dst[0] = self.treasury_signers.len() as u8;
+            for (signer, signer_dst) in self.treasury_signers.iter().zip(signers_dst.chunks_exact_mut(CO_PUBKEY_BYTE_LEN))
+            {
+                signer_dst.copy_from_slice(signer.as_ref());
+            }
+        }
+
+        if self.version >= CO_ACCOUNT_CONFIG_VERSION_TREASURY_LIMITS
+        {
+            let treasury_limits_dst = array_mut_ref![dst, Self::treasury_limits_offset(), CO_TREASURY_LIMITS_BYTE_LEN];
+            let (delay_slots_dst, epoch_cap_dst) = mut_array_refs![treasury_limits_dst, CO_SLOT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+            *delay_slots_dst = self.treasury_delay_slots.to_le_bytes();
+            *epoch_cap_dst = self.treasury_epoch_cap.to_le_bytes();
+        }
+
+        if self.version >= CO_ACCOUNT_CONFIG_VERSION_PAUSE
+        {
+            let pause_dst = array_mut_ref![dst, Self::pause_offset(), CO_PAUSE_BYTE_LEN];
+            let (pause_flags_dst, burn_pause_max_slots_dst, burn_paused_slot_dst) = mut_array_refs![pause_dst, 1, CO_SLOT_BYTE_LEN, CO_SLOT_BYTE_LEN];
+            pause_flags_dst[0] = self.pause_flags;
+            *burn_pause_max_slots_dst = self.burn_pause_max_slots.to_le_bytes();
+            *burn_paused_slot_dst = self.burn_paused_slot.to_le_bytes();
+        }
+
+        if self.version >= CO_ACCOUNT_CONFIG_VERSION_CREATOR_FEE_SHARE
+        {
+            *array_mut_ref![dst, Self::creator_fee_share_offset(), CO_BPS_BYTE_LEN] = self.creator_fee_share_bps.to_le_bytes();
+        }
+
+        if self.version >= CO_ACCOUNT_CONFIG_VERSION_REFERRER_FEE_SHARE
+        {
+            *array_mut_ref![dst, Self::referrer_fee_share_offset(), CO_BPS_BYTE_LEN] = self.referrer_fee_share_bps.to_le_bytes();
+        }
+
+        if self.version >= CO_ACCOUNT_CONFIG_VERSION_PENDING_TREASURY_LIMITS
+        {
+            let pending_limits_dst = array_mut_ref![dst, Self::pending_treasury_limits_offset(), CO_PENDING_TREASURY_LIMITS_BYTE_LEN];
+            let (delay_slots_dst, epoch_cap_dst, queued_slot_dst) = mut_array_refs![pending_limits_dst, CO_SLOT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_SLOT_BYTE_LEN];
+            *delay_slots_dst = self.pending_treasury_delay_slots.to_le_bytes();
+            *epoch_cap_dst = self.pending_treasury_epoch_cap.to_le_bytes();
+            *queued_slot_dst = self.pending_treasury_limits_slot.to_le_bytes();
+        }
+
+        let dst = array_mut_ref![dst, CO_ACCOUNT_HEADER_BYTE_LEN, CO_ACCOUNT_CONFIG_BYTE_LEN];
+        let (admin_dst, create_fee_dst, validate_fee_dst, add_fee_dst, burn_fee_dst, min_backing_lamports_dst, bump_dst) =
+            mut_array_refs![dst, CO_PUBKEY_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_BUMP_BYTE_LEN];
+        admin_dst.copy_from_slice(self.admin.as_ref());
+        *create_fee_dst = self.create_fee.to_le_bytes();
+        *validate_fee_dst = self.validate_fee.to_le_bytes();
+        *add_fee_dst = self.add_fee.to_le_bytes();
+        *burn_fee_dst = self.burn_fee.to_le_bytes();
+        *min_backing_lamports_dst = self.min_backing_lamports.to_le_bytes();
+        *bump_dst = self.bump.to_le_bytes();
+    }
+
+    pub fn unpack(source: &[u8]) -> Result<ConfigAccount, ProgramError>
+    {
+        let version = unpack_account_header(source, CO_ACCOUNT_DISCRIMINATOR_CONFIG, CoinbackedError::InvalidConfigAccountData)?;
+
+        let byte_len = match Self::byte_len_of_version(version)
+        {
+            Some(byte_len) => byte_len,
+            None =>
+            {
+                msg!("Unsupported config account version {}. Aborting", version);
+                return Err(CoinbackedError::InvalidConfigAccountData.into());
+            }
+        };
+
+        if source.len() != byte_len
+        {
+            msg!("No or invalid config account data found. Aborting");
+            return Err(CoinbackedError::InvalidConfigAccountData.into());
+        }
+
+        let (admin_data, create_fee_data, validate_fee_data, add_fee_data, burn_fee_data, min_backing_lamports_data, bump_data) =
+            array_refs![array_ref![source, CO_ACCOUNT_HEADER_BYTE_LEN, CO_ACCOUNT_CONFIG_BYTE_LEN], CO_PUBKEY_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_BUMP_BYTE_LEN];
+
+        // version 1 accounts charge flat fees only
+        let (burn_fee_bps, burn_fee_max, add_fee_bps, add_fee_max) = if version >= CO_ACCOUNT_CONFIG_VERSION_FEE_RATES
+        {
+            let (burn_fee_bps_data, burn_fee_max_data, add_fee_bps_data, add_fee_max_data) =
+                array_refs![array_ref![source, CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_CONFIG_BYTE_LEN, CO_ACCOUNT_CONFIG_FEE_RATES_BYTE_LEN], CO_BPS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_BPS_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+
+            (
+                u16::from_le_bytes(*burn_fee_bps_data),
+                u64::from_le_bytes(*burn_fee_max_data),
+                u16::from_le_bytes(*add_fee_bps_data),
+                u64::from_le_bytes(*add_fee_max_data)
+            )
+        }
+        else
+        {
+            (0, 0, 0, 0)
+        };
+
+        // older layouts have no handover in progress
+        let pending_admin = if version >= CO_ACCOUNT_CONFIG_VERSION_PENDING_ADMIN
+        {
+            Some(Pubkey::new_from_array(*array_ref![source, Self::pending_admin_offset(), CO_PUBKEY_BYTE_LEN]))
+                .filter(|pending_admin| pending_admin.ne(&Pubkey::default()))
+        }
+        else
+        {
+            None
+        };
+
+        // older layouts have no treasury signers, admin withdraws alone
+        let (treasury_threshold, treasury_signers) = if version >= CO_ACCOUNT_CONFIG_VERSION_TREASURY_SIGNERS
+        {
+            let (threshold_data, count_data, signers_data) =
+                array_refs![array_ref![source, Self::treasury_signers_offset(), CO_TREASURY_SIGNERS_BYTE_LEN], 1, 1, CO_TREASURY_MAX_SIGNERS * CO_PUBKEY_BYTE_LEN];
+
+            let count = count_data[0] as usize;
+            if count > CO_TREASURY_MAX_SIGNERS || threshold_data[0] as usize > count
+            {
+                msg!("Invalid treasury signers in config account. Aborting");
+                return Err(CoinbackedError::InvalidConfigAccountData.into());
+            }
+
+            (
+                threshold_data[0],
+                signers_data.chunks_exact(CO_PUBKEY_BYTE_LEN).take(count).map(|signer_data| Pubkey::new_from_array(*array_ref![signer_data, 0, CO_PUBKEY_BYTE_LEN])).collect()
+            )
+        }
+        else
+        {
+            (0, Vec::new())
+        };
+
+        // older layouts withdraw without delay or cap
+        let (treasury_delay_slots, treasury_epoch_cap) = if version >= CO_ACCOUNT_CONFIG_VERSION_TREASURY_LIMITS
+        {
+            let (delay_slots_data, epoch_cap_data) =
+                array_refs![array_ref![source, Self::treasury_limits_offset(), CO_TREASURY_LIMITS_BYTE_LEN], CO_SLOT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN];
+
+            (u64::from_le_bytes(*delay_slots_data), u64::from_le_bytes(*epoch_cap_data))
+        }
+        else
+        {
+            (0, 0)
+        };
+
+        // older layouts are never paused
+        let (pause_flags, burn_pause_max_slots, burn_paused_slot) = if version >= CO_ACCOUNT_CONFIG_VERSION_PAUSE
+        {
+            let (pause_flags_data, burn_pause_max_slots_data, burn_paused_slot_data) =
+                array_refs![array_ref![source, Self::pause_offset(), CO_PAUSE_BYTE_LEN], 1, CO_SLOT_BYTE_LEN, CO_SLOT_BYTE_LEN];
+
+            (pause_flags_data[0], u64::from_le_bytes(*burn_pause_max_slots_data), u64::from_le_bytes(*burn_paused_slot_data))
+        }
+        else
+        {
+            (0, 0, 0)
+        };
+
+        // older layouts route all protocol fees to treasury
+        let creator_fee_share_bps = if version >= CO_ACCOUNT_CONFIG_VERSION_CREATOR_FEE_SHARE
+        {
+            u16::from_le_bytes(*array_ref![source, Self::creator_fee_share_offset(), CO_BPS_BYTE_LEN])
+        }
+        else
+        {
+            0
+        };
+
+        let referrer_fee_share_bps = if version >= CO_ACCOUNT_CONFIG_VERSION_REFERRER_FEE_SHARE
+        {
+            u16::from_le_bytes(*array_ref![source, Self::referrer_fee_share_offset(), CO_BPS_BYTE_LEN])
+        }
+        else
+        {
+            0
+        };
+
+        // older layouts have no looser limits queued
+        let (pending_treasury_delay_slots, pending_treasury_epoch_cap, pending_treasury_limits_slot) = if version >= CO_ACCOUNT_CONFIG_VERSION_PENDING_TREASURY_LIMITS
+        {
+            let (delay_slots_data, epoch_cap_data, queued_slot_data) =
+                array_refs![array_ref![source, Self::pending_treasury_limits_offset(), CO_PENDING_TREASURY_LIMITS_BYTE_LEN], CO_SLOT_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_SLOT_BYTE_LEN];
+
+            (u64::from_le_bytes(*delay_slots_data), u64::from_le_bytes(*epoch_cap_data), u64::from_le_bytes(*queued_slot_data))
+        }
+        else
+        {
+            (0, 0, 0)
+        };
+
+        Ok(
+            ConfigAccount
+            {
+                version,
+                admin: Pubkey::new_from_array(*admin_data),
+                create_fee: u64::from_le_bytes(*create_fee_data),
+                validate_fee: u64::from_le_bytes(*validate_fee_data),
+                add_fee: u64::from_le_bytes(*add_fee_data),
+                burn_fee: u64::from_le_bytes(*burn_fee_data),
+                min_backing_lamports: u64::from_le_bytes(*min_backing_lamports_data),
+                bump: u8::from_le_bytes(*bump_data),
+                burn_fee_bps,
+                burn_fee_max,
+                add_fee_bps,
+                add_fee_max,
+                pending_admin,
+                treasury_threshold,
+                treasury_signers,
+                treasury_delay_slots,
+                treasury_epoch_cap,
+                pause_flags,
+                burn_pause_max_slots,
+                burn_paused_slot,
+                creator_fee_share_bps,
+                referrer_fee_share_bps,
+                pending_treasury_delay_slots,
+                pending_treasury_epoch_cap,
+                pending_treasury_limits_slot,
+            }
+        )
+    }
+}
+
+/// Data for a treasury withdrawal proposal, closed once executed
+///
+/// Layouts:
+/// - version 1: header | base
+/// - version 2: header | base | creation slot
+#[derive(Debug)]
+pub struct WithdrawalProposal
+{
+    /// layout version
+    pub version: u8,
+
+    /// id chosen by proposer, part of the account seeds
+    pub proposal_id: u64,
+
+    /// treasury signer that proposed the withdrawal, receives the rent once executed
+    pub proposer: Pubkey,
+
+    /// receiver of the withdrawal
+    pub recipient: Pubkey,
+
+    /// amount to withdraw
+    pub lamports: u64,
+
+    /// last slot the withdrawal can be approved and executed in
+    pub expiry_slot: u64,
+
+    /// treasury signers that approved, proposer included
+    pub approvals: Vec<Pubkey>,
+
+    /// account seed bump for validation
+    pub bump: u8,
+
+    /// slot the proposal was created in, delay of treasury withdrawals counts from here
+    pub created_slot: u64,
+}
+
+impl WithdrawalProposal
+{
+    /// size of account data for the layout of this proposal
+    pub fn byte_len(&self) -> usize
+    {
+        Self::byte_len_of_version(self.version).unwrap_or(0)
+    }
+
+    fn byte_len_of_version(version: u8) -> Option<usize>
+    {
+        match version
+        {
+            CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION_BASE => Some(CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_WITHDRAWAL_PROPOSAL_BYTE_LEN),
+            CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION_CREATED_SLOT => Some(CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_WITHDRAWAL_PROPOSAL_BYTE_LEN + CO_SLOT_BYTE_LEN),
+            _ => None,
+        }
+    }
+
+    pub fn pack(&self, dst: &mut [u8])
+    {
+        pack_account_header(dst, CO_ACCOUNT_DISCRIMINATOR_WITHDRAWAL_PROPOSAL, self.version);
+
+        let (dst, dst_created_slot) = dst[CO_ACCOUNT_HEADER_BYTE_LEN..].split_at_mut(CO_ACCOUNT_WITHDRAWAL_PROPOSAL_BYTE_LEN);
+        let dst = array_mut_ref![dst, 0, CO_ACCOUNT_WITHDRAWAL_PROPOSAL_BYTE_LEN];
+        let (proposal_id_dst, proposer_dst, recipient_dst, lamports_dst, expiry_slot_dst, approval_count_dst, approvals_dst, bump_dst) =
+            mut_array_refs![dst, CO_COUNT_BYTE_LEN, CO_PUBKEY_BYTE_LEN, CO_PUBKEY_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_SLOT_BYTE_LEN, 1, CO_TREASURY_MAX_SIGNERS * CO_PUBKEY_BYTE_LEN, CO_BUMP_BYTE_LEN];
+        *proposal_id_dst = self.proposal_id.to_le_bytes();
+        proposer_dst.copy_from_slice(self.proposer.as_ref());
+        recipient_dst.copy_from_slice(self.recipient.as_ref());
+        *lamports_dst = self.lamports.to_le_bytes();
+        *expiry_slot_dst = self.expiry_slot.to_le_bytes();
+        approval_count_dst[0] = self.approvals.len() as u8;
+        for (approval, approval_dst) in self.approvals.iter().zip(approvals_dst.chunks_exact_mut(CO_PUBKEY_BYTE_LEN))
+        {
+            approval_dst.copy_from_slice(approval.as_ref());
+        }
+        *bump_dst = self.bump.to_le_bytes();
+
+        if self.version >= CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION_CREATED_SLOT
+        {
+            *array_mut_ref![dst_created_slot, 0, CO_SLOT_BYTE_LEN] = self.created_slot.to_le_bytes();
+        }
+    }
+
+    pub fn unpack(source: &[u8]) -> Result<WithdrawalProposal, ProgramError>
+    {
+        let version = unpack_account_header(source, CO_ACCOUNT_DISCRIMINATOR_WITHDRAWAL_PROPOSAL, CoinbackedError::InvalidWithdrawalProposalData)?;
+        let byte_len = match Self::byte_len_of_version(version)
+        {
+            Some(byte_len) => byte_len,
+            None =>
+            {
+                msg!("Unsupported withdrawal proposal version {}. Aborting", version);
+                return Err(CoinbackedError::InvalidWithdrawalProposalData.into());
+            }
+        };
+
+        if source.len() != byte_len
+        {
+            msg!("No or invalid withdrawal proposal data found. Aborting");
+            return Err(CoinbackedError::InvalidWithdrawalProposalData.into());
+        }
+
+        // version 1 proposals count as created at slot zero, delay has passed for them
+        let created_slot = if version >= CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION_CREATED_SLOT
+        {
+            u64::from_le_bytes(*array_ref![source, CO_ACCOUNT_HEADER_BYTE_LEN + CO_ACCOUNT_WITHDRAWAL_PROPOSAL_BYTE_LEN, CO_SLOT_BYTE_LEN])
+        }
+        else
+        {
+            0
+        };
+
+        let (proposal_id_data, proposer_data, recipient_data, lamports_data, expiry_slot_data, approval_count_data, approvals_data, bump_data) =
+            array_refs![array_ref![source, CO_ACCOUNT_HEADER_BYTE_LEN, CO_ACCOUNT_WITHDRAWAL_PROPOSAL_BYTE_LEN], CO_COUNT_BYTE_LEN, CO_PUBKEY_BYTE_LEN, CO_PUBKEY_BYTE_LEN, CO_LAMPORTS_BYTE_LEN, CO_SLOT_BYTE_LEN, 1, CO_TREASURY_MAX_SIGNERS * CO_PUBKEY_BYTE_LEN, CO_BUMP_BYTE_LEN];
+
+        let approval_count = approval_count_data[0] as usize;
+        if approval_count > CO_TREASURY_MAX_SIGNERS
+        {
+            msg!("Invalid approval count in withdrawal proposal. Aborting");
+            return Err(CoinbackedError::InvalidWithdrawalProposalData.into());
+        }
+
+        Ok(
+            WithdrawalProposal
+            {
+                version,
+                proposal_id: u64::from_le_bytes(*proposal_id_data),
+                proposer: Pubkey::new_from_array(*proposer_data),
+                recipient: Pubkey::new_from_array(*recipient_data),
+                lamports: u64::from_le_bytes(*lamports_data),
+                expiry_slot: u64::from_le_bytes(*expiry_slot_data),
+                approvals: approvals_data.chunks_exact(CO_PUBKEY_BYTE_LEN).take(approval_count).map(|approval_data| Pubkey::new_from_array(*array_ref![approval_data, 0, CO_PUBKEY_BYTE_LEN])).collect(),
+                bump: u8::from_le_bytes(*bump_data),
+                created_slot,
+            }
+        )
+    }
+}
+
+// helper functions
+
+fn pack_account_header(
+    dst: &mut [u8],
+    discriminator: u8,
+    version: u8
+)
+{
+    let dst = array_mut_ref![dst, 0, CO_ACCOUNT_HEADER_BYTE_LEN];
+    let (discriminator_dst, version_dst) = mut_array_refs![dst, CO_ACCOUNT_DISCRIMINATOR_BYTE_LEN, CO_ACCOUNT_VERSION_BYTE_LEN];
+    *discriminator_dst = discriminator.to_le_bytes();
+    *version_dst = version.to_le_bytes();
+}
+
+fn unpack_account_header(
+    source: &[u8],
+    discriminator: u8,
+    error: CoinbackedError
+) -> Result<u8, ProgramError>
+{
+    if source.len() < CO_ACCOUNT_HEADER_BYTE_LEN
+    {
+        msg!("No account header found. Aborting");
+        return Err(error.into());
+    }
+
+    let (discriminator_data, version_data) = array_refs![array_ref![source, 0, CO_ACCOUNT_HEADER_BYTE_LEN], CO_ACCOUNT_DISCRIMINATOR_BYTE_LEN, CO_ACCOUNT_VERSION_BYTE_LEN];
+
+    if u8::from_le_bytes(*discriminator_data) != discriminator
+    {
+        msg!("Account discriminator does not match. Aborting");
+        return Err(error.into());
+    }
+
+    Ok(u8::from_le_bytes(*version_data))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::instruction::{CO_PAUSE_CREATE, CO_PAUSE_ADD};
+
+    fn sample_backer_record(version: u8) -> BackerRecord
+    {
+        BackerRecord
+        {
+            version,
+            token_key: Pubkey::new_unique(),
+            contributor: Pubkey::new_unique(),
+            total_lamports: 3_000_000,
+            contribution_count: 3,
+            first_slot: 100,
+            last_slot: 300,
+            bump: 254,
+        }
+    }
+
+    fn sample_backing_account(version: u8, collateral_count: usize) -> BackingAccount
+    {
+        let stats = if version >= CO_ACCOUNT_BACKING_VERSION_STATS
+        {
+            Some(
+                BackingStats
+                {
+                    total_deposited: 5_000_000,
+                    total_paid_out: 1_000_000,
+                    total_burned: 200,
+                    burn_count: 2,
+                    creation_slot: 10,
+                    last_activity_slot: 20,
+                }
+            )
+        }
+        else
+        {
+            None
+        };
+
+        BackingAccount
+        {
+            version,
+            token_key: Pubkey::new_unique(),
+            rent_excemption: 890_880,
+            bump: 253,
+            stats,
+            exit_fee_bps: 0,
+            creator: None,
+            collateral_mints: (0..collateral_count).map(|_| Pubkey::new_unique()).collect(),
+        }
+    }
+
+    fn assert_backing_round_trip(backing_account: &BackingAccount)
+    {
+        let mut data = vec![0u8; backing_account.byte_len()];
+        backing_account.pack(&mut data);
+
+        let unpacked = BackingAccount::unpack(&data).unwrap();
+        assert_eq!(unpacked.version, backing_account.version);
+        assert_eq!(unpacked.token_key, backing_account.token_key);
+        assert_eq!(unpacked.rent_excemption, backing_account.rent_excemption);
+        assert_eq!(unpacked.bump, backing_account.bump);
+        assert_eq!(unpacked.exit_fee_bps, backing_account.exit_fee_bps);
+        assert_eq!(unpacked.creator, backing_account.creator);
+        assert_eq!(unpacked.collateral_mints, backing_account.collateral_mints);
+
+        match (&unpacked.stats, &backing_account.stats)
+        {
+            (Some(unpacked_stats), Some(stats)) =>
+            {
+                assert_eq!(unpacked_stats.total_deposited, stats.total_deposited);
+                assert_eq!(unpacked_stats.total_paid_out, stats.total_paid_out);
+                assert_eq!(unpacked_stats.total_burned, stats.total_burned);
+                assert_eq!(unpacked_stats.burn_count, stats.burn_count);
+                assert_eq!(unpacked_stats.creation_slot, stats.creation_slot);
+                assert_eq!(unpacked_stats.last_activity_slot, stats.last_activity_slot);
+            },
+            (None, None) => {},
+            _ => panic!("statistics do not round trip"),
+        }
+    }
+
+    #[test]
+    fn test_backing_account_without_header_round_trip()
+    {
+        for version in [0, CO_ACCOUNT_BACKING_VERSION_STATS]
+        {
+            for collateral_count in 0..=CO_BASKET_MAX_COLLATERAL
+            {
+                assert_backing_round_trip(&sample_backing_account(version, collateral_count));
+            }
+        }
+    }
+
+    #[test]
+    fn test_backing_account_with_header_round_trip()
+    {
+        for collateral_count in 0..=CO_BASKET_MAX_COLLATERAL
+        {
+            assert_backing_round_trip(&sample_backing_account(CO_ACCOUNT_BACKING_VERSION_HEADER, collateral_count));
+        }
+    }
+
+    #[test]
+    fn test_backing_account_unpack_rejects_foreign_data()
+    {
+        let backing_account = sample_backing_account(CO_ACCOUNT_BACKING_VERSION_HEADER, 0);
+        let mut data = vec![0u8; backing_account.byte_len()];
+        backing_account.pack(&mut data);
+
+        // headered account of another kind
+        data[0] = CO_ACCOUNT_DISCRIMINATOR_BACKER_RECORD;
+        assert!(BackingAccount::unpack(&data).is_err());
+
+        // lengths of no legacy layout are not read as headerless accounts
+        assert!(BackingAccount::unpack(&[0u8; CO_ACCOUNT_BACKING_BYTE_LEN + (CO_BASKET_MAX_COLLATERAL + 1) * CO_PUBKEY_BYTE_LEN]).is_err());
+        assert!(BackingAccount::unpack(&[0u8; CO_ACCOUNT_BACKING_BYTE_LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn test_backing_account_exit_fee_round_trip()
+    {
+        for collateral_count in 0..=CO_BASKET_MAX_COLLATERAL
+        {
+            let backing_account = BackingAccount
+            {
+                exit_fee_bps: CO_EXIT_FEE_MAX_BPS,
+                ..sample_backing_account(CO_ACCOUNT_BACKING_VERSION_EXIT_FEE, collateral_count)
+            };
+            assert_backing_round_trip(&backing_account);
+        }
+    }
+
+    #[test]
+    fn test_backing_account_creator_round_trip()
+    {
+        for creator in [Some(Pubkey::new_unique()), None]
+        {
+            let backing_account = BackingAccount
+            {
+                exit_fee_bps: 100,
+                creator,
+                ..sample_backing_account(CO_ACCOUNT_BACKING_VERSION_CREATOR, 1)
+            };
+            assert_backing_round_trip(&backing_account);
+        }
+    }
+
+    #[test]
+    fn test_treasury_account_round_trip()
+    {
+        for version in [0, CO_ACCOUNT_PROTOCOL_TREASURY_VERSION_HEADER]
+        {
+            let treasure = TreasuryAccount
+            {
+                version,
+                rent_excemption: 890_880,
+                bump: 252,
+                queued_lamports: 0,
+                queued_recipient: Pubkey::default(),
+                queued_slot: 0,
+                epoch: 0,
+                epoch_withdrawn: 0,
+            };
+
+            let mut data = vec![0u8; treasure.byte_len()];
+            treasure.pack(&mut data);
+
+            let unpacked = TreasuryAccount::unpack(&data).unwrap();
+            assert_eq!(unpacked.version, version);
+            assert_eq!(unpacked.rent_excemption, treasure.rent_excemption);
+            assert_eq!(unpacked.bump, treasure.bump);
+        }
+    }
+
+    fn sample_config(version: u8) -> ConfigAccount
+    {
+        ConfigAccount
+        {
+            version,
+            admin: Pubkey::new_unique(),
+            create_fee: 10_000,
+            validate_fee: 2_000,
+            add_fee: 3_000,
+            burn_fee: 4_000,
+            min_backing_lamports: 1_000_000,
+            bump: 251,
+            ..ConfigAccount::with_default_fees()
+        }
+    }
+
+    fn assert_config_round_trip(config: &ConfigAccount) -> ConfigAccount
+    {
+        let mut data = vec![0u8; config.byte_len()];
+        config.pack(&mut data);
+
+        let unpacked = ConfigAccount::unpack(&data).unwrap();
+        assert_eq!(unpacked.version, config.version);
+        assert_eq!(unpacked.admin, config.admin);
+        assert_eq!(unpacked.create_fee, config.create_fee);
+        assert_eq!(unpacked.validate_fee, config.validate_fee);
+        assert_eq!(unpacked.add_fee, config.add_fee);
+        assert_eq!(unpacked.burn_fee, config.burn_fee);
+        assert_eq!(unpacked.min_backing_lamports, config.min_backing_lamports);
+        assert_eq!(unpacked.bump, config.bump);
+
+        // fields of the layout survive another round
+        let mut repacked = vec![0u8; unpacked.byte_len()];
+        unpacked.pack(&mut repacked);
+        assert_eq!(repacked, data);
+
+        unpacked
+    }
+
+    #[test]
+    fn test_config_flat_fees_round_trip()
+    {
+        assert_config_round_trip(&sample_config(CO_ACCOUNT_CONFIG_VERSION_FLAT_FEES));
+    }
+
+    #[test]
+    fn test_config_fee_rates_round_trip()
+    {
+        let config = ConfigAccount
+        {
+            burn_fee_bps: 100,
+            burn_fee_max: 50_000_000,
+            add_fee_bps: 50,
+            add_fee_max: 10_000_000,
+            ..sample_config(CO_ACCOUNT_CONFIG_VERSION_FEE_RATES)
+        };
+
+        let unpacked = assert_config_round_trip(&config);
+        assert_eq!(unpacked.burn_fee_bps, 100);
+        assert_eq!(unpacked.burn_fee_max, 50_000_000);
+        assert_eq!(unpacked.add_fee_bps, 50);
+        assert_eq!(unpacked.add_fee_max, 10_000_000);
+
+        // version 1 charges flat fees only
+        let unpacked = assert_config_round_trip(&ConfigAccount { version: CO_ACCOUNT_CONFIG_VERSION_FLAT_FEES, ..config });
+        assert_eq!(unpacked.burn_fee_bps, 0);
+        assert_eq!(unpacked.add_fee_bps, 0);
+    }
+
+    #[test]
+    fn test_config_pending_admin_round_trip()
+    {
+        let pending_admin = Pubkey::new_unique();
+        let unpacked = assert_config_round_trip(&ConfigAccount { pending_admin: Some(pending_admin), ..sample_config(CO_ACCOUNT_CONFIG_VERSION_PENDING_ADMIN) });
+        assert_eq!(unpacked.pending_admin, Some(pending_admin));
+
+        // no handover in progress is stored as zeroes
+        let unpacked = assert_config_round_trip(&sample_config(CO_ACCOUNT_CONFIG_VERSION_PENDING_ADMIN));
+        assert_eq!(unpacked.pending_admin, None);
+    }
+
+    #[test]
+    fn test_config_treasury_signers_round_trip()
+    {
+        let signers: Vec<Pubkey> = (0..CO_TREASURY_MAX_SIGNERS).map(|_| Pubkey::new_unique()).collect();
+        let config = ConfigAccount
+        {
+            treasury_threshold: 3,
+            treasury_signers: signers.clone(),
+            ..sample_config(CO_ACCOUNT_CONFIG_VERSION_TREASURY_SIGNERS)
+        };
+
+        let unpacked = assert_config_round_trip(&config);
+        assert_eq!(unpacked.treasury_threshold, 3);
+        assert_eq!(unpacked.treasury_signers, signers);
+        assert_eq!(unpacked.treasury_signer_index(&signers[2]), Some(2));
+        assert_eq!(unpacked.treasury_signer_index(&config.admin), None);
+
+        // threshold above signer count is rejected
+        let mut data = vec![0u8; config.byte_len()];
+        ConfigAccount { treasury_threshold: 2, treasury_signers: vec![signers[0]], ..config }.pack(&mut data);
+        assert!(ConfigAccount::unpack(&data).is_err());
+    }
+
+    #[test]
+    fn test_config_treasury_limits_round_trip()
+    {
+        let config = ConfigAccount
+        {
+            treasury_delay_slots: 432_000,
+            treasury_epoch_cap: 100_000_000_000,
+            ..sample_config(CO_ACCOUNT_CONFIG_VERSION_TREASURY_LIMITS)
+        };
+
+        let unpacked = assert_config_round_trip(&config);
+        assert_eq!(unpacked.treasury_delay_slots, 432_000);
+        assert_eq!(unpacked.treasury_epoch_cap, 100_000_000_000);
+
+        let config = ConfigAccount
+        {
+            pending_treasury_delay_slots: 1_000,
+            pending_treasury_epoch_cap: 0,
+            pending_treasury_limits_slot: 5_000,
+            ..sample_config(CO_ACCOUNT_CONFIG_VERSION_PENDING_TREASURY_LIMITS)
+        };
+
+        let unpacked = assert_config_round_trip(&config);
+        assert_eq!(unpacked.pending_treasury_delay_slots, 1_000);
+        assert_eq!(unpacked.pending_treasury_epoch_cap, 0);
+        assert_eq!(unpacked.pending_treasury_limits_slot, 5_000);
+    }
+
+    #[test]
+    fn test_config_loosens_treasury_limits()
+    {
+        let config = ConfigAccount
+        {
+            treasury_delay_slots: 1_000,
+            treasury_epoch_cap: 50_000,
+            ..sample_config(CO_ACCOUNT_CONFIG_VERSION)
+        };
+
+        // tightening
+        assert!(!config.loosens_treasury_limits(1_000, 50_000));
+        assert!(!config.loosens_treasury_limits(2_000, 10_000));
+
+        // shorter delay, higher or no cap
+        assert!(config.loosens_treasury_limits(999, 50_000));
+        assert!(config.loosens_treasury_limits(1_000, 50_001));
+        assert!(config.loosens_treasury_limits(1_000, 0));
+
+        // any cap is tighter than none
+        let uncapped = ConfigAccount { treasury_epoch_cap: 0, ..config };
+        assert!(!uncapped.loosens_treasury_limits(1_000, 1));
+    }
+
+    #[test]
+    fn test_config_pause_round_trip()
+    {
+        let config = ConfigAccount
+        {
+            pause_flags: CO_PAUSE_CREATE | CO_PAUSE_BURN,
+            burn_pause_max_slots: 1_000,
+            burn_paused_slot: 50,
+            ..sample_config(CO_ACCOUNT_CONFIG_VERSION_PAUSE)
+        };
+
+        let unpacked = assert_config_round_trip(&config);
+        assert_eq!(unpacked.pause_flags, CO_PAUSE_CREATE | CO_PAUSE_BURN);
+        assert_eq!(unpacked.burn_pause_max_slots, 1_000);
+        assert_eq!(unpacked.burn_paused_slot, 50);
+    }
+
+    #[test]
+    fn test_config_is_paused()
+    {
+        let config = ConfigAccount
+        {
+            pause_flags: CO_PAUSE_CREATE | CO_PAUSE_BURN,
+            burn_pause_max_slots: 1_000,
+            burn_paused_slot: 500,
+            ..sample_config(CO_ACCOUNT_CONFIG_VERSION)
+        };
+
+        assert!(config.is_paused(CO_PAUSE_CREATE, 10_000_000));
+        assert!(!config.is_paused(CO_PAUSE_ADD, 600));
+
+        // burn pause lapses after its window
+        assert!(config.is_paused(CO_PAUSE_BURN, 500));
+        assert!(config.is_paused(CO_PAUSE_BURN, 1_499));
+        assert!(!config.is_paused(CO_PAUSE_BURN, 1_500));
+    }
+
+    #[test]
+    fn test_config_burn_pause_cooldown_end()
+    {
+        let config = ConfigAccount
+        {
+            burn_pause_max_slots: 1_000,
+            ..sample_config(CO_ACCOUNT_CONFIG_VERSION)
+        };
+
+        // never paused
+        assert_eq!(config.burn_pause_cooldown_end(), 0);
+
+        // burns stay open for at least one window after a pause lapsed
+        let config = ConfigAccount { burn_paused_slot: 500, ..config };
+        assert_eq!(config.burn_pause_cooldown_end(), 2_500);
+    }
+
+    #[test]
+    fn test_config_creator_fee_share_round_trip()
+    {
+        let unpacked = assert_config_round_trip(&ConfigAccount { creator_fee_share_bps: 2_000, ..sample_config(CO_ACCOUNT_CONFIG_VERSION_CREATOR_FEE_SHARE) });
+        assert_eq!(unpacked.creator_fee_share_bps, 2_000);
+    }
+
+    #[test]
+    fn test_config_referrer_fee_share_round_trip()
+    {
+        let unpacked = assert_config_round_trip(&ConfigAccount { creator_fee_share_bps: 2_000, referrer_fee_share_bps: 1_000, ..sample_config(CO_ACCOUNT_CONFIG_VERSION_REFERRER_FEE_SHARE) });
+        assert_eq!(unpacked.creator_fee_share_bps, 2_000);
+        assert_eq!(unpacked.referrer_fee_share_bps, 1_000);
+    }
+
+    #[test]
+    fn test_config_default_fees()
+    {
+        let config = ConfigAccount::with_default_fees();
+        assert_eq!(config.version, CO_ACCOUNT_CONFIG_VERSION);
+        assert_eq!(config.create_fee, CO_PROTOCOL_FEE);
+        assert_eq!(config.validate_fee, CO_PROTOCOL_FEE);
+        assert_eq!(config.add_fee, CO_PROTOCOL_FEE);
+        assert_eq!(config.burn_fee, CO_PROTOCOL_FEE);
+        assert_eq!(config.pause_flags, 0);
+        assert_eq!(config.creator_fee_share_bps, 0);
+        assert_eq!(config.referrer_fee_share_bps, 0);
+    }
+
+    #[test]
+    fn test_config_unpack_rejects_invalid_data()
+    {
+        let config = sample_config(CO_ACCOUNT_CONFIG_VERSION);
+        let mut data = vec![0u8; config.byte_len()];
+        config.pack(&mut data);
+
+        assert!(ConfigAccount::unpack(&data[..data.len() - 1]).is_err());
+
+        // unknown version
+        data[CO_ACCOUNT_DISCRIMINATOR_BYTE_LEN] = CO_ACCOUNT_CONFIG_VERSION + 1;
+        assert!(ConfigAccount::unpack(&data).is_err());
+    }
+
+    fn sample_withdrawal_proposal(version: u8) -> WithdrawalProposal
+    {
+        WithdrawalProposal
+        {
+            version,
+            proposal_id: 7,
+            proposer: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            lamports: 2_000_000_000,
+            expiry_slot: 1_000,
+            approvals: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+            bump: 250,
+            created_slot: 0,
+        }
+    }
+
+    fn assert_withdrawal_proposal_round_trip(proposal: &WithdrawalProposal) -> WithdrawalProposal
+    {
+        let mut data = vec![0u8; proposal.byte_len()];
+        proposal.pack(&mut data);
+
+        let unpacked = WithdrawalProposal::unpack(&data).unwrap();
+        assert_eq!(unpacked.version, proposal.version);
+        assert_eq!(unpacked.proposal_id, proposal.proposal_id);
+        assert_eq!(unpacked.proposer, proposal.proposer);
+        assert_eq!(unpacked.recipient, proposal.recipient);
+        assert_eq!(unpacked.lamports, proposal.lamports);
+        assert_eq!(unpacked.expiry_slot, proposal.expiry_slot);
+        assert_eq!(unpacked.approvals, proposal.approvals);
+        assert_eq!(unpacked.bump, proposal.bump);
+
+        unpacked
+    }
+
+    #[test]
+    fn test_withdrawal_proposal_round_trip()
+    {
+        let unpacked = assert_withdrawal_proposal_round_trip(&sample_withdrawal_proposal(CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION_BASE));
+        assert_eq!(unpacked.created_slot, 0);
+
+        let mut data = vec![0u8; unpacked.byte_len()];
+        unpacked.pack(&mut data);
+        assert!(WithdrawalProposal::unpack(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_withdrawal_proposal_created_slot_round_trip()
+    {
+        let proposal = WithdrawalProposal
+        {
+            created_slot: 123_456,
+            ..sample_withdrawal_proposal(CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION_CREATED_SLOT)
+        };
+
+        let unpacked = assert_withdrawal_proposal_round_trip(&proposal);
+        assert_eq!(unpacked.created_slot, 123_456);
+    }
+
+    #[test]
+    fn test_treasury_account_limits_round_trip()
+    {
+        let treasure = TreasuryAccount
+        {
+            version: CO_ACCOUNT_PROTOCOL_TREASURY_VERSION_LIMITS,
+            rent_excemption: 890_880,
+            bump: 252,
+            queued_lamports: 1_000_000_000,
+            queued_recipient: Pubkey::new_unique(),
+            queued_slot: 77,
+            epoch: 5,
+            epoch_withdrawn: 3_000_000_000,
+        };
+
+        let mut data = vec![0u8; treasure.byte_len()];
+        treasure.pack(&mut data);
+
+        let unpacked = TreasuryAccount::unpack(&data).unwrap();
+        assert_eq!(unpacked.version, treasure.version);
+        assert_eq!(unpacked.rent_excemption, treasure.rent_excemption);
+        assert_eq!(unpacked.bump, treasure.bump);
+        assert_eq!(unpacked.queued_lamports, treasure.queued_lamports);
+        assert_eq!(unpacked.queued_recipient, treasure.queued_recipient);
+        assert_eq!(unpacked.queued_slot, treasure.queued_slot);
+        assert_eq!(unpacked.epoch, treasure.epoch);
+        assert_eq!(unpacked.epoch_withdrawn, treasure.epoch_withdrawn);
+    }
+
+    #[test]
+    fn test_backer_record_round_trip()
+    {
+        for version in [0, CO_ACCOUNT_BACKER_RECORD_VERSION_HEADER]
+        {
+            let record = sample_backer_record(version);
+            let mut data = vec![0u8; record.byte_len()];
+            record.pack(&mut data);
+
+            let unpacked = BackerRecord::unpack(&data).unwrap();
+            assert_eq!(unpacked.version, version);
+            assert_eq!(unpacked.token_key, record.token_key);
+            assert_eq!(unpacked.contributor, record.contributor);
+            assert_eq!(unpacked.total_lamports, record.total_lamports);
+            assert_eq!(unpacked.contribution_count, record.contribution_count);
+            assert_eq!(unpacked.first_slot, record.first_slot);
+            assert_eq!(unpacked.last_slot, record.last_slot);
+            assert_eq!(unpacked.bump, record.bump);
+        }
+    }
+
+    #[test]
+    fn test_backer_record_unpack_rejects_invalid_data()
+    {
+        let record = sample_backer_record(CO_ACCOUNT_BACKER_RECORD_VERSION);
+        let mut data = vec![0u8; record.byte_len()];
+        record.pack(&mut data);
+
+        assert!(BackerRecord::unpack(&data[..data.len() - 1]).is_err());
+
+        // other account kind
+        data[0] = CO_ACCOUNT_DISCRIMINATOR_BACKING;
+        assert!(BackerRecord::unpack(&data).is_err());
+    }
+}
@@ -7,13 +7,83 @@ pub const CO_LAMPORTS_BYTE_LEN: usize = 8;
 pub const CO_TOKEN_AMOUNT_BYTE_LEN: usize = 8;
 pub const CO_BUMP_BYTE_LEN: usize = 1;
 pub const CO_PUBKEY_BYTE_LEN: usize = 32;
+pub const CO_BPS_BYTE_LEN: usize = 2;
+pub const CO_BPS_DENOMINATOR: u64 = 10_000;
+
+// account header, discriminator and layout version in front of versioned account layouts
+pub const CO_ACCOUNT_DISCRIMINATOR_BYTE_LEN: usize = 1;
+pub const CO_ACCOUNT_VERSION_BYTE_LEN: usize = 1;
+pub const CO_ACCOUNT_HEADER_BYTE_LEN: usize = CO_ACCOUNT_DISCRIMINATOR_BYTE_LEN + CO_ACCOUNT_VERSION_BYTE_LEN;
+pub const CO_ACCOUNT_DISCRIMINATOR_BACKING: u8 = 1;
+pub const CO_ACCOUNT_DISCRIMINATOR_TREASURY: u8 = 2;
+pub const CO_ACCOUNT_DISCRIMINATOR_BACKER_RECORD: u8 = 3;
+pub const CO_ACCOUNT_DISCRIMINATOR_CONFIG: u8 = 4;
+pub const CO_ACCOUNT_DISCRIMINATOR_WITHDRAWAL_PROPOSAL: u8 = 5;
 
 pub const CO_SEED_COINBACKED: &[u8; 10] = b"COINBACKED"; // [67, 79, 73, 78, 66, 65, 67, 75, 69, 68]
 pub const CO_ACCOUNT_BACKING_BYTE_LEN: usize = CO_PUBKEY_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_BUMP_BYTE_LEN;
+pub const CO_BASKET_MAX_COLLATERAL: usize = 2;
+
+pub const CO_SLOT_BYTE_LEN: usize = 8;
+pub const CO_COUNT_BYTE_LEN: usize = 8;
+pub const CO_SEED_BACKER_RECORD: &[u8; 17] = b"COINBACKED-BACKER";
+pub const CO_ACCOUNT_BACKING_STATS_BYTE_LEN: usize = 2 * CO_LAMPORTS_BYTE_LEN + CO_TOKEN_AMOUNT_BYTE_LEN + CO_COUNT_BYTE_LEN + 2 * CO_SLOT_BYTE_LEN;
+pub const CO_ACCOUNT_BACKING_VERSION_STATS: u8 = 1; // first version with statistics, no header
+pub const CO_ACCOUNT_BACKING_VERSION_HEADER: u8 = 2; // first version with header
+pub const CO_ACCOUNT_BACKING_VERSION_EXIT_FEE: u8 = 3; // first version with exit fee
+pub const CO_ACCOUNT_BACKING_VERSION_CREATOR: u8 = 4; // first version with creator
+pub const CO_ACCOUNT_BACKING_VERSION: u8 = CO_ACCOUNT_BACKING_VERSION_CREATOR;
+pub const CO_EXIT_FEE_MAX_BPS: u16 = 1_000;
+pub const CO_ACCOUNT_BACKER_RECORD_BYTE_LEN: usize = 2 * CO_PUBKEY_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_COUNT_BYTE_LEN + 2 * CO_SLOT_BYTE_LEN + CO_BUMP_BYTE_LEN;
+pub const CO_ACCOUNT_BACKER_RECORD_VERSION_HEADER: u8 = 1; // first version with header
+pub const CO_ACCOUNT_BACKER_RECORD_VERSION: u8 = CO_ACCOUNT_BACKER_RECORD_VERSION_HEADER;
 
-pub const CO_PROTOCOL_FEE: u64 = 5000;
+pub const CO_PROTOCOL_FEE: u64 = 5000; // flat fee per operation while no config account is initialised
+pub const CO_BURN_FEE_MAX_BPS: u16 = 500; // hard cap on burn fee rate, admin cannot exceed it
+pub const CO_BURN_FEE_MAX_LAMPORTS: u64 = 100_000_000; // hard cap on flat and maximum burn fee
 pub const CO_SEED_PROTOCOL_TREASURY: &[u8; 19] = b"COINBACKED-TREASURY";
 pub const CO_ACCOUNT_PROTOCOL_TREASURY_BYTE_LEN: usize = CO_LAMPORTS_BYTE_LEN + CO_BUMP_BYTE_LEN;
+pub const CO_ACCOUNT_PROTOCOL_TREASURY_LIMITS_BYTE_LEN: usize = CO_LAMPORTS_BYTE_LEN + CO_PUBKEY_BYTE_LEN + CO_SLOT_BYTE_LEN + CO_COUNT_BYTE_LEN + CO_LAMPORTS_BYTE_LEN;
+pub const CO_ACCOUNT_PROTOCOL_TREASURY_VERSION_HEADER: u8 = 1; // first version with header
+pub const CO_ACCOUNT_PROTOCOL_TREASURY_VERSION_LIMITS: u8 = 2; // first version with withdrawal queue and epoch counter
+pub const CO_ACCOUNT_PROTOCOL_TREASURY_VERSION: u8 = CO_ACCOUNT_PROTOCOL_TREASURY_VERSION_LIMITS;
+
+pub const CO_SEED_CONFIG: &[u8; 17] = b"COINBACKED-CONFIG";
+pub const CO_ACCOUNT_CONFIG_FEES_BYTE_LEN: usize = 4 * CO_LAMPORTS_BYTE_LEN + CO_LAMPORTS_BYTE_LEN;
+pub const CO_ACCOUNT_CONFIG_BYTE_LEN: usize = CO_PUBKEY_BYTE_LEN + CO_ACCOUNT_CONFIG_FEES_BYTE_LEN + CO_BUMP_BYTE_LEN;
+pub const CO_ACCOUNT_CONFIG_FEE_RATES_BYTE_LEN: usize = 2 * (CO_BPS_BYTE_LEN + CO_LAMPORTS_BYTE_LEN);
+pub const CO_ACCOUNT_CONFIG_VERSION_FLAT_FEES: u8 = 1; // first version, flat fees only
+pub const CO_ACCOUNT_CONFIG_VERSION_FEE_RATES: u8 = 2; // first version with fee rates
+pub const CO_ACCOUNT_CONFIG_VERSION_PENDING_ADMIN: u8 = 3; // first version with two-step admin handover
+pub const CO_ACCOUNT_CONFIG_VERSION_TREASURY_SIGNERS: u8 = 4; // first version with treasury signers
+pub const CO_ACCOUNT_CONFIG_VERSION_TREASURY_LIMITS: u8 = 5; // first version with treasury withdrawal delay and cap
+pub const CO_ACCOUNT_CONFIG_VERSION_PAUSE: u8 = 6; // first version with pause flags
+pub const CO_ACCOUNT_CONFIG_VERSION_CREATOR_FEE_SHARE: u8 = 7; // first version with creator fee share
+pub const CO_ACCOUNT_CONFIG_VERSION_REFERRER_FEE_SHARE: u8 = 8; // first version with referrer fee share
+pub const CO_ACCOUNT_CONFIG_VERSION_PENDING_TREASURY_LIMITS: u8 = 9; // first version with delayed loosening of treasury limits
+pub const CO_ACCOUNT_CONFIG_VERSION: u8 = CO_ACCOUNT_CONFIG_VERSION_PENDING_TREASURY_LIMITS;
+
+pub const CO_TREASURY_MAX_SIGNERS: usize = 8;
+pub const CO_TREASURY_SIGNERS_BYTE_LEN: usize = 1 + 1 + CO_TREASURY_MAX_SIGNERS * CO_PUBKEY_BYTE_LEN;
+pub const CO_TREASURY_LIMITS_BYTE_LEN: usize = CO_SLOT_BYTE_LEN + CO_LAMPORTS_BYTE_LEN;
+pub const CO_PENDING_TREASURY_LIMITS_BYTE_LEN: usize = CO_TREASURY_LIMITS_BYTE_LEN + CO_SLOT_BYTE_LEN;
+
+// pause window, flags are part of the instruction interface
+pub const CO_PAUSE_BURN_MAX_SLOTS: u64 = 216_000; // about a day
+pub const CO_PAUSE_BYTE_LEN: usize = 1 + 2 * CO_SLOT_BYTE_LEN;
+
+pub const CO_SEED_WITHDRAWAL_PROPOSAL: &[u8; 21] = b"COINBACKED-WITHDRAWAL";
+pub const CO_ACCOUNT_WITHDRAWAL_PROPOSAL_BYTE_LEN: usize = CO_COUNT_BYTE_LEN + 2 * CO_PUBKEY_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_SLOT_BYTE_LEN + 1 + CO_TREASURY_MAX_SIGNERS * CO_PUBKEY_BYTE_LEN + CO_BUMP_BYTE_LEN;
+pub const CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION_BASE: u8 = 1; // first version
+pub const CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION_CREATED_SLOT: u8 = 2; // first version with creation slot
+pub const CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION: u8 = CO_ACCOUNT_WITHDRAWAL_PROPOSAL_VERSION_CREATED_SLOT;
+
+// return data
+pub const CO_RETURN_PAYOUT_QUOTE_BYTE_LEN: usize = CO_LAMPORTS_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_TOKEN_AMOUNT_BYTE_LEN + CO_LAMPORTS_BYTE_LEN;
+pub const CO_RETURN_VALIDATION_REPORT_BYTE_LEN: usize = 1 + CO_LAMPORTS_BYTE_LEN + 1;
+pub const CO_RETURN_COLLATERAL_QUOTE_BYTE_LEN: usize = CO_PUBKEY_BYTE_LEN + 3 * CO_TOKEN_AMOUNT_BYTE_LEN;
+pub const CO_RETURN_BASKET_BYTE_LEN: usize = 1 + CO_BASKET_MAX_COLLATERAL * CO_RETURN_COLLATERAL_QUOTE_BYTE_LEN;
+pub const CO_RETURN_EXIT_FEE_BYTE_LEN: usize = CO_BPS_BYTE_LEN;
 
 // operation specific
 pub const CO_OP_CREATE_BACKING_ACCOUNT:u8 = 0;
@@ -31,9 +101,92 @@ pub const CO_OP_BURN_AND_FREE_BALANCE_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + C
 pub const CO_OP_CLEAN_ACCOUNTS_AFTER_BURNING: u8 = 4;
 pub const CO_OP_CLEAN_ACCOUNTS_AFTER_BURNING_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN;
 
+pub const CO_OP_QUOTE_PAYOUT: u8 = 5;
+pub const CO_OP_QUOTE_PAYOUT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_TOKEN_AMOUNT_BYTE_LEN;
+
+pub const CO_OP_VALIDATE_BACKING_ACCOUNT_STRICT: u8 = 6;
+pub const CO_OP_VALIDATE_BACKING_ACCOUNT_STRICT_BYTE_LEN: usize = CO_OPERATION_BYTE_LEN;
+
+pub const CO_OP_BURN_AND_FREE_BALANCE_WITH_MIN_PAYOUT: u8 = 7;
+pub const CO_OP_BURN_AND_FREE_BALANCE_WITH_MIN_PAYOUT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_TOKEN_AMOUNT_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_SIGNATURE_BASE58_BYTE_LEN;
+
+pub const CO_OP_BURN_AND_FREE_BALANCE_TO_RECIPIENT: u8 = 8;
+pub const CO_OP_BURN_AND_FREE_BALANCE_TO_RECIPIENT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_TOKEN_AMOUNT_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_SIGNATURE_BASE58_BYTE_LEN;
+
 pub const CO_OP_ADMIN_CREATE_TREASURY_ACCOUNT: u8 = 10;
 pub const CO_OP_ADMIN_CREATE_TREASURY_ACCOUNT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN;
 
 pub const CO_OP_ADMIN_TRANSFER_FROM_TREASURY_ACCOUNT: u8 = 11;
 pub const CO_OP_ADMIN_TRANSFER_FROM_TREASURY_ACCOUNT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_LAMPORTS_BYTE_LEN;
 
+pub const CO_OP_CREATE_COLLATERAL_BACKING_ACCOUNT: u8 = 12;
+pub const CO_OP_CREATE_COLLATERAL_BACKING_ACCOUNT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_TOKEN_AMOUNT_BYTE_LEN + CO_SIGNATURE_BASE58_BYTE_LEN;
+
+pub const CO_OP_ADD_COLLATERAL_TO_BALANCE_OF_BACKING_ACCOUNT: u8 = 13;
+pub const CO_OP_ADD_COLLATERAL_TO_BALANCE_OF_BACKING_ACCOUNT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_TOKEN_AMOUNT_BYTE_LEN + CO_SIGNATURE_BASE58_BYTE_LEN;
+
+pub const CO_OP_BURN_AND_FREE_COLLATERAL: u8 = 14;
+pub const CO_OP_BURN_AND_FREE_COLLATERAL_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_TOKEN_AMOUNT_BYTE_LEN + CO_TOKEN_AMOUNT_BYTE_LEN + CO_SIGNATURE_BASE58_BYTE_LEN;
+
+pub const CO_OP_CREATE_BASKET_BACKING_ACCOUNT: u8 = 15;
+pub const CO_OP_CREATE_BASKET_BACKING_ACCOUNT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_BASKET_MAX_COLLATERAL * CO_TOKEN_AMOUNT_BYTE_LEN + CO_SIGNATURE_BASE58_BYTE_LEN;
+
+pub const CO_OP_BURN_AND_FREE_BASKET: u8 = 16;
+pub const CO_OP_BURN_AND_FREE_BASKET_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_TOKEN_AMOUNT_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_BASKET_MAX_COLLATERAL * CO_TOKEN_AMOUNT_BYTE_LEN + CO_SIGNATURE_BASE58_BYTE_LEN;
+
+pub const CO_OP_MIGRATE_BACKING_ACCOUNT: u8 = 17;
+pub const CO_OP_MIGRATE_BACKING_ACCOUNT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN;
+
+pub const CO_OP_ADMIN_INIT_CONFIG_ACCOUNT: u8 = 18;
+pub const CO_OP_ADMIN_INIT_CONFIG_ACCOUNT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_ACCOUNT_CONFIG_FEES_BYTE_LEN;
+
+pub const CO_OP_ADMIN_UPDATE_CONFIG_ACCOUNT: u8 = 19;
+pub const CO_OP_ADMIN_UPDATE_CONFIG_ACCOUNT_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_ACCOUNT_CONFIG_FEES_BYTE_LEN;
+
+pub const CO_OP_ADMIN_UPDATE_CONFIG_FEE_RATES: u8 = 20;
+pub const CO_OP_ADMIN_UPDATE_CONFIG_FEE_RATES_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_ACCOUNT_CONFIG_FEE_RATES_BYTE_LEN;
+
+pub const CO_OP_CREATE_BACKING_ACCOUNT_WITH_EXIT_FEE: u8 = 21;
+pub const CO_OP_CREATE_BACKING_ACCOUNT_WITH_EXIT_FEE_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_BPS_BYTE_LEN + CO_SIGNATURE_BASE58_BYTE_LEN;
+
+pub const CO_OP_ADMIN_PROPOSE_ADMIN: u8 = 22;
+pub const CO_OP_ADMIN_PROPOSE_ADMIN_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_PUBKEY_BYTE_LEN;
+
+pub const CO_OP_ADMIN_ACCEPT_ADMIN: u8 = 23;
+pub const CO_OP_ADMIN_ACCEPT_ADMIN_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN;
+
+pub const CO_OP_ADMIN_MAKE_PROGRAM_IMMUTABLE: u8 = 24;
+pub const CO_OP_ADMIN_MAKE_PROGRAM_IMMUTABLE_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN;
+
+pub const CO_OP_ADMIN_SET_TREASURY_SIGNERS: u8 = 25;
+pub const CO_OP_ADMIN_SET_TREASURY_SIGNERS_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_TREASURY_SIGNERS_BYTE_LEN;
+
+pub const CO_OP_PROPOSE_TREASURY_WITHDRAWAL: u8 = 26;
+pub const CO_OP_PROPOSE_TREASURY_WITHDRAWAL_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_COUNT_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_SLOT_BYTE_LEN;
+
+pub const CO_OP_APPROVE_TREASURY_WITHDRAWAL: u8 = 27;
+pub const CO_OP_APPROVE_TREASURY_WITHDRAWAL_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN;
+
+pub const CO_OP_EXECUTE_TREASURY_WITHDRAWAL: u8 = 28;
+pub const CO_OP_EXECUTE_TREASURY_WITHDRAWAL_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN;
+
+pub const CO_OP_ADMIN_UPDATE_TREASURY_LIMITS: u8 = 29;
+pub const CO_OP_ADMIN_UPDATE_TREASURY_LIMITS_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_TREASURY_LIMITS_BYTE_LEN;
+
+pub const CO_OP_ADMIN_QUEUE_TREASURY_TRANSFER: u8 = 30;
+pub const CO_OP_ADMIN_QUEUE_TREASURY_TRANSFER_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_LAMPORTS_BYTE_LEN;
+
+pub const CO_OP_ADMIN_SET_PAUSE: u8 = 31;
+pub const CO_OP_ADMIN_SET_PAUSE_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + 1 + CO_SLOT_BYTE_LEN;
+
+pub const CO_OP_CREATE_BACKING_ACCOUNT_WITH_CREATOR: u8 = 32;
+pub const CO_OP_CREATE_BACKING_ACCOUNT_WITH_CREATOR_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_LAMPORTS_BYTE_LEN + CO_BPS_BYTE_LEN + CO_PUBKEY_BYTE_LEN + CO_SIGNATURE_BASE58_BYTE_LEN;
+
+pub const CO_OP_ADMIN_UPDATE_CREATOR_FEE_SHARE: u8 = 33;
+pub const CO_OP_ADMIN_UPDATE_CREATOR_FEE_SHARE_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_BPS_BYTE_LEN;
+
+pub const CO_OP_ADMIN_UPDATE_REFERRER_FEE_SHARE: u8 = 34;
+pub const CO_OP_ADMIN_UPDATE_REFERRER_FEE_SHARE_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN + CO_BPS_BYTE_LEN;
+
+pub const CO_OP_CLOSE_EXPIRED_TREASURY_WITHDRAWAL: u8 = 35;
+pub const CO_OP_CLOSE_EXPIRED_TREASURY_WITHDRAWAL_BYTE_LEN:usize = CO_OPERATION_BYTE_LEN;
@@ -0,0 +1,128 @@
+//! Program tests of basket backing
+
+mod common;
+
+use solana_program_test::tokio;
+use solana_sdk::
+{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use coinbacked::
+{
+    error::CoinbackedError,
+    instruction::{self, BasketCollateral},
+    return_data::PayoutQuote,
+};
+
+use common::*;
+
+struct Basket
+{
+    holder: Keypair,
+    mint: Pubkey,
+    token_account: Pubkey,
+    collateral: [BasketCollateral; 2],
+}
+
+/// Basket of lamports, an SPL Token and a Token-2022 asset, created by the holder of the whole supply.
+async fn start_with_basket() -> (solana_program_test::ProgramTestContext, Basket)
+{
+    let (mut program_test, admin) = program_test();
+    let (holder, mint, token_account) = add_holder(&mut program_test, &spl_token::ID);
+    let collateral_mint = add_mint(&mut program_test, &spl_token::ID, COLLATERAL);
+    let collateral_account = add_token_account(&mut program_test, &spl_token::ID, &collateral_mint, &holder.pubkey(), COLLATERAL);
+    let collateral_2022_mint = add_mint(&mut program_test, &spl_token_2022::ID, COLLATERAL);
+    let collateral_2022_account = add_token_2022_account(&mut program_test, &collateral_2022_mint, &holder.pubkey(), COLLATERAL);
+    let mut context = start(program_test, &admin).await;
+
+    let collateral = [
+        BasketCollateral { mint: collateral_mint, token_program_id: spl_token::ID, token_account: collateral_account, amount: COLLATERAL },
+        BasketCollateral { mint: collateral_2022_mint, token_program_id: spl_token_2022::ID, token_account: collateral_2022_account, amount: COLLATERAL / 2 },
+    ];
+    let instruction = instruction::create_basket_backing_account(&program_id(), &holder.pubkey(), &mint, &token_account, BACKING_LAMPORTS, &collateral, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+
+    (context, Basket { holder, mint, token_account, collateral })
+}
+
+fn with_min_payouts(collateral: &[BasketCollateral; 2], amounts: [u64; 2]) -> [BasketCollateral; 2]
+{
+    [
+        BasketCollateral { amount: amounts[0], ..collateral[0] },
+        BasketCollateral { amount: amounts[1], ..collateral[1] },
+    ]
+}
+
+#[tokio::test]
+async fn test_quote_and_burn_basket()
+{
+    let (mut context, basket) = start_with_basket().await;
+
+    // quote covers every basket asset
+    let instruction = instruction::quote_basket_payout(&program_id(), &basket.mint, &basket.collateral, 100).unwrap();
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+    let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+    let data = simulation.simulation_details.unwrap().return_data.unwrap().data;
+    let quote = PayoutQuote::unpack(&data).unwrap();
+    assert_eq!(quote.payout, BACKING_LAMPORTS / 10);
+    assert_eq!(quote.collateral.iter().map(|asset| asset.payout).collect::<Vec<_>>(), vec![COLLATERAL / 10, COLLATERAL / 20]);
+
+    // every minimum has to be met
+    let collateral = with_min_payouts(&basket.collateral, [COLLATERAL / 10, COLLATERAL / 20 + 1]);
+    let instruction = instruction::burn_token_and_free_basket(&program_id(), &spl_token::ID, &basket.holder.pubkey(), &basket.mint, &basket.token_account, &basket.holder.pubkey(), &collateral, 100, BACKING_LAMPORTS / 10, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&basket.holder]).await, CoinbackedError::PayoutBelowMinimum);
+
+    let lamports_before = get_lamports(&mut context, &basket.holder.pubkey()).await;
+    let collateral = with_min_payouts(&basket.collateral, [COLLATERAL / 10, COLLATERAL / 20]);
+    let instruction = instruction::burn_token_and_free_basket(&program_id(), &spl_token::ID, &basket.holder.pubkey(), &basket.mint, &basket.token_account, &basket.holder.pubkey(), &collateral, 100, BACKING_LAMPORTS / 10, TOS).unwrap();
+    process(&mut context, &[instruction], &[&basket.holder]).await.unwrap();
+    assert_eq!(get_token_amount(&mut context, &basket.token_account).await, SUPPLY - 100);
+    assert_eq!(get_lamports(&mut context, &basket.holder.pubkey()).await, lamports_before + BACKING_LAMPORTS / 10 - PROTOCOL_FEE);
+    assert_eq!(get_token_amount(&mut context, &basket.collateral[0].token_account).await, COLLATERAL / 10);
+    assert_eq!(get_token_amount(&mut context, &basket.collateral[1].token_account).await, COLLATERAL / 2 + COLLATERAL / 20);
+}
+
+#[tokio::test]
+async fn test_basket_account_checks()
+{
+    let (mut context, basket) = start_with_basket().await;
+    let holder = &basket.holder;
+    let other = Keypair::new();
+
+    // assets have to be passed complete and in basket order
+    let reversed = [
+        BasketCollateral { amount: 0, ..basket.collateral[1] },
+        BasketCollateral { amount: 0, ..basket.collateral[0] },
+    ];
+    for collateral in [&reversed[..], &basket.collateral[..1]]
+    {
+        let instruction = instruction::burn_token_and_free_basket(&program_id(), &spl_token::ID, &holder.pubkey(), &basket.mint, &basket.token_account, &holder.pubkey(), collateral, 100, 0, TOS).unwrap();
+        assert_error(process(&mut context, &[instruction], &[holder]).await, CoinbackedError::InvalidBasketAccounts);
+    }
+
+    // lamports and collateral go to the token owner only
+    let collateral = with_min_payouts(&basket.collateral, [0, 0]);
+    let instruction = instruction::burn_token_and_free_basket(&program_id(), &spl_token::ID, &holder.pubkey(), &basket.mint, &basket.token_account, &other.pubkey(), &collateral, 100, 0, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[holder]).await, CoinbackedError::InvalidRecipientAccount);
+
+    let mut collateral = with_min_payouts(&basket.collateral, [0, 0]);
+    collateral[0].token_account = instruction::find_collateral_vault_address(&basket.mint, &collateral[0].mint, &spl_token::ID, &program_id());
+    let instruction = instruction::burn_token_and_free_basket(&program_id(), &spl_token::ID, &holder.pubkey(), &basket.mint, &basket.token_account, &holder.pubkey(), &collateral, 100, 0, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[holder]).await, CoinbackedError::InvalidRecipientAccount);
+
+    // basket can neither be topped up with lamports nor burned partially
+    let instruction = instruction::add_to_balance_of_backing_account(&program_id(), &holder.pubkey(), &basket.mint, BACKING_LAMPORTS, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[holder]).await, CoinbackedError::BackingCollateralMismatch);
+
+    let instruction = instruction::burn_token_and_free_balance(&program_id(), &spl_token::ID, &holder.pubkey(), &basket.mint, &basket.token_account, 100, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[holder]).await, CoinbackedError::BackingCollateralMismatch);
+
+    let instruction = instruction::burn_token_and_free_collateral(&program_id(), &spl_token::ID, &spl_token::ID, &holder.pubkey(), &basket.mint, &basket.token_account, &basket.collateral[0].mint, &basket.collateral[0].token_account, 100, 0, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[holder]).await, CoinbackedError::BackingCollateralMismatch);
+
+    assert_eq!(get_token_amount(&mut context, &basket.token_account).await, SUPPLY);
+}
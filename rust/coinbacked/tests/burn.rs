@@ -0,0 +1,141 @@
+//! Program tests of burning for lamport backed mints
+
+mod common;
+
+use solana_program_test::tokio;
+use solana_sdk::
+{
+    account::Account,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Signer,
+};
+use spl_token_2022::state::{Account as TokenAccount, AccountState};
+
+use coinbacked::
+{
+    error::CoinbackedError,
+    instruction,
+};
+
+use common::*;
+
+#[tokio::test]
+async fn test_burn_with_min_payout()
+{
+    let (mut program_test, admin) = program_test();
+    let (holder, mint, token_account) = add_holder(&mut program_test, &spl_token::ID);
+    let mut context = start(program_test, &admin).await;
+    create_backing(&mut context, &holder, &mint, &token_account).await;
+
+    // 10% of supply frees 10% of backing, protocol fee is deducted from it
+    let net_payout = BACKING_LAMPORTS / 10 - PROTOCOL_FEE;
+
+    let instruction = instruction::burn_token_and_free_balance_with_min_payout(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, net_payout + 1, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&holder]).await, CoinbackedError::PayoutBelowMinimum);
+    assert_eq!(get_token_amount(&mut context, &token_account).await, SUPPLY);
+
+    let lamports_before = get_lamports(&mut context, &holder.pubkey()).await;
+    let instruction = instruction::burn_token_and_free_balance_with_min_payout(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, net_payout, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    assert_eq!(get_token_amount(&mut context, &token_account).await, SUPPLY - 100);
+    assert_eq!(get_lamports(&mut context, &holder.pubkey()).await, lamports_before + net_payout);
+}
+
+#[tokio::test]
+async fn test_burn_min_payout_follows_backing()
+{
+    let (mut program_test, admin) = program_test();
+    let (holder, mint, token_account) = add_holder(&mut program_test, &spl_token::ID);
+    let mut context = start(program_test, &admin).await;
+    create_backing(&mut context, &holder, &mint, &token_account).await;
+
+    // minimum quoted before a burn front-running with exit of half the supply still holds, the per token payout is unchanged
+    let net_payout = BACKING_LAMPORTS / 10 - PROTOCOL_FEE;
+    let instruction = instruction::burn_token_and_free_balance(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 500, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+
+    let instruction = instruction::burn_token_and_free_balance_with_min_payout(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, net_payout, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+
+    // backing drained by a burn of the rest rejects a minimum above what is left
+    let instruction = instruction::burn_token_and_free_balance_with_min_payout(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 400, BACKING_LAMPORTS, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&holder]).await, CoinbackedError::PayoutBelowMinimum);
+    assert_eq!(get_token_amount(&mut context, &token_account).await, 400);
+}
+
+#[tokio::test]
+async fn test_burn_as_delegate_pays_program_owned_token_owner()
+{
+    let (mut program_test, admin) = program_test();
+    let (holder, mint, token_account) = add_holder(&mut program_test, &spl_token::ID);
+    let delegate = add_wallet(&mut program_test);
+
+    // token owner is a vault account of another program
+    let vault = Pubkey::new_unique();
+    program_test.add_account(vault, Account { lamports: Rent::default().minimum_balance(8), data: vec![0; 8], owner: Pubkey::new_unique(), executable: false, rent_epoch: 0 });
+    let vault_token_account = add_token_account_state(&mut program_test, &spl_token::ID, TokenAccount {
+        mint,
+        owner: vault,
+        amount: 100,
+        delegate: Some(delegate.pubkey()).into(),
+        delegated_amount: 100,
+        state: AccountState::Initialized,
+        ..TokenAccount::default()
+    });
+
+    let mut context = start(program_test, &admin).await;
+    create_backing(&mut context, &holder, &mint, &token_account).await;
+
+    // payout can only go to the token owner
+    let instruction = instruction::burn_token_and_free_balance_as_delegate(&program_id(), &spl_token::ID, &delegate.pubkey(), &delegate.pubkey(), &mint, &vault_token_account, 100, 0, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&delegate]).await, CoinbackedError::InvalidRecipientAccount);
+
+    let vault_lamports = get_lamports(&mut context, &vault).await;
+    let instruction = instruction::burn_token_and_free_balance_as_delegate(&program_id(), &spl_token::ID, &delegate.pubkey(), &vault, &mint, &vault_token_account, 100, 0, TOS).unwrap();
+    process(&mut context, &[instruction], &[&delegate]).await.unwrap();
+    assert_eq!(get_token_amount(&mut context, &vault_token_account).await, 0);
+    assert_eq!(get_lamports(&mut context, &vault).await, vault_lamports + BACKING_LAMPORTS / 10 - PROTOCOL_FEE);
+}
+
+#[tokio::test]
+async fn test_burn_and_clean_with_multisig()
+{
+    let (mut program_test, admin) = program_test();
+    let holder = add_wallet(&mut program_test);
+    let signers = [add_wallet(&mut program_test), add_wallet(&mut program_test), add_wallet(&mut program_test)];
+    let recipient = add_wallet(&mut program_test);
+    let multisig = add_multisig(&mut program_test, &spl_token::ID, 2, &[&signers[0], &signers[1], &signers[2]]);
+    let mint = add_mint(&mut program_test, &spl_token::ID, SUPPLY);
+    let token_account = add_token_account(&mut program_test, &spl_token::ID, &mint, &holder.pubkey(), SUPPLY - 100);
+    let multisig_token_account = add_token_account(&mut program_test, &spl_token::ID, &mint, &multisig, 100);
+
+    let mut context = start(program_test, &admin).await;
+    create_backing(&mut context, &holder, &mint, &token_account).await;
+
+    // one signer below threshold of two, signing twice does not count twice
+    let instruction = instruction::burn_token_and_free_balance_with_multisig(&program_id(), &spl_token::ID, &multisig, &[&signers[0].pubkey(), &signers[0].pubkey()], &mint, &multisig_token_account, &recipient.pubkey(), 100, 0, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&signers[0]]).await, CoinbackedError::MultisigThresholdNotMet);
+
+    // multisig cannot receive the payout itself
+    let instruction = instruction::burn_token_and_free_balance_with_multisig(&program_id(), &spl_token::ID, &multisig, &[&signers[0].pubkey(), &signers[2].pubkey()], &mint, &multisig_token_account, &multisig, 100, 0, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&signers[0], &signers[2]]).await, CoinbackedError::InvalidRecipientAccount);
+
+    let recipient_lamports = get_lamports(&mut context, &recipient.pubkey()).await;
+    let instruction = instruction::burn_token_and_free_balance_with_multisig(&program_id(), &spl_token::ID, &multisig, &[&signers[0].pubkey(), &signers[2].pubkey()], &mint, &multisig_token_account, &recipient.pubkey(), 100, 0, TOS).unwrap();
+    process(&mut context, &[instruction], &[&signers[0], &signers[2]]).await.unwrap();
+    assert_eq!(get_token_amount(&mut context, &multisig_token_account).await, 0);
+    assert_eq!(get_lamports(&mut context, &recipient.pubkey()).await, recipient_lamports + BACKING_LAMPORTS / 10 - PROTOCOL_FEE);
+
+    let instruction = instruction::clean_accounts_after_burning_with_multisig(&program_id(), &spl_token::ID, &multisig, &[&signers[1].pubkey()], &mint, &multisig_token_account, &recipient.pubkey()).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&signers[1]]).await, CoinbackedError::MultisigThresholdNotMet);
+
+    // empty token account is closed to the destination, backing of remaining supply stays open
+    let recipient_lamports = get_lamports(&mut context, &recipient.pubkey()).await;
+    let token_account_lamports = get_lamports(&mut context, &multisig_token_account).await;
+    let instruction = instruction::clean_accounts_after_burning_with_multisig(&program_id(), &spl_token::ID, &multisig, &[&signers[1].pubkey(), &signers[2].pubkey()], &mint, &multisig_token_account, &recipient.pubkey()).unwrap();
+    process(&mut context, &[instruction], &[&signers[1], &signers[2]]).await.unwrap();
+    assert!(context.banks_client.get_account(multisig_token_account).await.unwrap().is_none());
+    assert_eq!(get_lamports(&mut context, &recipient.pubkey()).await, recipient_lamports + token_account_lamports);
+    assert!(context.banks_client.get_account(instruction::find_backing_account_address(&mint, &program_id()).0).await.unwrap().is_some());
+}
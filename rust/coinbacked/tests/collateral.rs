@@ -0,0 +1,107 @@
+//! Program tests of burning for collateral backed mints
+
+mod common;
+
+use solana_program_test::tokio;
+use solana_sdk::
+{
+    instruction::AccountMeta,
+    signature::Signer,
+    system_instruction,
+};
+
+use coinbacked::
+{
+    error::CoinbackedError,
+    instruction::{self, BasketCollateral},
+};
+
+use common::*;
+
+#[tokio::test]
+async fn test_burn_collateral_ignores_lamport_surplus()
+{
+    let (mut program_test, admin) = program_test();
+    let (holder, mint, token_account) = add_holder(&mut program_test, &spl_token::ID);
+    let collateral_mint = add_mint(&mut program_test, &spl_token::ID, COLLATERAL);
+    let collateral_account = add_token_account(&mut program_test, &spl_token::ID, &collateral_mint, &holder.pubkey(), COLLATERAL);
+    let mut context = start(program_test, &admin).await;
+    create_collateral_backing(&mut context, &holder, &mint, &token_account, &collateral_mint, &collateral_account).await;
+
+    // anyone can send lamports to the backing account
+    let backing = instruction::find_backing_account_address(&mint, &program_id()).0;
+    let instruction = system_instruction::transfer(&context.payer.pubkey(), &backing, 1);
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    let lamports_before = get_lamports(&mut context, &holder.pubkey()).await;
+    let instruction = instruction::burn_token_and_free_collateral(&program_id(), &spl_token::ID, &spl_token::ID, &holder.pubkey(), &mint, &token_account, &collateral_mint, &collateral_account, 100, COLLATERAL / 10, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    assert_eq!(get_token_amount(&mut context, &token_account).await, SUPPLY - 100);
+    assert_eq!(get_token_amount(&mut context, &collateral_account).await, COLLATERAL / 10);
+    assert_eq!(get_lamports(&mut context, &holder.pubkey()).await, lamports_before - PROTOCOL_FEE);
+}
+
+#[tokio::test]
+async fn test_burn_collateral_with_multisig()
+{
+    let (mut program_test, admin) = program_test();
+    let holder = add_wallet(&mut program_test);
+    let signers = [add_wallet(&mut program_test), add_wallet(&mut program_test), add_wallet(&mut program_test)];
+    let multisig = add_multisig(&mut program_test, &spl_token::ID, 2, &[&signers[0], &signers[1], &signers[2]]);
+    let mint = add_mint(&mut program_test, &spl_token::ID, SUPPLY);
+    let token_account = add_token_account(&mut program_test, &spl_token::ID, &mint, &holder.pubkey(), SUPPLY - 100);
+    let multisig_token_account = add_token_account(&mut program_test, &spl_token::ID, &mint, &multisig, 100);
+    let collateral_mint = add_mint(&mut program_test, &spl_token::ID, COLLATERAL);
+    let collateral_account = add_token_account(&mut program_test, &spl_token::ID, &collateral_mint, &holder.pubkey(), COLLATERAL);
+    let multisig_collateral_account = add_token_account(&mut program_test, &spl_token::ID, &collateral_mint, &multisig, 0);
+    let mut context = start(program_test, &admin).await;
+    create_collateral_backing(&mut context, &holder, &mint, &token_account, &collateral_mint, &collateral_account).await;
+
+    let instruction = instruction::burn_token_and_free_collateral_with_multisig(&program_id(), &spl_token::ID, &spl_token::ID, &multisig, &[&signers[1].pubkey()], &mint, &multisig_token_account, &collateral_mint, &multisig_collateral_account, 100, 0, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&signers[1]]).await, CoinbackedError::MultisigThresholdNotMet);
+
+    // first signer pays the protocol fee
+    let lamports_before = get_lamports(&mut context, &signers[1].pubkey()).await;
+    let instruction = instruction::burn_token_and_free_collateral_with_multisig(&program_id(), &spl_token::ID, &spl_token::ID, &multisig, &[&signers[1].pubkey(), &signers[2].pubkey()], &mint, &multisig_token_account, &collateral_mint, &multisig_collateral_account, 100, 0, TOS).unwrap();
+    process(&mut context, &[instruction], &[&signers[1], &signers[2]]).await.unwrap();
+    assert_eq!(get_token_amount(&mut context, &multisig_token_account).await, 0);
+    assert_eq!(get_token_amount(&mut context, &multisig_collateral_account).await, COLLATERAL / 10);
+    assert_eq!(get_lamports(&mut context, &signers[1].pubkey()).await, lamports_before - PROTOCOL_FEE);
+}
+
+#[tokio::test]
+async fn test_burn_basket_with_multisig()
+{
+    let (mut program_test, admin) = program_test();
+    let holder = add_wallet(&mut program_test);
+    let signers = [add_wallet(&mut program_test), add_wallet(&mut program_test)];
+    let multisig = add_multisig(&mut program_test, &spl_token::ID, 2, &[&signers[0], &signers[1]]);
+    let mint = add_mint(&mut program_test, &spl_token::ID, SUPPLY);
+    let token_account = add_token_account(&mut program_test, &spl_token::ID, &mint, &holder.pubkey(), SUPPLY - 100);
+    let multisig_token_account = add_token_account(&mut program_test, &spl_token::ID, &mint, &multisig, 100);
+    let collateral_mint = add_mint(&mut program_test, &spl_token::ID, COLLATERAL);
+    let collateral_account = add_token_account(&mut program_test, &spl_token::ID, &collateral_mint, &holder.pubkey(), COLLATERAL);
+    let multisig_collateral_account = add_token_account(&mut program_test, &spl_token::ID, &collateral_mint, &multisig, 0);
+    let mut context = start(program_test, &admin).await;
+
+    let basket = [BasketCollateral { mint: collateral_mint, token_program_id: spl_token::ID, token_account: collateral_account, amount: COLLATERAL }];
+    let instruction = instruction::create_basket_backing_account(&program_id(), &holder.pubkey(), &mint, &token_account, BACKING_LAMPORTS, &basket, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+
+    let basket = [BasketCollateral { mint: collateral_mint, token_program_id: spl_token::ID, token_account: multisig_collateral_account, amount: 0 }];
+    let instruction = instruction::burn_token_and_free_basket_with_multisig(&program_id(), &spl_token::ID, &multisig, &[&signers[1].pubkey()], &mint, &multisig_token_account, &basket, 100, 0, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&signers[1]]).await, CoinbackedError::MultisigThresholdNotMet);
+
+    // lamports cannot go to the multisig itself
+    let mut instruction = instruction::burn_token_and_free_basket_with_multisig(&program_id(), &spl_token::ID, &multisig, &[&signers[1].pubkey(), &signers[0].pubkey()], &mint, &multisig_token_account, &basket, 100, 0, TOS).unwrap();
+    instruction.accounts[6] = AccountMeta::new(multisig, false);
+    assert_error(process(&mut context, &[instruction], &[&signers[1], &signers[0]]).await, CoinbackedError::InvalidRecipientAccount);
+
+    // first signer pays the protocol fee and receives the lamports, collateral goes to the multisig
+    let lamports_before = get_lamports(&mut context, &signers[1].pubkey()).await;
+    let instruction = instruction::burn_token_and_free_basket_with_multisig(&program_id(), &spl_token::ID, &multisig, &[&signers[1].pubkey(), &signers[0].pubkey()], &mint, &multisig_token_account, &basket, 100, 0, TOS).unwrap();
+    process(&mut context, &[instruction], &[&signers[1], &signers[0]]).await.unwrap();
+    assert_eq!(get_token_amount(&mut context, &multisig_token_account).await, 0);
+    assert_eq!(get_token_amount(&mut context, &multisig_collateral_account).await, COLLATERAL / 10);
+    assert_eq!(get_lamports(&mut context, &signers[1].pubkey()).await, lamports_before + BACKING_LAMPORTS / 10 - PROTOCOL_FEE);
+}
@@ -0,0 +1,223 @@
+//! Setup shared by the program tests
+#![allow(dead_code)]
+
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+
+use solana_sdk::
+{
+    account::Account,
+    bpf_loader_upgradeable,
+    instruction::{Instruction, InstructionError},
+    native_loader,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+use spl_token_2022::
+{
+    extension::{immutable_owner::ImmutableOwner, Extension, ExtensionType, StateWithExtensions, StateWithExtensionsMut},
+    state::{Account as TokenAccount, AccountState, Mint, Multisig},
+};
+
+use coinbacked::
+{
+    error::CoinbackedError,
+    instruction,
+};
+
+pub const PROTOCOL_FEE: u64 = 5_000;
+pub const SUPPLY: u64 = 1_000;
+pub const BACKING_LAMPORTS: u64 = 1_000_000_000;
+pub const COLLATERAL: u64 = 1_000_000;
+pub const WALLET_LAMPORTS: u64 = 10 * BACKING_LAMPORTS;
+pub const TOS: &str = "signed";
+
+pub fn program_id() -> Pubkey
+{
+    coinbacked::id()
+}
+
+pub fn treasury() -> Pubkey
+{
+    instruction::find_protocol_treasury_account_address(&program_id()).0
+}
+
+pub fn config() -> Pubkey
+{
+    instruction::find_config_account_address(&program_id()).0
+}
+
+/// Program with an upgrade authority, which becomes admin once `start` initialised config and treasury.
+pub fn program_test() -> (ProgramTest, Keypair)
+{
+    let program_id = program_id();
+    let mut program_test = ProgramTest::new("coinbacked", program_id, processor!(coinbacked::processor::process_instruction));
+    let admin = add_wallet(&mut program_test);
+
+    // program account points to its executable data like an upgradeable program, native loader keeps running the processor
+    let program_executable_data = instruction::find_program_executable_data_account_address(&program_id).0;
+    let mut data = vec![2, 0, 0, 0];
+    data.extend_from_slice(program_executable_data.as_ref());
+    program_test.add_account(program_id, Account { lamports: 1, data, owner: native_loader::id(), executable: true, rent_epoch: 0 });
+
+    // executable data header: state, deployment slot, upgrade authority
+    let mut data = vec![3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+    data.extend_from_slice(admin.pubkey().as_ref());
+    program_test.add_account(program_executable_data, Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: bpf_loader_upgradeable::id(), executable: false, rent_epoch: 0 });
+
+    (program_test, admin)
+}
+
+/// Starts the program with config charging the default flat fees and an empty treasury.
+pub async fn start(program_test: ProgramTest, admin: &Keypair) -> ProgramTestContext
+{
+    let mut context = program_test.start_with_context().await;
+
+    let instructions = [
+        instruction::admin_init_config_account(&program_id(), &admin.pubkey(), PROTOCOL_FEE, PROTOCOL_FEE, PROTOCOL_FEE, PROTOCOL_FEE, 0).unwrap(),
+        instruction::admin_create_treasury_account(&program_id(), &admin.pubkey()).unwrap(),
+    ];
+    process(&mut context, &instructions, &[admin]).await.unwrap();
+
+    context
+}
+
+pub fn add_wallet(program_test: &mut ProgramTest) -> Keypair
+{
+    let wallet = Keypair::new();
+    program_test.add_account(wallet.pubkey(), Account { lamports: WALLET_LAMPORTS, ..Account::default() });
+
+    wallet
+}
+
+pub fn add_mint(program_test: &mut ProgramTest, token_program_id: &Pubkey, supply: u64) -> Pubkey
+{
+    let mint = Pubkey::new_unique();
+    let mut data = vec![0u8; Mint::LEN];
+    Mint { supply, decimals: 0, is_initialized: true, ..Mint::default() }.pack_into_slice(&mut data);
+    program_test.add_account(mint, Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: *token_program_id, executable: false, rent_epoch: 0 });
+
+    mint
+}
+
+pub fn add_token_account(program_test: &mut ProgramTest, token_program_id: &Pubkey, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey
+{
+    add_token_account_state(program_test, token_program_id, TokenAccount { mint: *mint, owner: *owner, amount, state: AccountState::Initialized, ..TokenAccount::default() })
+}
+
+pub fn add_token_account_state(program_test: &mut ProgramTest, token_program_id: &Pubkey, state: TokenAccount) -> Pubkey
+{
+    let token_account = Pubkey::new_unique();
+    let mut data = vec![0u8; TokenAccount::LEN];
+    state.pack_into_slice(&mut data);
+    program_test.add_account(token_account, Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: *token_program_id, executable: false, rent_epoch: 0 });
+
+    token_account
+}
+
+/// Token-2022 mint carrying extension `V` in its default state.
+pub fn add_mint_with_extension<V: Extension>(program_test: &mut ProgramTest, supply: u64) -> Pubkey
+{
+    let mut data = vec![0u8; ExtensionType::get_account_len::<Mint>(&[V::TYPE])];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    state.init_extension::<V>(true).unwrap();
+    state.base = Mint { supply, decimals: 0, is_initialized: true, ..Mint::default() };
+    state.pack_base();
+    state.init_account_type().unwrap();
+
+    let mint = Pubkey::new_unique();
+    program_test.add_account(mint, Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: spl_token_2022::ID, executable: false, rent_epoch: 0 });
+
+    mint
+}
+
+/// Token-2022 account with immutable owner, as created by the associated token account program.
+pub fn add_token_2022_account(program_test: &mut ProgramTest, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey
+{
+    let mut data = vec![0u8; ExtensionType::get_account_len::<TokenAccount>(&[ExtensionType::ImmutableOwner])];
+    let mut state = StateWithExtensionsMut::<TokenAccount>::unpack_uninitialized(&mut data).unwrap();
+    state.init_extension::<ImmutableOwner>(true).unwrap();
+    state.base = TokenAccount { mint: *mint, owner: *owner, amount, state: AccountState::Initialized, ..TokenAccount::default() };
+    state.pack_base();
+    state.init_account_type().unwrap();
+
+    let token_account = Pubkey::new_unique();
+    program_test.add_account(token_account, Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: spl_token_2022::ID, executable: false, rent_epoch: 0 });
+
+    token_account
+}
+
+/// SPL multisig requiring `m` of the given signers.
+pub fn add_multisig(program_test: &mut ProgramTest, token_program_id: &Pubkey, m: u8, signers: &[&Keypair]) -> Pubkey
+{
+    let mut multisig = Multisig { m, n: signers.len() as u8, is_initialized: true, ..Multisig::default() };
+    for (position, signer) in signers.iter().enumerate()
+    {
+        multisig.signers[position] = signer.pubkey();
+    }
+
+    let key = Pubkey::new_unique();
+    let mut data = vec![0u8; Multisig::LEN];
+    multisig.pack_into_slice(&mut data);
+    program_test.add_account(key, Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: *token_program_id, executable: false, rent_epoch: 0 });
+
+    key
+}
+
+/// Holder of the whole supply of a new mint: holder, mint and token account.
+pub fn add_holder(program_test: &mut ProgramTest, token_program_id: &Pubkey) -> (Keypair, Pubkey, Pubkey)
+{
+    let holder = add_wallet(program_test);
+    let mint = add_mint(program_test, token_program_id, SUPPLY);
+    let token_account = add_token_account(program_test, token_program_id, &mint, &holder.pubkey(), SUPPLY);
+
+    (holder, mint, token_account)
+}
+
+/// Processes instructions in one transaction, fees are paid by the context payer so balances of signers only move by the instructions.
+pub async fn process(context: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError>
+{
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(instructions, Some(&context.payer.pubkey()), &all_signers, blockhash);
+
+    context.banks_client.process_transaction(transaction).await
+}
+
+pub fn assert_error(result: Result<(), BanksClientError>, error: CoinbackedError)
+{
+    match result.unwrap_err().unwrap()
+    {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => assert_eq!(code, error as u32),
+        other => panic!("Unexpected error {:?}, expected {:?}", other, error),
+    }
+}
+
+pub async fn get_lamports(context: &mut ProgramTestContext, key: &Pubkey) -> u64
+{
+    context.banks_client.get_balance(*key).await.unwrap()
+}
+
+pub async fn get_token_amount(context: &mut ProgramTestContext, key: &Pubkey) -> u64
+{
+    let account = context.banks_client.get_account(*key).await.unwrap().unwrap();
+    StateWithExtensions::<TokenAccount>::unpack(&account.data).unwrap().base.amount
+}
+
+/// Backs the mint of holder with `BACKING_LAMPORTS`.
+pub async fn create_backing(context: &mut ProgramTestContext, holder: &Keypair, mint: &Pubkey, token_account: &Pubkey)
+{
+    let instruction = instruction::create_backing_account(&program_id(), &holder.pubkey(), mint, token_account, BACKING_LAMPORTS, TOS).unwrap();
+    process(context, &[instruction], &[holder]).await.unwrap();
+}
+
+/// Backs the mint of holder with `COLLATERAL` taken from the collateral account of holder.
+pub async fn create_collateral_backing(context: &mut ProgramTestContext, holder: &Keypair, mint: &Pubkey, token_account: &Pubkey, collateral_mint: &Pubkey, collateral_account: &Pubkey)
+{
+    let instruction = instruction::create_collateral_backing_account(&program_id(), &spl_token::ID, &holder.pubkey(), mint, token_account, collateral_mint, collateral_account, COLLATERAL, TOS).unwrap();
+    process(context, &[instruction], &[holder]).await.unwrap();
+}
@@ -0,0 +1,96 @@
+//! Program tests of protocol config handling
+
+mod common;
+
+use solana_program_test::tokio;
+use solana_sdk::
+{
+    account::Account,
+    pubkey::Pubkey,
+    signature::Signer,
+};
+
+use coinbacked::
+{
+    error::CoinbackedError,
+    instruction::{self, CO_PAUSE_BURN, CO_PAUSE_CREATE},
+};
+
+use common::*;
+
+const BURN_FEE: u64 = 3 * PROTOCOL_FEE;
+
+#[tokio::test]
+async fn test_config_cannot_be_skipped()
+{
+    let (mut program_test, admin) = program_test();
+    let (holder, mint, token_account) = add_holder(&mut program_test, &spl_token::ID);
+    let mut context = start(program_test, &admin).await;
+    create_backing(&mut context, &holder, &mint, &token_account).await;
+
+    let instruction = instruction::admin_update_config_account(&program_id(), &admin.pubkey(), PROTOCOL_FEE, PROTOCOL_FEE, PROTOCOL_FEE, BURN_FEE, 0).unwrap();
+    process(&mut context, &[instruction], &[&admin]).await.unwrap();
+
+    // omitting the config would fall back to default fees
+    let mut instruction = instruction::burn_token_and_free_balance(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, TOS).unwrap();
+    instruction.accounts.retain(|account| account.pubkey.ne(&config()));
+    assert_error(process(&mut context, &[instruction], &[&holder]).await, CoinbackedError::ConfigAccountMissing);
+
+    let lamports_before = get_lamports(&mut context, &holder.pubkey()).await;
+    let instruction = instruction::burn_token_and_free_balance(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    assert_eq!(get_lamports(&mut context, &holder.pubkey()).await, lamports_before + BACKING_LAMPORTS / 10 - BURN_FEE);
+}
+
+#[tokio::test]
+async fn test_config_defaults_only_when_uninitialised()
+{
+    let (mut program_test, admin) = program_test();
+    let (holder, mint, token_account) = add_holder(&mut program_test, &spl_token::ID);
+    let mut context = start(program_test, &admin).await;
+    create_backing(&mut context, &holder, &mint, &token_account).await;
+    let config_account = context.banks_client.get_account(config()).await.unwrap().unwrap();
+
+    // config data held by another program is rejected
+    context.set_account(&config(), &Account { owner: Pubkey::new_unique(), ..config_account.clone() }.into());
+    let instruction = instruction::burn_token_and_free_balance(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&holder]).await, CoinbackedError::InvalidConfigAccountData);
+
+    // config never initialised by the admin applies the default fee
+    context.set_account(&config(), &Account::default().into());
+    let lamports_before = get_lamports(&mut context, &holder.pubkey()).await;
+    let instruction = instruction::burn_token_and_free_balance(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    assert_eq!(get_lamports(&mut context, &holder.pubkey()).await, lamports_before + BACKING_LAMPORTS / 10 - PROTOCOL_FEE);
+}
+
+#[tokio::test]
+async fn test_pause_cannot_be_skipped()
+{
+    let (mut program_test, admin) = program_test();
+    let (holder, mint, token_account) = add_holder(&mut program_test, &spl_token::ID);
+    let other_mint = add_mint(&mut program_test, &spl_token::ID, SUPPLY);
+    let other_token_account = add_token_account(&mut program_test, &spl_token::ID, &other_mint, &holder.pubkey(), SUPPLY);
+    let mut context = start(program_test, &admin).await;
+    create_backing(&mut context, &holder, &mint, &token_account).await;
+
+    let instruction = instruction::admin_set_pause(&program_id(), &admin.pubkey(), CO_PAUSE_CREATE | CO_PAUSE_BURN, 1_000).unwrap();
+    process(&mut context, &[instruction], &[&admin]).await.unwrap();
+
+    let create = instruction::create_backing_account(&program_id(), &holder.pubkey(), &other_mint, &other_token_account, BACKING_LAMPORTS, TOS).unwrap();
+    let burn = instruction::burn_token_and_free_balance(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, TOS).unwrap();
+    for instruction in [&create, &burn]
+    {
+        assert_error(process(&mut context, std::slice::from_ref(instruction), &[&holder]).await, CoinbackedError::OperationPaused);
+
+        let mut instruction = instruction.clone();
+        instruction.accounts.retain(|account| account.pubkey.ne(&config()));
+        assert_error(process(&mut context, &[instruction], &[&holder]).await, CoinbackedError::ConfigAccountMissing);
+    }
+
+    // burn pause lapses after its window, create stays paused
+    context.warp_to_slot(1_100).unwrap();
+    assert_error(process(&mut context, &[create], &[&holder]).await, CoinbackedError::OperationPaused);
+    process(&mut context, &[burn], &[&holder]).await.unwrap();
+    assert_eq!(get_token_amount(&mut context, &token_account).await, SUPPLY - 100);
+}
@@ -0,0 +1,127 @@
+//! Program tests of protocol fee rates
+
+mod common;
+
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+
+use coinbacked::
+{
+    error::CoinbackedError,
+    instruction::{self, BasketCollateral},
+};
+
+use common::*;
+
+// 10% of additions and 5% of payouts, well above the flat fee for the amounts below
+const ADD_FEE_BPS: u16 = 1_000;
+const BURN_FEE_BPS: u16 = 500;
+
+fn add_fee(amount: u64) -> u64
+{
+    amount / 10
+}
+
+fn burn_fee(amount: u64) -> u64
+{
+    amount / 20
+}
+
+#[tokio::test]
+async fn test_fee_rate_applies_to_every_add_and_burn()
+{
+    let (mut program_test, admin) = program_test();
+    let (holder, mint, token_account) = add_holder(&mut program_test, &spl_token::ID);
+    let collateral_backed_mint = add_mint(&mut program_test, &spl_token::ID, SUPPLY);
+    let collateral_backed_token_account = add_token_account(&mut program_test, &spl_token::ID, &collateral_backed_mint, &holder.pubkey(), SUPPLY);
+    let basket_mint = add_mint(&mut program_test, &spl_token::ID, SUPPLY);
+    let basket_token_account = add_token_account(&mut program_test, &spl_token::ID, &basket_mint, &holder.pubkey(), SUPPLY);
+    let collateral_mint = add_mint(&mut program_test, &spl_token::ID, 3 * COLLATERAL);
+    let collateral_account = add_token_account(&mut program_test, &spl_token::ID, &collateral_mint, &holder.pubkey(), 3 * COLLATERAL);
+    let recipient = add_wallet(&mut program_test);
+    let mut context = start(program_test, &admin).await;
+
+    create_backing(&mut context, &holder, &mint, &token_account).await;
+    create_collateral_backing(&mut context, &holder, &collateral_backed_mint, &collateral_backed_token_account, &collateral_mint, &collateral_account).await;
+    let basket = [BasketCollateral { mint: collateral_mint, token_program_id: spl_token::ID, token_account: collateral_account, amount: COLLATERAL }];
+    let instruction = instruction::create_basket_backing_account(&program_id(), &holder.pubkey(), &basket_mint, &basket_token_account, BACKING_LAMPORTS, &basket, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+
+    let instruction = instruction::admin_update_config_fee_rates(&program_id(), &admin.pubkey(), BURN_FEE_BPS, 0, ADD_FEE_BPS, 0).unwrap();
+    process(&mut context, &[instruction], &[&admin]).await.unwrap();
+
+    // add lamports, backing doubles
+    let lamports_before = get_lamports(&mut context, &holder.pubkey()).await;
+    let instruction = instruction::add_to_balance_of_backing_account(&program_id(), &holder.pubkey(), &mint, BACKING_LAMPORTS, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    assert_eq!(get_lamports(&mut context, &holder.pubkey()).await, lamports_before - BACKING_LAMPORTS - add_fee(BACKING_LAMPORTS));
+
+    // add collateral, collateral doubles
+    let lamports_before = get_lamports(&mut context, &holder.pubkey()).await;
+    let instruction = instruction::add_collateral_to_balance_of_backing_account(&program_id(), &spl_token::ID, &holder.pubkey(), &collateral_backed_mint, &collateral_mint, &collateral_account, COLLATERAL, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    assert_eq!(get_lamports(&mut context, &holder.pubkey()).await, lamports_before - add_fee(COLLATERAL));
+
+    // every burn of 100 tokens pays out a tenth of the doubled backing
+    let payout = 2 * BACKING_LAMPORTS / 10;
+    let burns = [
+        instruction::burn_token_and_free_balance(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, TOS).unwrap(),
+        instruction::burn_token_and_free_balance_with_min_payout(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, 0, TOS).unwrap(),
+    ];
+    for burn in burns
+    {
+        let lamports_before = get_lamports(&mut context, &holder.pubkey()).await;
+        process(&mut context, &[burn], &[&holder]).await.unwrap();
+        assert_eq!(get_lamports(&mut context, &holder.pubkey()).await, lamports_before + payout - burn_fee(payout));
+    }
+
+    let lamports_before = get_lamports(&mut context, &recipient.pubkey()).await;
+    let instruction = instruction::burn_token_and_free_balance_to_recipient(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, &recipient.pubkey(), 100, 0, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    assert_eq!(get_lamports(&mut context, &recipient.pubkey()).await, lamports_before + payout - burn_fee(payout));
+
+    // collateral burn, fee rate applies to the collateral paid out
+    let lamports_before = get_lamports(&mut context, &holder.pubkey()).await;
+    let instruction = instruction::burn_token_and_free_collateral(&program_id(), &spl_token::ID, &spl_token::ID, &holder.pubkey(), &collateral_backed_mint, &collateral_backed_token_account, &collateral_mint, &collateral_account, 100, 0, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    assert_eq!(get_lamports(&mut context, &holder.pubkey()).await, lamports_before - burn_fee(2 * COLLATERAL / 10));
+
+    // basket burn, fee rate applies to the lamports paid out
+    let lamports_before = get_lamports(&mut context, &holder.pubkey()).await;
+    let basket = [BasketCollateral { amount: 0, ..basket[0] }];
+    let instruction = instruction::burn_token_and_free_basket(&program_id(), &spl_token::ID, &holder.pubkey(), &basket_mint, &basket_token_account, &holder.pubkey(), &basket, 100, 0, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    assert_eq!(get_lamports(&mut context, &holder.pubkey()).await, lamports_before + BACKING_LAMPORTS / 10 - burn_fee(BACKING_LAMPORTS / 10));
+}
+
+#[tokio::test]
+async fn test_creator_share_requires_creator_account()
+{
+    let (mut program_test, admin) = program_test();
+    let (holder, mint, token_account) = add_holder(&mut program_test, &spl_token::ID);
+    let creator = add_wallet(&mut program_test);
+    let mut context = start(program_test, &admin).await;
+
+    let instruction = instruction::create_backing_account_with_creator(&program_id(), &holder.pubkey(), &mint, &token_account, &creator.pubkey(), BACKING_LAMPORTS, 0, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    let instruction = instruction::admin_update_creator_fee_share(&program_id(), &admin.pubkey(), 2_000).unwrap();
+    process(&mut context, &[instruction], &[&admin]).await.unwrap();
+
+    // omitted or other creator would leave the share with the treasury
+    let instruction = instruction::burn_token_and_free_balance_with_min_payout(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, 0, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&holder]).await, CoinbackedError::InvalidCreatorAccount);
+    let instruction = instruction::burn_token_and_free_balance_with_creator(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, &holder.pubkey(), 100, 0, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&holder]).await, CoinbackedError::InvalidCreatorAccount);
+    let instruction = instruction::add_to_balance_of_backing_account(&program_id(), &holder.pubkey(), &mint, BACKING_LAMPORTS, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&holder]).await, CoinbackedError::InvalidCreatorAccount);
+
+    let creator_lamports = get_lamports(&mut context, &creator.pubkey()).await;
+    let treasury_lamports = get_lamports(&mut context, &treasury()).await;
+    let instructions = [
+        instruction::burn_token_and_free_balance_with_creator(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, &creator.pubkey(), 100, 0, TOS).unwrap(),
+        instruction::add_to_balance_of_backing_account_with_creator(&program_id(), &holder.pubkey(), &mint, &creator.pubkey(), BACKING_LAMPORTS, TOS).unwrap(),
+    ];
+    process(&mut context, &instructions, &[&holder]).await.unwrap();
+    assert_eq!(get_lamports(&mut context, &creator.pubkey()).await, creator_lamports + 2 * PROTOCOL_FEE / 5);
+    assert_eq!(get_lamports(&mut context, &treasury()).await, treasury_lamports + 2 * (PROTOCOL_FEE - PROTOCOL_FEE / 5));
+}
@@ -0,0 +1,67 @@
+//! Program tests of backing Token-2022 mints
+
+mod common;
+
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+use spl_token_2022::extension::
+{
+    interest_bearing_mint::InterestBearingConfig,
+    mint_close_authority::MintCloseAuthority,
+    non_transferable::NonTransferable,
+    permanent_delegate::PermanentDelegate,
+};
+
+use coinbacked::
+{
+    error::CoinbackedError,
+    instruction,
+};
+
+use common::*;
+
+#[tokio::test]
+async fn test_back_and_burn_token_2022_mint()
+{
+    let (mut program_test, admin) = program_test();
+    let holder = add_wallet(&mut program_test);
+    let mint = add_mint_with_extension::<InterestBearingConfig>(&mut program_test, SUPPLY);
+    let token_account = add_token_2022_account(&mut program_test, &mint, &holder.pubkey(), SUPPLY);
+    let mut context = start(program_test, &admin).await;
+    create_backing(&mut context, &holder, &mint, &token_account).await;
+
+    // mint of other token program is rejected
+    let instruction = instruction::burn_token_and_free_balance(&program_id(), &spl_token::ID, &holder.pubkey(), &mint, &token_account, 100, TOS).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&holder]).await, CoinbackedError::InvalidTokenProgram);
+
+    let lamports_before = get_lamports(&mut context, &holder.pubkey()).await;
+    let instruction = instruction::burn_token_and_free_balance(&program_id(), &spl_token_2022::ID, &holder.pubkey(), &mint, &token_account, 100, TOS).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+    assert_eq!(get_token_amount(&mut context, &token_account).await, SUPPLY - 100);
+    assert_eq!(get_lamports(&mut context, &holder.pubkey()).await, lamports_before + BACKING_LAMPORTS / 10 - PROTOCOL_FEE);
+
+    let instruction = instruction::validate_backing_account(&program_id(), &holder.pubkey(), &mint).unwrap();
+    process(&mut context, &[instruction], &[&holder]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_refuse_mints_breaking_payout()
+{
+    let (mut program_test, admin) = program_test();
+    let holder = add_wallet(&mut program_test);
+    let mints = [
+        add_mint_with_extension::<PermanentDelegate>(&mut program_test, SUPPLY),
+        add_mint_with_extension::<NonTransferable>(&mut program_test, SUPPLY),
+        add_mint_with_extension::<MintCloseAuthority>(&mut program_test, SUPPLY),
+    ];
+    let token_accounts: Vec<_> = mints.iter()
+        .map(|mint| add_token_2022_account(&mut program_test, mint, &holder.pubkey(), SUPPLY))
+        .collect();
+    let mut context = start(program_test, &admin).await;
+
+    for (mint, token_account) in mints.iter().zip(token_accounts.iter())
+    {
+        let instruction = instruction::create_backing_account(&program_id(), &holder.pubkey(), mint, token_account, BACKING_LAMPORTS, TOS).unwrap();
+        assert_error(process(&mut context, &[instruction], &[&holder]).await, CoinbackedError::UnsupportedMintExtension);
+    }
+}
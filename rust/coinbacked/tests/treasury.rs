@@ -0,0 +1,109 @@
+//! Program tests of treasury withdrawal proposals
+
+mod common;
+
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::
+{
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+
+use coinbacked::
+{
+    error::CoinbackedError,
+    instruction,
+};
+
+use common::*;
+
+const DELAY_SLOTS: u64 = 100;
+const EXPIRY_SLOT: u64 = 1_000;
+const WITHDRAWAL: u64 = BACKING_LAMPORTS;
+
+/// Starts the program with a funded treasury withdrawn from by 2 of 3 signers after `DELAY_SLOTS`.
+async fn start_with_treasury_signers() -> (ProgramTestContext, [Keypair; 3])
+{
+    let (mut program_test, admin) = program_test();
+    let signers = [add_wallet(&mut program_test), add_wallet(&mut program_test), add_wallet(&mut program_test)];
+    let mut context = start(program_test, &admin).await;
+
+    let signer_pubkeys: Vec<_> = signers.iter().map(|signer| signer.pubkey()).collect();
+    let instructions = [
+        instruction::admin_update_treasury_limits(&program_id(), &admin.pubkey(), DELAY_SLOTS, 0).unwrap(),
+        instruction::admin_set_treasury_signers(&program_id(), &admin.pubkey(), 2, &signer_pubkeys).unwrap(),
+    ];
+    process(&mut context, &instructions, &[&admin]).await.unwrap();
+
+    // funded by transfer, lamports set directly break capitalization once slots are warped
+    let instruction = system_instruction::transfer(&context.payer.pubkey(), &treasury(), 10 * WITHDRAWAL);
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    (context, signers)
+}
+
+#[tokio::test]
+async fn test_withdrawal_requires_threshold_and_delay()
+{
+    let (mut context, [proposer, approver, _]) = start_with_treasury_signers().await;
+    let recipient = Keypair::new().pubkey();
+    let proposal = instruction::find_withdrawal_proposal_address(1, &program_id()).0;
+
+    let instruction = instruction::propose_treasury_withdrawal(&program_id(), &proposer.pubkey(), &recipient, 1, WITHDRAWAL, EXPIRY_SLOT).unwrap();
+    process(&mut context, &[instruction], &[&proposer]).await.unwrap();
+    let proposal_rent = get_lamports(&mut context, &proposal).await;
+    assert_eq!(get_lamports(&mut context, &proposer.pubkey()).await, WALLET_LAMPORTS - proposal_rent);
+
+    // proposing counts as one of two approvals
+    let execute = instruction::execute_treasury_withdrawal(&program_id(), &proposer.pubkey(), &recipient, 1).unwrap();
+    assert_error(process(&mut context, std::slice::from_ref(&execute), &[]).await, CoinbackedError::MultisigThresholdNotMet);
+
+    let instruction = instruction::approve_treasury_withdrawal(&program_id(), &approver.pubkey(), 1).unwrap();
+    process(&mut context, &[instruction], &[&approver]).await.unwrap();
+
+    context.warp_to_slot(DELAY_SLOTS / 2).unwrap();
+    assert_error(process(&mut context, std::slice::from_ref(&execute), &[]).await, CoinbackedError::TreasuryWithdrawalDelayed);
+
+    let treasury_lamports = get_lamports(&mut context, &treasury()).await;
+    context.warp_to_slot(2 * DELAY_SLOTS).unwrap();
+    process(&mut context, &[execute], &[]).await.unwrap();
+
+    assert_eq!(get_lamports(&mut context, &recipient).await, WITHDRAWAL);
+    assert_eq!(get_lamports(&mut context, &treasury()).await, treasury_lamports - WITHDRAWAL);
+    assert_eq!(get_lamports(&mut context, &proposer.pubkey()).await, WALLET_LAMPORTS);
+    assert!(context.banks_client.get_account(proposal).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_expired_withdrawal_can_be_closed()
+{
+    let (mut context, [proposer, approver, _]) = start_with_treasury_signers().await;
+    let recipient = Keypair::new().pubkey();
+    let closer = Keypair::new();
+    let proposal = instruction::find_withdrawal_proposal_address(1, &program_id()).0;
+
+    let instruction = instruction::propose_treasury_withdrawal(&program_id(), &proposer.pubkey(), &recipient, 1, WITHDRAWAL, DELAY_SLOTS).unwrap();
+    process(&mut context, &[instruction], &[&proposer]).await.unwrap();
+
+    // closable only once it can no longer be executed
+    let close = instruction::close_expired_treasury_withdrawal(&program_id(), &closer.pubkey(), &proposer.pubkey(), 1).unwrap();
+    assert_error(process(&mut context, std::slice::from_ref(&close), &[&closer]).await, CoinbackedError::WithdrawalProposalNotExpired);
+
+    context.warp_to_slot(2 * DELAY_SLOTS).unwrap();
+    let instruction = instruction::approve_treasury_withdrawal(&program_id(), &approver.pubkey(), 1).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&approver]).await, CoinbackedError::WithdrawalProposalExpired);
+
+    // rent only goes back to proposer
+    let instruction = instruction::close_expired_treasury_withdrawal(&program_id(), &closer.pubkey(), &approver.pubkey(), 1).unwrap();
+    assert_error(process(&mut context, &[instruction], &[&closer]).await, CoinbackedError::InvalidRecipientAccount);
+
+    // any signer may close
+    process(&mut context, &[close], &[&closer]).await.unwrap();
+    assert_eq!(get_lamports(&mut context, &proposer.pubkey()).await, WALLET_LAMPORTS);
+    assert!(context.banks_client.get_account(proposal).await.unwrap().is_none());
+
+    // proposal id is free again
+    let instruction = instruction::propose_treasury_withdrawal(&program_id(), &proposer.pubkey(), &recipient, 1, WITHDRAWAL, EXPIRY_SLOT).unwrap();
+    process(&mut context, &[instruction], &[&proposer]).await.unwrap();
+    assert!(context.banks_client.get_account(proposal).await.unwrap().is_some());
+}